@@ -0,0 +1,172 @@
+//! Proc macro attributes for async-std.
+//!
+//! These attributes are re-exported by `async-std` when its `attributes` Cargo feature is enabled,
+//! and are meant to be used as `#[async_std::main]`, `#[async_std::test]` and
+//! `#[async_std::bench]`.
+
+#![recursion_limit = "512"]
+#![warn(missing_docs, missing_debug_implementations, rust_2018_idioms)]
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+
+/// Enables an async main function.
+///
+/// The body of `main` is run on the async-std executor, and the current thread blocks until it
+/// completes.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[async_std::main]
+/// async fn main() -> std::io::Result<()> {
+///     println!("Hello, world!");
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn main(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::ItemFn);
+
+    let ret = &input.sig.output;
+    let inputs = &input.sig.inputs;
+    let name = &input.sig.ident;
+    let body = &input.block;
+    let attrs = &input.attrs;
+
+    if name != "main" {
+        return error(
+            name.span(),
+            "only the main function can be tagged with #[async_std::main]",
+        );
+    }
+
+    if input.sig.asyncness.is_none() {
+        return error(
+            input.span(),
+            "the async keyword is missing from the function declaration",
+        );
+    }
+
+    let result = quote! {
+        fn main() #ret {
+            #(#attrs)*
+            async fn main(#inputs) #ret {
+                #body
+            }
+
+            async_std::task::block_on(async {
+                main().await
+            })
+        }
+    };
+
+    result.into()
+}
+
+/// Enables an async test function.
+///
+/// The body of the test is run on the async-std executor, and the test thread blocks until it
+/// completes.
+///
+/// # Examples
+///
+/// ```ignore
+/// #[async_std::test]
+/// async fn my_test() -> std::io::Result<()> {
+///     assert_eq!(2 * 2, 4);
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn test(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::ItemFn);
+
+    let ret = &input.sig.output;
+    let name = &input.sig.ident;
+    let body = &input.block;
+    let attrs = &input.attrs;
+    let vis = &input.vis;
+
+    if input.sig.asyncness.is_none() {
+        return error(
+            input.span(),
+            "the async keyword is missing from the function declaration",
+        );
+    }
+
+    if !input.sig.inputs.is_empty() {
+        return error(
+            input.sig.inputs.span(),
+            "async tests don't take any arguments",
+        );
+    }
+
+    let result = quote! {
+        #[test]
+        #(#attrs)*
+        #vis fn #name() #ret {
+            async_std::task::block_on(async { #body })
+        }
+    };
+
+    result.into()
+}
+
+/// Enables an async benchmark function.
+///
+/// Each iteration of the benchmark runs the body on the async-std executor and blocks until it
+/// completes. Benchmarks require a nightly compiler with `#![feature(test)]` enabled.
+///
+/// # Examples
+///
+/// ```ignore
+/// #![feature(test)]
+/// extern crate test;
+///
+/// #[async_std::bench]
+/// async fn bench() {
+///     println!("hello world");
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn bench(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item as syn::ItemFn);
+
+    let name = &input.sig.ident;
+    let body = &input.block;
+    let attrs = &input.attrs;
+    let vis = &input.vis;
+
+    if input.sig.asyncness.is_none() {
+        return error(
+            input.span(),
+            "the async keyword is missing from the function declaration",
+        );
+    }
+
+    if !input.sig.inputs.is_empty() {
+        return error(
+            input.sig.inputs.span(),
+            "async benchmarks don't take any arguments",
+        );
+    }
+
+    let result = quote! {
+        #[bench]
+        #(#attrs)*
+        #vis fn #name(b: &mut test::Bencher) {
+            b.iter(|| {
+                async_std::task::block_on(async { #body })
+            });
+        }
+    };
+
+    result.into()
+}
+
+/// Returns a compile error pointing at `span`.
+fn error(span: proc_macro2::Span, message: &str) -> TokenStream {
+    syn::Error::new(span, message).to_compile_error().into()
+}
@@ -0,0 +1,254 @@
+use std::io::Write as _;
+use std::pin::Pin;
+
+use crate::fs::tempfile::create_unique;
+use crate::fs::File;
+use crate::io::{self, Write};
+use crate::path::{Path, PathBuf};
+use crate::task::{blocking, Context, Poll};
+
+/// Writes a slice as the entire contents of a file, atomically replacing it.
+///
+/// The contents are written to a temporary file in the same directory, synchronized to disk, and
+/// then renamed over `path`. Other processes and a crash at any point observe either the old
+/// contents or the new contents, but never a partially written file. If `path` already exists,
+/// its permissions are carried over to the new file.
+///
+/// Use [`AtomicFile`] to write the contents piece by piece.
+///
+/// [`AtomicFile`]: struct.AtomicFile.html
+///
+/// # Errors
+///
+/// An error will be returned in the following situations:
+///
+/// * The file's parent directory does not exist.
+/// * The current process lacks permissions to create files in the parent directory.
+/// * Some other I/O error occurred.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs;
+///
+/// fs::write_atomic("config.toml", b"port = 8080\n").await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn write_atomic<P: AsRef<Path>, C: AsRef<[u8]>>(path: P, contents: C) -> io::Result<()> {
+    let path = path.as_ref().to_owned();
+    let contents = contents.as_ref().to_owned();
+    blocking::spawn(move || {
+        let (tmp, mut file) = create_temp(&path)?;
+        let res = file
+            .write_all(&contents)
+            .and_then(|()| file.sync_all())
+            .and_then(|()| persist(&tmp, &path));
+        if res.is_err() {
+            let _ = std::fs::remove_file(&tmp);
+        }
+        res
+    })
+    .await
+}
+
+/// A file that atomically replaces its target once committed.
+///
+/// Data is written to a temporary file in the same directory as the target. Calling [`commit`]
+/// synchronizes the data to disk and renames the temporary file over the target, so the target
+/// is never observed half-written. Dropping an `AtomicFile` without committing it removes the
+/// temporary file in the background and leaves the target untouched.
+///
+/// [`commit`]: #method.commit
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs::AtomicFile;
+/// use async_std::prelude::*;
+///
+/// let mut file = AtomicFile::create("state.json").await?;
+/// file.write_all(b"{\"version\": ").await?;
+/// file.write_all(b"2}").await?;
+/// file.commit().await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct AtomicFile {
+    /// The temporary file.
+    file: File,
+
+    /// The path to the temporary file, or `None` if it has been committed.
+    tmp: Option<PathBuf>,
+
+    /// The path to replace on commit.
+    target: PathBuf,
+}
+
+impl AtomicFile {
+    /// Creates a temporary file that replaces `path` once committed.
+    ///
+    /// If `path` already exists, its permissions are carried over to the new file.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned in the following situations:
+    ///
+    /// * The file's parent directory does not exist.
+    /// * The current process lacks permissions to create files in the parent directory.
+    /// * Some other I/O error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::AtomicFile;
+    ///
+    /// let file = AtomicFile::create("state.json").await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn create<P: AsRef<Path>>(path: P) -> io::Result<AtomicFile> {
+        let target = path.as_ref().to_owned();
+        let (tmp, file, target) = blocking::spawn(move || {
+            let (tmp, file) = create_temp(&target)?;
+            io::Result::Ok((tmp, file, target))
+        })
+        .await?;
+
+        Ok(AtomicFile {
+            file: file.into(),
+            tmp: Some(tmp),
+            target,
+        })
+    }
+
+    /// Returns the path that is replaced on commit.
+    pub fn path(&self) -> &Path {
+        &self.target
+    }
+
+    /// Synchronizes the written data to disk and replaces the target with it.
+    ///
+    /// If this method fails, the temporary file is removed and the target is left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::AtomicFile;
+    /// use async_std::prelude::*;
+    ///
+    /// let mut file = AtomicFile::create("state.json").await?;
+    /// file.write_all(b"{}").await?;
+    /// file.commit().await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn commit(mut self) -> io::Result<()> {
+        let tmp = self.tmp.take().unwrap();
+
+        let res = self.file.sync_all().await;
+        let target = self.target.clone();
+        blocking::spawn(move || {
+            let res = res.and_then(|()| persist(&tmp, &target));
+            if res.is_err() {
+                let _ = std::fs::remove_file(&tmp);
+            }
+            res
+        })
+        .await
+    }
+}
+
+impl Write for AtomicFile {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.file).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.file).poll_flush(cx)
+    }
+
+    fn poll_close(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.file).poll_close(cx)
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if let Some(tmp) = self.tmp.take() {
+            // The join handle is dropped, which lets the removal finish in the background.
+            blocking::spawn(move || {
+                let _ = std::fs::remove_file(tmp);
+            });
+        }
+    }
+}
+
+/// Creates a temporary file next to `target`.
+///
+/// If `target` exists, the temporary file gets its permissions.
+fn create_temp(target: &Path) -> io::Result<(PathBuf, std::fs::File)> {
+    let dir = match target.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    let (tmp, file) = create_unique(dir, |path| {
+        options.open(path).map(|file| (path.to_owned(), file))
+    })?;
+
+    match std::fs::metadata(target) {
+        Ok(metadata) => {
+            if let Err(err) = file.set_permissions(metadata.permissions()) {
+                let _ = std::fs::remove_file(&tmp);
+                return Err(err);
+            }
+        }
+        Err(ref err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(err);
+        }
+    }
+
+    Ok((tmp, file))
+}
+
+/// Renames the synchronized temporary file over the target.
+fn persist(tmp: &Path, target: &Path) -> io::Result<()> {
+    std::fs::rename(tmp, target)?;
+
+    // Synchronize the directory so that the rename itself survives a crash.
+    #[cfg(unix)]
+    {
+        if let Some(dir) = target.parent() {
+            let dir = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            };
+            std::fs::File::open(dir)?.sync_all()?;
+        }
+    }
+
+    Ok(())
+}
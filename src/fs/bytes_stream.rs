@@ -0,0 +1,137 @@
+use std::pin::Pin;
+
+use crate::fs::File;
+use crate::io::{self, Read};
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A stream of chunks read from a file.
+///
+/// This stream is created by the [`bytes_stream`] method on [`File`]. See its documentation for
+/// more details.
+///
+/// [`bytes_stream`]: struct.File.html#method.bytes_stream
+/// [`File`]: struct.File.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct BytesStream {
+    /// The file being read.
+    file: File,
+
+    /// The size of each chunk.
+    chunk_size: usize,
+
+    /// The chunk being filled.
+    buf: Vec<u8>,
+
+    /// The number of bytes in `buf` that have been filled.
+    filled: usize,
+
+    /// The number of bytes yielded so far.
+    progress: u64,
+
+    /// Whether the end of the file has been reached.
+    done: bool,
+}
+
+impl BytesStream {
+    pub(crate) fn new(file: File, chunk_size: usize) -> BytesStream {
+        assert!(chunk_size > 0, "chunk size must be non-zero");
+        BytesStream {
+            file,
+            chunk_size,
+            buf: Vec::new(),
+            filled: 0,
+            progress: 0,
+            done: false,
+        }
+    }
+
+    /// Returns the total number of bytes yielded so far.
+    pub fn progress(&self) -> u64 {
+        self.progress
+    }
+
+    /// Consumes the stream and returns the underlying file.
+    ///
+    /// Data that was read but not yet yielded is lost.
+    pub fn into_inner(self) -> File {
+        self.file
+    }
+}
+
+impl Stream for BytesStream {
+    type Item = io::Result<Chunk>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.done {
+            return Poll::Ready(None);
+        }
+        if this.buf.is_empty() {
+            this.buf = vec![0; this.chunk_size];
+        }
+
+        // Fill the chunk completely so that only the last one can be shorter.
+        while this.filled < this.chunk_size {
+            let buf = &mut this.buf[this.filled..];
+            match futures_core::ready!(Pin::new(&mut this.file).poll_read(cx, buf)) {
+                Ok(0) => {
+                    this.done = true;
+                    break;
+                }
+                Ok(n) => this.filled += n,
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+
+        if this.filled == 0 {
+            return Poll::Ready(None);
+        }
+
+        let mut data = std::mem::take(&mut this.buf);
+        data.truncate(this.filled);
+        this.filled = 0;
+        this.progress += data.len() as u64;
+
+        Poll::Ready(Some(Ok(Chunk {
+            data,
+            progress: this.progress,
+        })))
+    }
+}
+
+/// A chunk of data yielded by [`BytesStream`].
+///
+/// [`BytesStream`]: struct.BytesStream.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Debug)]
+pub struct Chunk {
+    data: Vec<u8>,
+    progress: u64,
+}
+
+impl Chunk {
+    /// Returns the data in this chunk.
+    pub fn bytes(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Consumes the chunk and returns its data.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    /// Returns the total number of bytes read from the file, including this chunk.
+    pub fn progress(&self) -> u64 {
+        self.progress
+    }
+}
+
+impl AsRef<[u8]> for Chunk {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
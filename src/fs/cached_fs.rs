@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::fs::Metadata;
+use crate::io;
+use crate::path::{Path, PathBuf};
+use crate::task::blocking;
+
+/// The default maximum number of cached entries.
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// A handle that caches file metadata for a short time.
+///
+/// Every call to [`fs::metadata`] costs a trip to the blocking pool. Servers that look up the
+/// same paths over and over can use a `CachedFs` to answer repeated lookups from memory. Cached
+/// metadata is reused until it's older than the configured time-to-live, so changes to the
+/// filesystem may go unnoticed for that long. Errors are never cached.
+///
+/// Cloning a `CachedFs` creates another handle to the same cache.
+///
+/// [`fs::metadata`]: fn.metadata.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::fs::CachedFs;
+///
+/// let cache = CachedFs::new(Duration::from_secs(1));
+///
+/// // The second lookup is answered from the cache.
+/// let a = cache.metadata("index.html").await?;
+/// let b = cache.metadata("index.html").await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Debug)]
+pub struct CachedFs(Arc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+    /// How long cached metadata stays valid.
+    ttl: Duration,
+
+    /// The maximum number of cached entries.
+    capacity: usize,
+
+    /// Cached metadata and the time it was fetched.
+    entries: Mutex<HashMap<PathBuf, (Instant, Metadata)>>,
+}
+
+impl CachedFs {
+    /// Creates a cache that keeps metadata for `ttl`.
+    ///
+    /// The cache holds up to 1024 entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use async_std::fs::CachedFs;
+    ///
+    /// let cache = CachedFs::new(Duration::from_millis(500));
+    /// ```
+    pub fn new(ttl: Duration) -> CachedFs {
+        CachedFs::with_capacity(ttl, DEFAULT_CAPACITY)
+    }
+
+    /// Creates a cache that keeps metadata for `ttl` and holds up to `capacity` entries.
+    ///
+    /// When the cache is full, expired entries are dropped first and then the oldest ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use async_std::fs::CachedFs;
+    ///
+    /// let cache = CachedFs::with_capacity(Duration::from_secs(1), 64);
+    /// ```
+    pub fn with_capacity(ttl: Duration, capacity: usize) -> CachedFs {
+        CachedFs(Arc::new(Inner {
+            ttl,
+            capacity,
+            entries: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    /// Reads metadata for a path, using the cache if possible.
+    ///
+    /// Symbolic links are followed, like with [`fs::metadata`].
+    ///
+    /// [`fs::metadata`]: fn.metadata.html
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned in the following situations:
+    ///
+    /// * `path` does not point to an existing file or directory.
+    /// * The current process lacks permissions to read metadata for the path.
+    /// * Some other I/O error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::fs::CachedFs;
+    ///
+    /// let cache = CachedFs::new(Duration::from_secs(1));
+    /// println!("{}", cache.metadata("index.html").await?.len());
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn metadata<P: AsRef<Path>>(&self, path: P) -> io::Result<Metadata> {
+        let path = path.as_ref();
+
+        if let Some((time, metadata)) = self.0.entries.lock().unwrap().get(path) {
+            if time.elapsed() < self.0.ttl {
+                return Ok(metadata.clone());
+            }
+        }
+
+        let path = path.to_owned();
+        let (path, metadata) = blocking::spawn(move || {
+            let res = std::fs::metadata(&path);
+            res.map(|metadata| (path, metadata))
+        })
+        .await?;
+
+        self.insert(path, metadata.clone());
+        Ok(metadata)
+    }
+
+    /// Removes a path from the cache.
+    ///
+    /// Call this method after modifying a file to make sure the next lookup sees the change.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use async_std::fs::CachedFs;
+    ///
+    /// let cache = CachedFs::new(Duration::from_secs(1));
+    /// cache.invalidate("index.html");
+    /// ```
+    pub fn invalidate<P: AsRef<Path>>(&self, path: P) {
+        self.0.entries.lock().unwrap().remove(path.as_ref());
+    }
+
+    /// Removes all paths from the cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    ///
+    /// use async_std::fs::CachedFs;
+    ///
+    /// let cache = CachedFs::new(Duration::from_secs(1));
+    /// cache.clear();
+    /// ```
+    pub fn clear(&self) {
+        self.0.entries.lock().unwrap().clear();
+    }
+
+    /// Inserts freshly fetched metadata, making room if the cache is full.
+    fn insert(&self, path: PathBuf, metadata: Metadata) {
+        if self.0.capacity == 0 {
+            return;
+        }
+
+        let mut entries = self.0.entries.lock().unwrap();
+        if entries.len() >= self.0.capacity && !entries.contains_key(&path) {
+            let ttl = self.0.ttl;
+            entries.retain(|_, (time, _)| time.elapsed() < ttl);
+
+            if entries.len() >= self.0.capacity {
+                let oldest = entries
+                    .iter()
+                    .min_by_key(|(_, (time, _))| *time)
+                    .map(|(path, _)| path.clone());
+                if let Some(oldest) = oldest {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(path, (Instant::now(), metadata));
+    }
+}
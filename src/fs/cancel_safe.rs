@@ -0,0 +1,437 @@
+use std::fmt;
+use std::io::{Read as _, Write as _};
+use std::mem;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::fs::tempfile::create_unique;
+use crate::io;
+use crate::path::{Path, PathBuf};
+use crate::task::blocking;
+
+/// The size of chunks copied between checks for cancellation.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Returns a handle for filesystem operations that respect cancellation.
+///
+/// Regular filesystem functions run on the blocking pool and keep running to completion even if
+/// their future is dropped. Operations started through the returned [`CancelSafe`] handle
+/// instead behave as follows when their future is dropped:
+///
+/// * If the operation hasn't started yet, it never starts.
+/// * If the operation is running, it stops at the next safe point and cleans up after itself
+///   where possible. Whatever state it ends in is reported to the [`on_cancel`] hook.
+/// * If the operation has already finished, its result is reported to the [`on_cancel`] hook.
+///
+/// [`copy`] and [`remove_dir_all`] are provided, and other operations can be run with [`run`].
+///
+/// [`CancelSafe`]: struct.CancelSafe.html
+/// [`on_cancel`]: struct.CancelSafe.html#method.on_cancel
+/// [`copy`]: struct.CancelSafe.html#method.copy
+/// [`remove_dir_all`]: struct.CancelSafe.html#method.remove_dir_all
+/// [`run`]: struct.CancelSafe.html#method.run
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::fs;
+/// use async_std::future;
+///
+/// // If the copy takes too long, it's stopped and no partial file is left behind.
+/// let handle = fs::cancel_safe();
+/// let copy = handle.copy("large.iso", "backup.iso");
+/// let res = future::timeout(Duration::from_secs(10), copy).await;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn cancel_safe() -> CancelSafe {
+    CancelSafe { hook: None }
+}
+
+/// A handle for filesystem operations that respect cancellation.
+///
+/// This type is created by [`cancel_safe`]. See its documentation for more details.
+///
+/// [`cancel_safe`]: fn.cancel_safe.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone)]
+pub struct CancelSafe {
+    /// Called when an operation ends after its future was dropped.
+    hook: Option<Arc<dyn Fn(Cancelled) + Send + Sync>>,
+}
+
+impl CancelSafe {
+    /// Sets a hook that is called when a cancelled operation stops.
+    ///
+    /// The hook runs on the blocking pool once an operation whose future was dropped has stopped,
+    /// or on the thread dropping the future if the operation had already finished. It is not
+    /// called for operations that were cancelled before they started.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::fs;
+    ///
+    /// let mut handle = fs::cancel_safe();
+    /// handle.on_cancel(|cancelled| {
+    ///     eprintln!("{} stopped: {:?}", cancelled.operation(), cancelled.result());
+    /// });
+    /// ```
+    pub fn on_cancel<F>(&mut self, hook: F) -> &mut Self
+    where
+        F: Fn(Cancelled) + Send + Sync + 'static,
+    {
+        self.hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Copies the contents and permissions of a file to a new location.
+    ///
+    /// The contents are copied into a temporary file next to `to`, which is renamed over `to` once
+    /// the copy is complete. If the operation is cancelled, the temporary file is removed and `to`
+    /// is left untouched.
+    ///
+    /// Unlike [`fs::copy`], this method copies the data in chunks through user space, which can be
+    /// slower but lets it stop between chunks.
+    ///
+    /// [`fs::copy`]: fn.copy.html
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned in the following situations:
+    ///
+    /// * `from` does not point to an existing file.
+    /// * The current process lacks permissions to read `from` or write `to`.
+    /// * Some other I/O error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs;
+    ///
+    /// let num_bytes = fs::cancel_safe().copy("a.txt", "b.txt").await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn copy<P: AsRef<Path>, Q: AsRef<Path>>(&self, from: P, to: Q) -> io::Result<u64> {
+        let from = from.as_ref().to_owned();
+        let to = to.as_ref().to_owned();
+        self.run("copy", to.clone(), move |cancellation| {
+            copy(&from, &to, cancellation)
+        })
+        .await
+    }
+
+    /// Removes a directory and all of its contents.
+    ///
+    /// If the operation is cancelled, it stops before removing the next entry. Entries that were
+    /// already removed stay removed.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned in the following situations:
+    ///
+    /// * `path` does not point to an existing directory.
+    /// * The current process lacks permissions to remove the directory or its contents.
+    /// * Some other I/O error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs;
+    ///
+    /// fs::cancel_safe().remove_dir_all("./some/directory").await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn remove_dir_all<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref().to_owned();
+        self.run("remove_dir_all", path.clone(), move |cancellation| {
+            remove_dir_all(&path, cancellation)
+        })
+        .await
+    }
+
+    /// Runs a custom operation on the blocking pool.
+    ///
+    /// The operation is called with a [`Cancellation`] that tells it whether its future was
+    /// dropped, so that it can stop at safe points. `operation` and `path` describe it in the
+    /// report passed to the [`on_cancel`] hook.
+    ///
+    /// [`Cancellation`]: struct.Cancellation.html
+    /// [`on_cancel`]: #method.on_cancel
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use std::io::Write;
+    ///
+    /// use async_std::fs;
+    ///
+    /// fs::cancel_safe()
+    ///     .run("write", "log.txt", |cancellation| {
+    ///         let mut file = std::fs::File::create("log.txt")?;
+    ///         for i in 0..1000 {
+    ///             cancellation.check()?;
+    ///             writeln!(file, "line {}", i)?;
+    ///         }
+    ///         Ok(())
+    ///     })
+    ///     .await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn run<P, F, T>(&self, operation: &'static str, path: P, f: F) -> io::Result<T>
+    where
+        P: AsRef<Path>,
+        F: FnOnce(&Cancellation) -> io::Result<T> + Send + 'static,
+        T: Send + 'static,
+    {
+        let shared = Arc::new(Shared {
+            cancellation: Cancellation(AtomicBool::new(false)),
+            progress: Mutex::new(Progress::Running),
+            hook: self.hook.clone(),
+            operation,
+            path: path.as_ref().to_owned(),
+        });
+        let guard = ReportOnDrop(shared.clone());
+
+        let res = blocking::spawn(move || {
+            // The future was dropped before the operation got to start.
+            if shared.cancellation.is_cancelled() {
+                return Err(cancelled_error());
+            }
+
+            let res = f(&shared.cancellation);
+
+            let mut progress = shared.progress.lock().unwrap();
+            if shared.cancellation.is_cancelled() {
+                drop(progress);
+                shared.report(res.map(drop));
+                return Err(cancelled_error());
+            }
+
+            // The future may still be dropped before it receives the result, so keep a copy.
+            *progress = Progress::Finished(match &res {
+                Ok(_) => Ok(()),
+                Err(err) => Err(io::Error::new(err.kind(), err.to_string())),
+            });
+            res
+        })
+        .await;
+
+        *guard.0.progress.lock().unwrap() = Progress::Delivered;
+        res
+    }
+}
+
+impl fmt::Debug for CancelSafe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CancelSafe")
+            .field("hook", &self.hook.is_some())
+            .finish()
+    }
+}
+
+/// A report about an operation that stopped after its future was dropped.
+///
+/// Reports are passed to the hook set with [`CancelSafe::on_cancel`].
+///
+/// [`CancelSafe::on_cancel`]: struct.CancelSafe.html#method.on_cancel
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct Cancelled {
+    operation: &'static str,
+    path: PathBuf,
+    result: io::Result<()>,
+}
+
+impl Cancelled {
+    /// Returns the name of the operation, like `"copy"`.
+    pub fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// Returns the path the operation was modifying.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns how the operation ended.
+    ///
+    /// An error of kind [`Interrupted`] means the operation stopped before completing. Any other
+    /// result is what the operation returned after it ran to completion or failed.
+    ///
+    /// [`Interrupted`]: ../io/enum.ErrorKind.html#variant.Interrupted
+    pub fn result(&self) -> &io::Result<()> {
+        &self.result
+    }
+
+    /// Consumes the report and returns how the operation ended.
+    pub fn into_result(self) -> io::Result<()> {
+        self.result
+    }
+}
+
+/// Tells an operation started with [`CancelSafe::run`] whether its future was dropped.
+///
+/// [`CancelSafe::run`]: struct.CancelSafe.html#method.run
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct Cancellation(AtomicBool);
+
+impl Cancellation {
+    /// Returns `true` if the future of the operation was dropped.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Returns an error of kind [`Interrupted`] if the future of the operation was dropped.
+    ///
+    /// [`Interrupted`]: ../io/enum.ErrorKind.html#variant.Interrupted
+    pub fn check(&self) -> io::Result<()> {
+        if self.is_cancelled() {
+            Err(cancelled_error())
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The state of an operation shared between its future and the blocking pool.
+struct Shared {
+    cancellation: Cancellation,
+    progress: Mutex<Progress>,
+    hook: Option<Arc<dyn Fn(Cancelled) + Send + Sync>>,
+    operation: &'static str,
+    path: PathBuf,
+}
+
+impl Shared {
+    /// Passes the result of a cancelled operation to the hook.
+    fn report(&self, result: io::Result<()>) {
+        if let Some(hook) = &self.hook {
+            hook(Cancelled {
+                operation: self.operation,
+                path: self.path.clone(),
+                result,
+            });
+        }
+    }
+}
+
+/// How far an operation has got.
+enum Progress {
+    /// The operation hasn't finished yet.
+    Running,
+
+    /// The operation has finished, but its future hasn't received the result yet.
+    Finished(io::Result<()>),
+
+    /// The future has received the result or has been dropped.
+    Delivered,
+}
+
+/// Cancels the operation when its future is dropped, and reports the result if it has already
+/// finished.
+struct ReportOnDrop(Arc<Shared>);
+
+impl Drop for ReportOnDrop {
+    fn drop(&mut self) {
+        let progress = {
+            let mut progress = self.0.progress.lock().unwrap();
+            self.0.cancellation.0.store(true, Ordering::SeqCst);
+            mem::replace(&mut *progress, Progress::Delivered)
+        };
+        if let Progress::Finished(result) = progress {
+            self.0.report(result);
+        }
+    }
+}
+
+fn cancelled_error() -> io::Error {
+    io::Error::new(io::ErrorKind::Interrupted, "operation was cancelled")
+}
+
+fn copy(from: &Path, to: &Path, cancellation: &Cancellation) -> io::Result<u64> {
+    let mut reader = std::fs::File::open(from)?;
+    let metadata = reader.metadata()?;
+    if !metadata.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the source path is not an existing regular file",
+        ));
+    }
+
+    let dir = match to.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    let (tmp, mut writer) = create_unique(dir, |path| {
+        options.open(path).map(|file| (path.to_owned(), file))
+    })?;
+
+    let mut copy_to_tmp = || {
+        let mut buf = vec![0; CHUNK_SIZE];
+        let mut total = 0;
+        loop {
+            cancellation.check()?;
+            let n = match reader.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            writer.write_all(&buf[..n])?;
+            total += n as u64;
+        }
+        writer.set_permissions(metadata.permissions())?;
+        cancellation.check()?;
+        std::fs::rename(&tmp, to)?;
+        Ok(total)
+    };
+
+    let res = copy_to_tmp();
+    if res.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    res
+}
+
+fn remove_dir_all(path: &Path, cancellation: &Cancellation) -> io::Result<()> {
+    // Symbolic links are removed without following them.
+    if std::fs::symlink_metadata(path)?.file_type().is_symlink() {
+        return std::fs::remove_file(path);
+    }
+
+    for entry in std::fs::read_dir(path)? {
+        cancellation.check()?;
+        let entry = entry?;
+        let path: PathBuf = entry.path().into();
+        if entry.file_type()?.is_dir() {
+            remove_dir_all(&path, cancellation)?;
+        } else {
+            std::fs::remove_file(path)?;
+        }
+    }
+
+    cancellation.check()?;
+    std::fs::remove_dir(path)
+}
@@ -13,6 +13,12 @@ use crate::task::blocking;
 /// If you're working with open [`File`]s and want to copy contents through those types, use the
 /// [`io::copy`] function.
 ///
+/// On Linux, this function first tries to clone the file with the `FICLONE` ioctl, which shares
+/// data blocks between the files on filesystems like Btrfs and XFS and completes instantly
+/// regardless of file size. If cloning is not possible, it copies the data like
+/// [`std::fs::copy`], which lets the kernel do the copying via `copy_file_range(2)` on Linux,
+/// `fcopyfile(3)` on macOS, and `CopyFileEx` on Windows.
+///
 /// This function is an async version of [`std::fs::copy`].
 ///
 /// [`File`]: struct.File.html
@@ -41,5 +47,80 @@ use crate::task::blocking;
 pub async fn copy<P: AsRef<Path>, Q: AsRef<Path>>(from: P, to: Q) -> io::Result<u64> {
     let from = from.as_ref().to_owned();
     let to = to.as_ref().to_owned();
-    blocking::spawn(move || std::fs::copy(&from, &to)).await
+    blocking::spawn(move || sys::copy(&from, &to)).await
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sys {
+    use std::os::unix::io::AsRawFd;
+
+    use crate::io;
+    use crate::path::Path;
+
+    /// The `FICLONE` ioctl from `linux/fs.h`, which is `_IOW(0x94, 9, int)`.
+    ///
+    /// The direction bits of ioctl numbers are encoded differently on some architectures.
+    #[cfg(not(any(
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "sparc",
+        target_arch = "sparc64"
+    )))]
+    const FICLONE: libc::c_ulong = 0x4004_9409;
+    #[cfg(any(
+        target_arch = "powerpc",
+        target_arch = "powerpc64",
+        target_arch = "mips",
+        target_arch = "mips64",
+        target_arch = "sparc",
+        target_arch = "sparc64"
+    ))]
+    const FICLONE: libc::c_ulong = 0x8004_9409;
+
+    pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
+        // Any error from cloning is either reported again by the regular copy or means the
+        // filesystem doesn't support cloning.
+        clone(from, to).or_else(|_| std::fs::copy(from, to))
+    }
+
+    /// Clones `from` into `to`, sharing the underlying data blocks.
+    fn clone(from: &Path, to: &Path) -> io::Result<u64> {
+        let reader = std::fs::File::open(from)?;
+        let metadata = reader.metadata()?;
+        if !metadata.is_file() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the source path is not an existing regular file",
+            ));
+        }
+
+        // Don't truncate `to` yet, so that its contents are left alone if cloning fails and it
+        // turns out to be the same file as `from`.
+        let writer = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(to)?;
+        let ret = unsafe { libc::ioctl(writer.as_raw_fd(), FICLONE as _, reader.as_raw_fd()) };
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // Cloning replaces the contents of `to`, but leaves it longer than `from` if it was.
+        writer.set_len(metadata.len())?;
+        writer.set_permissions(metadata.permissions())?;
+        Ok(metadata.len())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+mod sys {
+    use crate::io;
+    use crate::path::Path;
+
+    pub fn copy(from: &Path, to: &Path) -> io::Result<u64> {
+        std::fs::copy(from, to)
+    }
 }
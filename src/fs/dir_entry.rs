@@ -15,12 +15,31 @@ use crate::task::blocking;
 ///
 /// [`read_dir`]: fn.read_dir.html
 /// [`std::fs::DirEntry`]: https://doc.rust-lang.org/std/fs/struct.DirEntry.html
-pub struct DirEntry(Arc<std::fs::DirEntry>);
+pub struct DirEntry {
+    /// The inner entry.
+    inner: Arc<std::fs::DirEntry>,
+
+    /// The file type, if it was fetched while reading the directory.
+    file_type: Option<FileType>,
+
+    /// The metadata, if it was fetched while reading the directory.
+    metadata: Option<Metadata>,
+}
 
 impl DirEntry {
     /// Creates an asynchronous `DirEntry` from a synchronous one.
-    pub(crate) fn new(inner: std::fs::DirEntry) -> DirEntry {
-        DirEntry(Arc::new(inner))
+    ///
+    /// The file type and metadata may have already been fetched while reading the directory.
+    pub(crate) fn new(
+        inner: std::fs::DirEntry,
+        file_type: Option<FileType>,
+        metadata: Option<Metadata>,
+    ) -> DirEntry {
+        DirEntry {
+            inner: Arc::new(inner),
+            file_type,
+            metadata,
+        }
     }
 
     /// Returns the full path to this entry.
@@ -48,7 +67,7 @@ impl DirEntry {
     /// # Ok(()) }) }
     /// ```
     pub fn path(&self) -> PathBuf {
-        self.0.path().into()
+        self.inner.path().into()
     }
 
     /// Reads the metadata for this entry.
@@ -86,7 +105,10 @@ impl DirEntry {
     /// # Ok(()) }) }
     /// ```
     pub async fn metadata(&self) -> io::Result<Metadata> {
-        let inner = self.0.clone();
+        if let Some(metadata) = &self.metadata {
+            return Ok(metadata.clone());
+        }
+        let inner = self.inner.clone();
         blocking::spawn(move || inner.metadata()).await
     }
 
@@ -124,7 +146,10 @@ impl DirEntry {
     /// # Ok(()) }) }
     /// ```
     pub async fn file_type(&self) -> io::Result<FileType> {
-        let inner = self.0.clone();
+        if let Some(file_type) = self.file_type {
+            return Ok(file_type);
+        }
+        let inner = self.inner.clone();
         blocking::spawn(move || inner.file_type()).await
     }
 
@@ -148,7 +173,7 @@ impl DirEntry {
     /// # Ok(()) }) }
     /// ```
     pub fn file_name(&self) -> OsString {
-        self.0.file_name()
+        self.inner.file_name()
     }
 }
 
@@ -163,7 +188,7 @@ cfg_unix! {
 
     impl DirEntryExt for DirEntry {
         fn ino(&self) -> u64 {
-            self.0.ino()
+            self.inner.ino()
         }
     }
 }
@@ -57,6 +57,16 @@ use crate::task::{self, blocking, Context, Poll, Waker};
 /// #
 /// # Ok(()) }) }
 /// ```
+///
+/// # Direct I/O
+///
+/// On Linux, files can be opened with `O_DIRECT` through [`OpenOptionsExt::custom_flags`] to
+/// bypass the page cache. Direct I/O requires aligned memory buffers, which `File` takes care of
+/// internally. The sizes of reads and writes and their offsets into the file must still be
+/// multiples of the logical block size of the device, so read and write in multiples of 4096
+/// bytes and avoid seeking to unaligned positions.
+///
+/// [`OpenOptionsExt::custom_flags`]: ../os/unix/fs/trait.OpenOptionsExt.html#tymethod.custom_flags
 pub struct File {
     /// A reference to the inner file.
     file: Arc<std::fs::File>,
@@ -134,6 +144,88 @@ impl File {
         Ok(file.into())
     }
 
+    /// Opens a file in append mode, creating it if it doesn't exist.
+    ///
+    /// All writes go to the end of the file. Each buffer passed to a single write call is written
+    /// to the file in one piece, so records written by concurrent appenders don't interleave as
+    /// long as each record is written at once, for example through a [`BufWriter`] that is large
+    /// enough to hold it.
+    ///
+    /// See the [`OpenOptions::open`] function for more options.
+    ///
+    /// [`BufWriter`]: ../io/struct.BufWriter.html
+    /// [`OpenOptions::open`]: struct.OpenOptions.html#method.open
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned in the following situations:
+    ///
+    /// * The file's parent directory does not exist.
+    /// * The current process lacks permissions to write to the file.
+    /// * Some other I/O error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::File;
+    /// use async_std::prelude::*;
+    ///
+    /// let mut file = File::append("app.log").await?;
+    /// file.write_all(b"started\n").await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn append<P: AsRef<Path>>(path: P) -> io::Result<File> {
+        crate::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .await
+    }
+
+    /// Creates a new file in write-only mode, failing if it already exists.
+    ///
+    /// Checking for existence and creating the file happen atomically, so this function can be
+    /// used to claim a path without racing against other processes.
+    ///
+    /// See the [`OpenOptions::open`] function for more options.
+    ///
+    /// [`OpenOptions::open`]: struct.OpenOptions.html#method.open
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned in the following situations:
+    ///
+    /// * A file or directory already exists at `path`.
+    /// * The file's parent directory does not exist.
+    /// * The current process lacks permissions to write to the file.
+    /// * Some other I/O error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::File;
+    ///
+    /// let file = File::create_new("server.pid").await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn create_new<P: AsRef<Path>>(path: P) -> io::Result<File> {
+        crate::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .await
+    }
+
     /// Synchronizes OS-internal buffered contents and metadata to disk.
     ///
     /// This function will ensure that all in-memory data reaches the filesystem.
@@ -225,12 +317,7 @@ impl File {
     /// ```
     pub async fn set_len(&self, size: u64) -> io::Result<()> {
         // Invalidate the read cache and flush the write cache before calling `set_len()`.
-        let state = future::poll_fn(|cx| {
-            let state = futures_core::ready!(self.lock.poll_lock(cx));
-            let state = futures_core::ready!(state.poll_unread(cx))?;
-            state.poll_flush(cx)
-        })
-        .await?;
+        let state = self.invalidated_state().await?;
 
         blocking::spawn(move || state.file.set_len(size)).await
     }
@@ -282,6 +369,392 @@ impl File {
         let file = self.file.clone();
         blocking::spawn(move || file.set_permissions(perm)).await
     }
+
+    /// Creates a new handle to the same open file.
+    ///
+    /// Both handles share the underlying file descriptor, including its cursor, but each one
+    /// has its own internal read and write cache. This makes it possible to read and write the
+    /// same file from different tasks. Pending writes are flushed before the handle is cloned.
+    ///
+    /// Because the cursor is shared, reads and writes through one handle move the position seen
+    /// by the other one. Use [`flush`] after writing and avoid relying on the position when
+    /// handles are used concurrently.
+    ///
+    /// This method is an async version of [`std::fs::File::try_clone`].
+    ///
+    /// [`flush`]: ../io/trait.Write.html#method.flush
+    /// [`std::fs::File::try_clone`]: https://doc.rust-lang.org/std/fs/struct.File.html#method.try_clone
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::File;
+    /// use async_std::prelude::*;
+    /// use async_std::task;
+    ///
+    /// let file = File::open("a.txt").await?;
+    /// let mut clone = file.try_clone().await?;
+    ///
+    /// task::spawn(async move {
+    ///     let mut contents = String::new();
+    ///     clone.read_to_string(&mut contents).await
+    /// });
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn try_clone(&self) -> io::Result<File> {
+        let state = self.invalidated_state().await?;
+        let file = state.file.clone();
+        drop(state);
+        blocking::spawn(move || file.try_clone())
+            .await
+            .map(File::from)
+    }
+
+    /// Reads a number of bytes starting from a given offset.
+    ///
+    /// Returns the number of bytes read. Unlike regular reads, this method doesn't use or move
+    /// the cursor, so concurrent readers of the same file don't have to coordinate on a shared
+    /// position. Pending writes are flushed before reading.
+    ///
+    /// This method uses `pread(2)` on Unix. On Windows, it uses `ReadFile` with an offset and
+    /// then restores the cursor, which makes it exclusive with other operations on the handle.
+    ///
+    /// If the file was opened with `O_DIRECT`, `offset` and the length of `buf` must be multiples
+    /// of the logical block size of the device, or else the operating system reports an error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::File;
+    ///
+    /// let file = File::open("index.bin").await?;
+    /// let mut header = [0; 16];
+    /// let n = file.read_at(&mut header, 1024).await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        let (file, direct, state) = self.positioned_io_state().await?;
+        let len = buf.len();
+        let (res, data) = blocking::spawn(move || {
+            let _state = state;
+            let mut data = vec![0; len];
+            let res = if direct {
+                direct::read_at(&file, &mut data, offset)
+            } else {
+                sys::read_at(&file, &mut data, offset)
+            };
+            (res, data)
+        })
+        .await;
+
+        let n = res?;
+        buf[..n].copy_from_slice(&data[..n]);
+        Ok(n)
+    }
+
+    /// Writes a number of bytes starting from a given offset.
+    ///
+    /// Returns the number of bytes written. Unlike regular writes, this method doesn't use or
+    /// move the cursor. Pending writes are flushed before writing.
+    ///
+    /// On Linux, if the file was opened in append mode, the data is appended to the end of the
+    /// file regardless of `offset`.
+    ///
+    /// This method uses `pwrite(2)` on Unix. On Windows, it uses `WriteFile` with an offset and
+    /// then restores the cursor, which makes it exclusive with other operations on the handle.
+    ///
+    /// If the file was opened with `O_DIRECT`, `offset` and the length of `buf` must be multiples
+    /// of the logical block size of the device, or else the operating system reports an error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::OpenOptions;
+    ///
+    /// let file = OpenOptions::new().write(true).open("index.bin").await?;
+    /// file.write_at(b"header", 0).await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn write_at(&self, buf: &[u8], offset: u64) -> io::Result<usize> {
+        let (file, direct, state) = self.positioned_io_state().await?;
+        let data = buf.to_vec();
+        blocking::spawn(move || {
+            let _state = state;
+            if direct {
+                direct::write_at(&file, &data, offset)
+            } else {
+                sys::write_at(&file, &data, offset)
+            }
+        })
+        .await
+    }
+
+    /// Acquires an exclusive advisory lock on the file.
+    ///
+    /// If another handle holds a lock on the same file, this method waits until the lock is
+    /// released. The lock is held until [`unlock`] is called or the file is closed.
+    ///
+    /// Advisory locks only coordinate processes that also use locking. They don't prevent other
+    /// processes from reading or writing the file.
+    ///
+    /// This method uses `flock(2)` on Unix and `LockFileEx` on Windows.
+    ///
+    /// [`unlock`]: #method.unlock
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::File;
+    /// use async_std::prelude::*;
+    ///
+    /// let mut file = File::create("state.txt").await?;
+    /// file.lock_exclusive().await?;
+    /// file.write_all(b"running").await?;
+    /// file.unlock().await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn lock_exclusive(&self) -> io::Result<()> {
+        let state = self.invalidated_state().await?;
+        blocking::spawn(move || sys::lock(&state.file, true)).await
+    }
+
+    /// Acquires a shared advisory lock on the file.
+    ///
+    /// Any number of handles can hold a shared lock at the same time, but not while an exclusive
+    /// lock is held. If an exclusive lock is held, this method waits until it is released.
+    ///
+    /// See [`lock_exclusive`] for more details about advisory locks.
+    ///
+    /// [`lock_exclusive`]: #method.lock_exclusive
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::File;
+    /// use async_std::prelude::*;
+    ///
+    /// let mut file = File::open("state.txt").await?;
+    /// file.lock_shared().await?;
+    ///
+    /// let mut contents = String::new();
+    /// file.read_to_string(&mut contents).await?;
+    /// file.unlock().await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn lock_shared(&self) -> io::Result<()> {
+        let state = self.invalidated_state().await?;
+        blocking::spawn(move || sys::lock(&state.file, false)).await
+    }
+
+    /// Attempts to acquire an exclusive advisory lock on the file without waiting.
+    ///
+    /// Returns `true` if the lock was acquired and `false` if another handle holds a lock on the
+    /// same file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::File;
+    ///
+    /// let file = File::create("state.txt").await?;
+    /// if !file.try_lock_exclusive().await? {
+    ///     println!("the file is locked by another process");
+    /// }
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn try_lock_exclusive(&self) -> io::Result<bool> {
+        let state = self.invalidated_state().await?;
+        blocking::spawn(move || sys::try_lock(&state.file, true)).await
+    }
+
+    /// Attempts to acquire a shared advisory lock on the file without waiting.
+    ///
+    /// Returns `true` if the lock was acquired and `false` if another handle holds an exclusive
+    /// lock on the same file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::File;
+    ///
+    /// let file = File::open("state.txt").await?;
+    /// if !file.try_lock_shared().await? {
+    ///     println!("the file is being written to by another process");
+    /// }
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn try_lock_shared(&self) -> io::Result<bool> {
+        let state = self.invalidated_state().await?;
+        blocking::spawn(move || sys::try_lock(&state.file, false)).await
+    }
+
+    /// Releases an advisory lock held on the file.
+    ///
+    /// Buffered writes are flushed before the lock is released so other processes observe them
+    /// as soon as they acquire the lock.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::File;
+    ///
+    /// let file = File::create("state.txt").await?;
+    /// file.lock_exclusive().await?;
+    /// file.unlock().await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn unlock(&self) -> io::Result<()> {
+        let state = self.invalidated_state().await?;
+        blocking::spawn(move || sys::unlock(&state.file)).await
+    }
+
+    /// Preallocates disk space for the file.
+    ///
+    /// After this method returns, at least `len` bytes of disk space are reserved for the file,
+    /// and the file is at least `len` bytes long. If the file is already longer, it is not
+    /// truncated. Preallocating avoids fragmentation and running out of space in the middle of
+    /// later writes.
+    ///
+    /// This method uses `fallocate(2)` on Linux, `F_PREALLOCATE` on macOS, `posix_fallocate(3)`
+    /// on FreeBSD, and `SetFileInformationByHandle` on Windows. On other platforms, it returns an
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::File;
+    ///
+    /// let file = File::create("log.bin").await?;
+    /// file.allocate(64 * 1024 * 1024).await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn allocate(&self, len: u64) -> io::Result<()> {
+        let state = self.invalidated_state().await?;
+        blocking::spawn(move || sys::allocate(&state.file, len)).await
+    }
+
+    /// Converts the file into a stream of chunks.
+    ///
+    /// The stream reads from the current position and yields chunks of `chunk_size` bytes, except
+    /// for the last one, which may be shorter. Each chunk carries the total number of bytes read
+    /// so far, which makes it easy to report progress. A new chunk is only read once the
+    /// previous one has been taken, so a slow consumer naturally slows down reading.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if `chunk_size` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::File;
+    /// use async_std::prelude::*;
+    ///
+    /// let file = File::open("video.mp4").await?;
+    /// let len = file.metadata().await?.len();
+    ///
+    /// let mut chunks = file.bytes_stream(64 * 1024);
+    /// while let Some(chunk) = chunks.next().await {
+    ///     let chunk = chunk?;
+    ///     println!("sent {} of {} bytes", chunk.progress(), len);
+    /// }
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn bytes_stream(self, chunk_size: usize) -> crate::fs::BytesStream {
+        crate::fs::BytesStream::new(self, chunk_size)
+    }
+
+    /// Acquires the state lock, invalidating the read cache and flushing the write cache.
+    ///
+    /// Operations that change what other handles may observe, like locking or truncating the
+    /// file, must not leave cached data behind.
+    async fn invalidated_state(&self) -> io::Result<LockGuard<State>> {
+        future::poll_fn(|cx| {
+            let state = futures_core::ready!(self.lock.poll_lock(cx));
+            let state = futures_core::ready!(state.poll_unread(cx))?;
+            state.poll_flush(cx)
+        })
+        .await
+    }
+
+    /// Prepares the file for positioned I/O.
+    ///
+    /// Returns the inner file, whether it is in direct mode, and, if positioned I/O moves the
+    /// cursor on this platform, the state lock that must be held for the duration of the
+    /// operation.
+    #[cfg(feature = "unstable")]
+    async fn positioned_io_state(
+        &self,
+    ) -> io::Result<(Arc<std::fs::File>, bool, Option<LockGuard<State>>)> {
+        let state = self.invalidated_state().await?;
+        let file = state.file.clone();
+        let direct = state.direct;
+        if sys::POSITIONED_IO_USES_CURSOR {
+            Ok((file, direct, Some(state)))
+        } else {
+            drop(state);
+            Ok((file, direct, None))
+        }
+    }
+
+    /// Flushes the write cache and returns the inner file.
+    ///
+    /// This is used by types that access the file directly, like memory maps.
+    #[cfg(feature = "unstable")]
+    pub(crate) async fn flushed_file(&self) -> io::Result<Arc<std::fs::File>> {
+        let state = self.invalidated_state().await?;
+        Ok(state.file.clone())
+    }
 }
 
 impl Drop for File {
@@ -383,6 +856,7 @@ impl Seek for &File {
 
 impl From<std::fs::File> for File {
     fn from(file: std::fs::File) -> File {
+        let direct = direct::is_direct(&file);
         let file = Arc::new(file);
 
         File {
@@ -391,6 +865,7 @@ impl From<std::fs::File> for File {
                 file,
                 mode: Mode::Idle,
                 cache: Vec::new(),
+                direct,
                 is_flushed: false,
                 last_read_err: None,
                 last_write_err: None,
@@ -451,6 +926,363 @@ cfg_windows! {
     }
 }
 
+/// Advisory file locking based on `flock(2)`, preallocation, and positioned I/O.
+#[cfg(all(unix, feature = "unstable"))]
+mod sys {
+    use std::os::unix::fs::FileExt;
+    use std::os::unix::io::AsRawFd;
+
+    use crate::io;
+
+    fn flock(file: &std::fs::File, flag: libc::c_int) -> io::Result<()> {
+        let ret = unsafe { libc::flock(file.as_raw_fd(), flag) };
+        if ret < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn flag(exclusive: bool) -> libc::c_int {
+        if exclusive {
+            libc::LOCK_EX
+        } else {
+            libc::LOCK_SH
+        }
+    }
+
+    pub fn lock(file: &std::fs::File, exclusive: bool) -> io::Result<()> {
+        flock(file, flag(exclusive))
+    }
+
+    pub fn try_lock(file: &std::fs::File, exclusive: bool) -> io::Result<bool> {
+        match flock(file, flag(exclusive) | libc::LOCK_NB) {
+            Ok(()) => Ok(true),
+            Err(ref err) if err.raw_os_error() == Some(libc::EWOULDBLOCK) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn unlock(file: &std::fs::File) -> io::Result<()> {
+        flock(file, libc::LOCK_UN)
+    }
+
+    /// Positioned I/O doesn't touch the cursor, so it can run concurrently with other operations.
+    pub const POSITIONED_IO_USES_CURSOR: bool = false;
+
+    pub fn read_at(file: &std::fs::File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        file.read_at(buf, offset)
+    }
+
+    pub fn write_at(file: &std::fs::File, buf: &[u8], offset: u64) -> io::Result<usize> {
+        file.write_at(buf, offset)
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn allocate(file: &std::fs::File, len: u64) -> io::Result<()> {
+        // Allocating zero bytes is an error.
+        if len == 0 {
+            return Ok(());
+        }
+        let ret = unsafe { libc::fallocate(file.as_raw_fd(), 0, 0, len as libc::off_t) };
+        if ret < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "ios"))]
+    pub fn allocate(file: &std::fs::File, len: u64) -> io::Result<()> {
+        let size = file.metadata()?.len();
+        if len <= size {
+            return Ok(());
+        }
+
+        let mut store = libc::fstore_t {
+            fst_flags: libc::F_ALLOCATECONTIG,
+            fst_posmode: libc::F_PEOFPOSMODE,
+            fst_offset: 0,
+            fst_length: (len - size) as libc::off_t,
+            fst_bytesalloc: 0,
+        };
+        let mut ret = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_PREALLOCATE, &store) };
+        if ret < 0 {
+            // Contiguous space is not available, so settle for any space.
+            store.fst_flags = libc::F_ALLOCATEALL;
+            ret = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_PREALLOCATE, &store) };
+        }
+        if ret < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        // Preallocation doesn't change the file size.
+        file.set_len(len)
+    }
+
+    #[cfg(target_os = "freebsd")]
+    pub fn allocate(file: &std::fs::File, len: u64) -> io::Result<()> {
+        // Allocating zero bytes is an error.
+        if len == 0 {
+            return Ok(());
+        }
+        // This function returns the error code instead of setting `errno`.
+        match unsafe { libc::posix_fallocate(file.as_raw_fd(), 0, len as libc::off_t) } {
+            0 => Ok(()),
+            code => Err(io::Error::from_raw_os_error(code)),
+        }
+    }
+
+    #[cfg(not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+    )))]
+    pub fn allocate(_: &std::fs::File, _: u64) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            "preallocation is not supported on this platform",
+        ))
+    }
+}
+
+/// Advisory file locking based on `LockFileEx`, preallocation, and positioned I/O.
+#[cfg(all(windows, feature = "unstable"))]
+mod sys {
+    use std::io::{Seek as _, SeekFrom};
+    use std::mem;
+    use std::os::windows::fs::FileExt;
+    use std::os::windows::io::AsRawHandle;
+
+    use winapi::shared::minwindef::{DWORD, LPVOID};
+    use winapi::shared::winerror::ERROR_LOCK_VIOLATION;
+    use winapi::um::fileapi::{
+        LockFileEx, SetFileInformationByHandle, UnlockFile, FILE_ALLOCATION_INFO,
+    };
+    use winapi::um::minwinbase::{
+        FileAllocationInfo, LOCKFILE_EXCLUSIVE_LOCK, LOCKFILE_FAIL_IMMEDIATELY,
+    };
+
+    use crate::io;
+
+    fn lock_file(file: &std::fs::File, flags: DWORD) -> io::Result<()> {
+        unsafe {
+            let mut overlapped = mem::zeroed();
+            let ret = LockFileEx(file.as_raw_handle(), flags, 0, !0, !0, &mut overlapped);
+            if ret == 0 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn flags(exclusive: bool) -> DWORD {
+        if exclusive {
+            LOCKFILE_EXCLUSIVE_LOCK
+        } else {
+            0
+        }
+    }
+
+    pub fn lock(file: &std::fs::File, exclusive: bool) -> io::Result<()> {
+        lock_file(file, flags(exclusive))
+    }
+
+    pub fn try_lock(file: &std::fs::File, exclusive: bool) -> io::Result<bool> {
+        match lock_file(file, flags(exclusive) | LOCKFILE_FAIL_IMMEDIATELY) {
+            Ok(()) => Ok(true),
+            Err(ref err) if err.raw_os_error() == Some(ERROR_LOCK_VIOLATION as i32) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn unlock(file: &std::fs::File) -> io::Result<()> {
+        let ret = unsafe { UnlockFile(file.as_raw_handle(), 0, 0, !0, !0) };
+        if ret == 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    pub fn allocate(file: &std::fs::File, len: u64) -> io::Result<()> {
+        // Setting a smaller allocation size would truncate the file.
+        if len <= file.metadata()?.len() {
+            return Ok(());
+        }
+
+        unsafe {
+            let mut info: FILE_ALLOCATION_INFO = mem::zeroed();
+            *info.AllocationSize.QuadPart_mut() = len as i64;
+            let ret = SetFileInformationByHandle(
+                file.as_raw_handle(),
+                FileAllocationInfo,
+                &mut info as *mut _ as LPVOID,
+                mem::size_of::<FILE_ALLOCATION_INFO>() as DWORD,
+            );
+            if ret == 0 {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        // The allocation size doesn't change the file size.
+        file.set_len(len)
+    }
+
+    /// Positioned I/O moves the cursor, so it must not run concurrently with other operations.
+    pub const POSITIONED_IO_USES_CURSOR: bool = true;
+
+    pub fn read_at(file: &std::fs::File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        let pos = (&*file).seek(SeekFrom::Current(0))?;
+        let res = file.seek_read(buf, offset);
+        (&*file).seek(SeekFrom::Start(pos))?;
+        res
+    }
+
+    pub fn write_at(file: &std::fs::File, buf: &[u8], offset: u64) -> io::Result<usize> {
+        let pos = (&*file).seek(SeekFrom::Current(0))?;
+        let res = file.seek_write(buf, offset);
+        (&*file).seek(SeekFrom::Start(pos))?;
+        res
+    }
+}
+
+/// Support for files opened with `O_DIRECT`.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod direct {
+    use std::alloc::{self, Layout};
+    use std::io::{Read as _, Write as _};
+    #[cfg(feature = "unstable")]
+    use std::os::unix::fs::FileExt;
+    use std::os::unix::io::AsRawFd;
+    use std::slice;
+
+    use crate::io;
+
+    /// The alignment of buffers used for direct I/O.
+    ///
+    /// This is the largest logical block size in common use, so it satisfies every device.
+    pub const ALIGN: usize = 4096;
+
+    /// Returns `true` if the file was opened with `O_DIRECT`.
+    pub fn is_direct(file: &std::fs::File) -> bool {
+        let flags = unsafe { libc::fcntl(file.as_raw_fd(), libc::F_GETFL) };
+        flags >= 0 && flags & libc::O_DIRECT != 0
+    }
+
+    /// Rounds `n` up to a multiple of `ALIGN`.
+    pub fn round_up(n: usize) -> usize {
+        (n + ALIGN - 1) / ALIGN * ALIGN
+    }
+
+    /// Reads into `buf` through an aligned buffer.
+    pub fn read(file: &std::fs::File, buf: &mut [u8]) -> io::Result<usize> {
+        let mut aligned = AlignedBuf::new(buf.len());
+        let n = (&*file).read(aligned.as_mut_slice())?;
+        buf[..n].copy_from_slice(&aligned.as_mut_slice()[..n]);
+        Ok(n)
+    }
+
+    /// Writes all of `buf` through an aligned buffer.
+    ///
+    /// The length of `buf` and the current offset into the file must be multiples of the logical
+    /// block size of the device, or else the operating system reports an error.
+    pub fn write_all(file: &std::fs::File, buf: &[u8]) -> io::Result<()> {
+        let mut aligned = AlignedBuf::new(buf.len());
+        aligned.as_mut_slice().copy_from_slice(buf);
+        (&*file).write_all(aligned.as_mut_slice())
+    }
+
+    /// Reads into `buf` from `offset` through an aligned buffer.
+    #[cfg(feature = "unstable")]
+    pub fn read_at(file: &std::fs::File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        let mut aligned = AlignedBuf::new(buf.len());
+        let n = file.read_at(aligned.as_mut_slice(), offset)?;
+        buf[..n].copy_from_slice(&aligned.as_mut_slice()[..n]);
+        Ok(n)
+    }
+
+    /// Writes `buf` at `offset` through an aligned buffer.
+    ///
+    /// The length of `buf` and `offset` must be multiples of the logical block size of the
+    /// device, or else the operating system reports an error.
+    #[cfg(feature = "unstable")]
+    pub fn write_at(file: &std::fs::File, buf: &[u8], offset: u64) -> io::Result<usize> {
+        let mut aligned = AlignedBuf::new(buf.len());
+        aligned.as_mut_slice().copy_from_slice(buf);
+        file.write_at(aligned.as_mut_slice(), offset)
+    }
+
+    /// A heap-allocated buffer aligned to `ALIGN`.
+    struct AlignedBuf {
+        ptr: *mut u8,
+        len: usize,
+    }
+
+    impl AlignedBuf {
+        fn new(len: usize) -> AlignedBuf {
+            let layout = AlignedBuf::layout(len);
+            let ptr = unsafe { alloc::alloc_zeroed(layout) };
+            if ptr.is_null() {
+                alloc::handle_alloc_error(layout);
+            }
+            AlignedBuf { ptr, len }
+        }
+
+        fn layout(len: usize) -> Layout {
+            // Zero-sized allocations are not allowed.
+            Layout::from_size_align(round_up(len).max(ALIGN), ALIGN).unwrap()
+        }
+
+        fn as_mut_slice(&mut self) -> &mut [u8] {
+            unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+        }
+    }
+
+    impl Drop for AlignedBuf {
+        fn drop(&mut self) {
+            unsafe { alloc::dealloc(self.ptr, AlignedBuf::layout(self.len)) }
+        }
+    }
+}
+
+/// Direct I/O without alignment requirements, or no direct I/O at all.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+mod direct {
+    use std::io::{Read as _, Write as _};
+
+    use crate::io;
+
+    pub fn is_direct(_: &std::fs::File) -> bool {
+        false
+    }
+
+    pub fn round_up(n: usize) -> usize {
+        n
+    }
+
+    pub fn read(file: &std::fs::File, buf: &mut [u8]) -> io::Result<usize> {
+        (&*file).read(buf)
+    }
+
+    pub fn write_all(file: &std::fs::File, buf: &[u8]) -> io::Result<()> {
+        (&*file).write_all(buf)
+    }
+
+    #[cfg(feature = "unstable")]
+    pub fn read_at(file: &std::fs::File, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        super::sys::read_at(file, buf, offset)
+    }
+
+    #[cfg(feature = "unstable")]
+    pub fn write_at(file: &std::fs::File, buf: &[u8], offset: u64) -> io::Result<usize> {
+        super::sys::write_at(file, buf, offset)
+    }
+}
+
 /// An async mutex with non-borrowing lock guards.
 struct Lock<T>(Arc<LockState<T>>);
 
@@ -583,6 +1415,13 @@ struct State {
     /// If in writing mode, the cache contains data that will eventually be written to the file.
     cache: Vec<u8>,
 
+    /// Set to `true` if the file was opened with `O_DIRECT`.
+    ///
+    /// Direct I/O requires buffers aligned in memory, so every read and write then copies through
+    /// a freshly allocated aligned buffer. Reads fill the cache with a multiple of the alignment,
+    /// and the cache itself is not aligned.
+    direct: bool,
+
     /// Set to `true` if the file is flushed.
     ///
     /// When a file is flushed, the write cache and the inner file's buffer are empty.
@@ -660,7 +1499,7 @@ impl LockGuard<State> {
                 if available > 0 || self.cache.is_empty() {
                     // Copy data from the cache into the buffer.
                     let n = cmp::min(available, buf.len());
-                    buf[..n].copy_from_slice(&self.cache[start..n]);
+                    buf[..n].copy_from_slice(&self.cache[start..start + n]);
 
                     // Move the read cursor forward.
                     self.mode = Mode::Reading(start + n);
@@ -674,13 +1513,18 @@ impl LockGuard<State> {
             }
         }
 
-        // Make the cache as long as `buf`.
-        if self.cache.len() < buf.len() {
-            let diff = buf.len() - self.cache.len();
+        // Make the cache as long as `buf`, rounded up to the alignment for direct I/O.
+        let len = if self.direct {
+            direct::round_up(buf.len())
+        } else {
+            buf.len()
+        };
+        if self.cache.len() < len {
+            let diff = len - self.cache.len();
             self.cache.reserve(diff);
         }
         unsafe {
-            self.cache.set_len(buf.len());
+            self.cache.set_len(len);
         }
 
         // Register current task's interest in the file lock.
@@ -690,8 +1534,17 @@ impl LockGuard<State> {
         blocking::spawn(move || {
             // Read some data from the file into the cache.
             let res = {
-                let State { file, cache, .. } = &mut *self;
-                (&**file).read(cache)
+                let State {
+                    file,
+                    cache,
+                    direct,
+                    ..
+                } = &mut *self;
+                if *direct {
+                    direct::read(file, cache)
+                } else {
+                    (&**file).read(cache)
+                }
             };
 
             match res {
@@ -750,19 +1603,26 @@ impl LockGuard<State> {
         // If we're in reading mode, invalidate the read buffer.
         self = futures_core::ready!(self.poll_unread(cx))?;
 
-        // If necessary, grow the cache to have as much capacity as `buf`.
-        if self.cache.capacity() < buf.len() {
-            let diff = buf.len() - self.cache.capacity();
+        // If necessary, grow the cache to have as much capacity as `buf`, rounded up to the
+        // alignment for direct I/O.
+        let len = if self.direct {
+            direct::round_up(buf.len())
+        } else {
+            buf.len()
+        };
+        if self.cache.capacity() < len {
+            let diff = len - self.cache.len();
             self.cache.reserve(diff);
         }
 
         // How many bytes can be written into the cache before filling up.
         let available = self.cache.capacity() - self.cache.len();
 
-        // If there is space available in the cache or if the buffer is empty, we can write data
-        // into the cache.
-        if available > 0 || buf.is_empty() {
-            let n = cmp::min(available, buf.len());
+        // If the whole buffer fits into the cache, we can write data into the cache. Buffers are
+        // never split between two drains so that each one reaches the file in a single write,
+        // which keeps records from concurrent appenders intact.
+        if available >= buf.len() {
+            let n = buf.len();
             let start = self.cache.len();
 
             // Copy data from the buffer into the cache.
@@ -776,7 +1636,7 @@ impl LockGuard<State> {
             self.mode = Mode::Writing;
             Poll::Ready(Ok(n))
         } else {
-            // Drain the write cache because it's full.
+            // Drain the write cache because the buffer doesn't fit.
             futures_core::ready!(self.poll_drain(cx))?;
             Poll::Pending
         }
@@ -797,7 +1657,12 @@ impl LockGuard<State> {
 
                 // Start a write operation asynchronously.
                 blocking::spawn(move || {
-                    match (&*self.file).write_all(&self.cache) {
+                    let res = if self.direct {
+                        direct::write_all(&self.file, &self.cache)
+                    } else {
+                        (&*self.file).write_all(&self.cache)
+                    };
+                    match res {
                         Ok(_) => {
                             // Switch to idle mode.
                             self.cache.clear();
@@ -0,0 +1,288 @@
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::slice;
+use std::sync::Arc;
+
+use crate::fs::File;
+use crate::io;
+use crate::task::blocking;
+
+/// A read-only memory map of a file.
+///
+/// The contents of the file are accessed through [`Deref`] to `[u8]` without copying them into a
+/// separate buffer. Wrap the map in an [`io::Cursor`] to read it through the [`Read`] trait.
+///
+/// Accessing the map never blocks the current task on a system call, but it may still page in
+/// data from disk. Memory maps are best suited for large files that are read repeatedly and are
+/// likely to stay in the page cache.
+///
+/// [`Deref`]: https://doc.rust-lang.org/std/ops/trait.Deref.html
+/// [`io::Cursor`]: ../io/struct.Cursor.html
+/// [`Read`]: ../io/trait.Read.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs::{File, Mmap};
+///
+/// let file = File::open("index.bin").await?;
+/// let map = unsafe { Mmap::map(&file).await? };
+/// println!("first byte: {}", map[0]);
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Mmap(memmap2::Mmap);
+
+impl Mmap {
+    /// Creates a read-only memory map of the whole file.
+    ///
+    /// Pending writes to `file` are flushed before mapping it. The map stays valid after `file`
+    /// is closed.
+    ///
+    /// # Safety
+    ///
+    /// The contents of the map may change if the file is modified, either by this process or by
+    /// another one. If the file is truncated, accessing the unmapped data results in undefined
+    /// behavior. The caller must make sure the file is not modified while it is mapped.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned in the following situations:
+    ///
+    /// * `file` was not opened for reading.
+    /// * Some other I/O error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::{File, Mmap};
+    ///
+    /// let file = File::open("index.bin").await?;
+    /// let map = unsafe { Mmap::map(&file).await? };
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async unsafe fn map(file: &File) -> io::Result<Mmap> {
+        let file = file.flushed_file().await?;
+        blocking::spawn(move || memmap2::Mmap::map(&*file))
+            .await
+            .map(Mmap)
+    }
+}
+
+impl Deref for Mmap {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for Mmap {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl fmt::Debug for Mmap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mmap").field("len", &self.0.len()).finish()
+    }
+}
+
+/// A writable memory map of a file.
+///
+/// Writes to the map are carried through to the file. They become visible to other processes
+/// immediately, but are only guaranteed to reach the disk after [`flush`].
+///
+/// [`flush`]: #method.flush
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs::{MmapMut, OpenOptions};
+///
+/// let file = OpenOptions::new().read(true).write(true).open("data.bin").await?;
+/// let mut map = unsafe { MmapMut::map_mut(&file).await? };
+/// map[..5].copy_from_slice(b"hello");
+/// map.flush().await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct MmapMut {
+    /// The map, which is shared with flush operations on the blocking pool.
+    map: Arc<memmap2::MmapMut>,
+
+    /// The start of the map.
+    ///
+    /// Flush operations only pass the address range to the operating system and never access
+    /// the contents, so the contents can be modified through this pointer while the map is
+    /// shared.
+    ptr: *mut u8,
+
+    /// The length of the map, stored because getting it from the map would borrow the contents.
+    len: usize,
+}
+
+unsafe impl Send for MmapMut {}
+unsafe impl Sync for MmapMut {}
+
+impl MmapMut {
+    /// Creates a writable memory map of the whole file.
+    ///
+    /// Pending writes to `file` are flushed before mapping it. The map stays valid after `file`
+    /// is closed.
+    ///
+    /// # Safety
+    ///
+    /// The contents of the map may change if the file is modified, either by this process or by
+    /// another one. If the file is truncated, accessing the unmapped data results in undefined
+    /// behavior. The caller must make sure the file is not modified while it is mapped.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned in the following situations:
+    ///
+    /// * `file` was not opened for both reading and writing.
+    /// * Some other I/O error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::{MmapMut, OpenOptions};
+    ///
+    /// let file = OpenOptions::new().read(true).write(true).open("data.bin").await?;
+    /// let map = unsafe { MmapMut::map_mut(&file).await? };
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async unsafe fn map_mut(file: &File) -> io::Result<MmapMut> {
+        let file = file.flushed_file().await?;
+        let mut map = blocking::spawn(move || memmap2::MmapMut::map_mut(&*file)).await?;
+        Ok(MmapMut {
+            ptr: map.as_mut_ptr(),
+            len: map.len(),
+            map: Arc::new(map),
+        })
+    }
+
+    /// Writes modified data in the map to disk.
+    ///
+    /// The data is written on the blocking pool, which shares the map until the operation
+    /// completes. If the returned future is dropped early, the write still finishes in the
+    /// background and the map can be modified in the meantime.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::{MmapMut, OpenOptions};
+    ///
+    /// let file = OpenOptions::new().read(true).write(true).open("data.bin").await?;
+    /// let mut map = unsafe { MmapMut::map_mut(&file).await? };
+    /// map[0] = 1;
+    /// map.flush().await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn flush(&mut self) -> io::Result<()> {
+        let map = self.map.clone();
+        blocking::spawn(move || map.flush()).await
+    }
+
+    /// Starts writing modified data in the map to disk without waiting for it to finish.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::{MmapMut, OpenOptions};
+    ///
+    /// let file = OpenOptions::new().read(true).write(true).open("data.bin").await?;
+    /// let mut map = unsafe { MmapMut::map_mut(&file).await? };
+    /// map[0] = 1;
+    /// map.flush_async()?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub fn flush_async(&self) -> io::Result<()> {
+        self.map.flush_async()
+    }
+
+    /// Converts the map into a read-only one.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if a flush operation whose future was dropped early is still running.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::{MmapMut, OpenOptions};
+    ///
+    /// let file = OpenOptions::new().read(true).write(true).open("data.bin").await?;
+    /// let map = unsafe { MmapMut::map_mut(&file).await? };
+    /// let map = map.make_read_only()?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub fn make_read_only(self) -> io::Result<Mmap> {
+        match Arc::try_unwrap(self.map) {
+            Ok(map) => map.make_read_only().map(Mmap),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "memory map is still being flushed",
+            )),
+        }
+    }
+}
+
+impl Deref for MmapMut {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl DerefMut for MmapMut {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        unsafe { slice::from_raw_parts_mut(self.ptr, self.len) }
+    }
+}
+
+impl AsRef<[u8]> for MmapMut {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl AsMut<[u8]> for MmapMut {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+impl fmt::Debug for MmapMut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MmapMut")
+            .field("len", &self.len)
+            .finish()
+    }
+}
@@ -3,11 +3,13 @@
 //! This module is an async version of [`std::fs`].
 //!
 //! [`os::unix::fs`]: ../os/unix/fs/index.html
+//! [`os::windows::fs`]: ../os/windows/fs/index.html
 //! [`std::fs`]: https://doc.rust-lang.org/std/fs/index.html
 //!
 //! # Platform-specific extensions
 //!
 //! * Unix: use the [`os::unix::fs`] module.
+//! * Windows: use the [`os::windows::fs`] module.
 //!
 //! # Examples
 //!
@@ -75,3 +77,25 @@ mod rename;
 mod set_permissions;
 mod symlink_metadata;
 mod write;
+
+cfg_unstable! {
+    pub use atomic_file::{write_atomic, AtomicFile};
+    pub use bytes_stream::{BytesStream, Chunk};
+    pub use cached_fs::CachedFs;
+    pub use cancel_safe::{cancel_safe, CancelSafe, Cancellation, Cancelled};
+    pub use mmap::{Mmap, MmapMut};
+    pub use read_dir_options::ReadDirOptions;
+    pub use tempfile::{tempdir, tempdir_in, tempfile, tempfile_in, TempDir};
+    pub use walk_dir::{walk_dir, WalkDir, WalkEntry};
+    pub use watch::{watch, Event, EventKind, Watch};
+
+    mod atomic_file;
+    mod bytes_stream;
+    mod cached_fs;
+    mod cancel_safe;
+    mod mmap;
+    mod read_dir_options;
+    mod tempfile;
+    mod walk_dir;
+    mod watch;
+}
@@ -1,3 +1,4 @@
+use std::ffi::OsString;
 use std::pin::Pin;
 
 use crate::fs::DirEntry;
@@ -70,14 +71,43 @@ pub struct ReadDir(State);
 /// The `ReadDir` can be either idle or busy performing an asynchronous operation.
 #[derive(Debug)]
 enum State {
-    Idle(Option<std::fs::ReadDir>),
-    Busy(JoinHandle<(std::fs::ReadDir, Option<io::Result<std::fs::DirEntry>>)>),
+    Idle(Option<Inner>),
+    Busy(JoinHandle<(Inner, Option<io::Result<DirEntry>>)>),
+}
+
+/// A synchronous `ReadDir` handle and the filter applied to its entries.
+#[derive(Debug)]
+struct Inner {
+    entries: std::fs::ReadDir,
+    filter: Filter,
+}
+
+impl Inner {
+    /// Returns the next entry that passes the filter.
+    fn next(&mut self) -> Option<io::Result<DirEntry>> {
+        loop {
+            let res = match self.entries.next()? {
+                Ok(entry) => self.filter.apply(entry),
+                Err(err) => Err(err),
+            };
+            match res {
+                Ok(None) => continue,
+                Ok(Some(entry)) => return Some(Ok(entry)),
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
 }
 
 impl ReadDir {
     /// Creates an asynchronous `ReadDir` from a synchronous handle.
     pub(crate) fn new(inner: std::fs::ReadDir) -> ReadDir {
-        ReadDir(State::Idle(Some(inner)))
+        ReadDir::with_filter(inner, Filter::default())
+    }
+
+    /// Creates an asynchronous `ReadDir` that only yields entries passing `filter`.
+    pub(crate) fn with_filter(entries: std::fs::ReadDir, filter: Filter) -> ReadDir {
+        ReadDir(State::Idle(Some(Inner { entries, filter })))
     }
 }
 
@@ -100,9 +130,83 @@ impl Stream for ReadDir {
                 State::Busy(task) => {
                     let (inner, opt) = futures_core::ready!(Pin::new(task).poll(cx));
                     self.0 = State::Idle(Some(inner));
-                    return Poll::Ready(opt.map(|res| res.map(DirEntry::new)));
+                    return Poll::Ready(opt);
                 }
             }
         }
     }
 }
+
+/// Decides which directory entries are yielded and what is fetched along with them.
+///
+/// Filtering happens on the blocking pool, so skipped entries don't cost an extra trip there.
+#[derive(Clone, Debug)]
+pub(crate) struct Filter {
+    /// Yield only entries with one of these extensions, or all entries if empty.
+    pub extensions: Vec<OsString>,
+
+    /// Yield entries that are neither directories nor symbolic links.
+    pub files: bool,
+
+    /// Yield directories.
+    pub dirs: bool,
+
+    /// Yield symbolic links.
+    pub symlinks: bool,
+
+    /// Fetch the metadata of yielded entries.
+    pub metadata: bool,
+}
+
+impl Default for Filter {
+    fn default() -> Filter {
+        Filter {
+            extensions: Vec::new(),
+            files: true,
+            dirs: true,
+            symlinks: true,
+            metadata: false,
+        }
+    }
+}
+
+impl Filter {
+    /// Returns the entry if it passes the filter.
+    fn apply(&self, entry: std::fs::DirEntry) -> io::Result<Option<DirEntry>> {
+        if !self.extensions.is_empty() {
+            let name = entry.file_name();
+            match std::path::Path::new(&name).extension() {
+                Some(ext) if self.extensions.iter().any(|e| e == ext) => {}
+                _ => return Ok(None),
+            }
+        }
+
+        let metadata = if self.metadata {
+            Some(entry.metadata()?)
+        } else {
+            None
+        };
+
+        let file_type = if !(self.files && self.dirs && self.symlinks) {
+            let file_type = match &metadata {
+                Some(metadata) => metadata.file_type(),
+                None => entry.file_type()?,
+            };
+            let keep = if file_type.is_dir() {
+                self.dirs
+            } else if file_type.is_symlink() {
+                self.symlinks
+            } else {
+                self.files
+            };
+            if !keep {
+                return Ok(None);
+            }
+            Some(file_type)
+        } else {
+            metadata.as_ref().map(|m| m.file_type())
+        };
+
+        Ok(Some(DirEntry::new(entry, file_type, metadata)))
+    }
+}
@@ -0,0 +1,188 @@
+use std::ffi::OsStr;
+use std::future::Future;
+
+use crate::fs::read_dir::Filter;
+use crate::fs::ReadDir;
+use crate::io;
+use crate::path::Path;
+use crate::task::blocking;
+
+/// A builder for reading directories with configurable options.
+///
+/// Entries that don't pass the filters are skipped while reading the directory on the blocking
+/// pool, so they don't cost a round-trip each. Enabling [`metadata`] fetches the metadata of each
+/// entry in the same trip as the entry itself.
+///
+/// [`metadata`]: #method.metadata
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs::ReadDirOptions;
+/// use async_std::prelude::*;
+///
+/// let mut dir = ReadDirOptions::new()
+///     .extension("rs")
+///     .dirs(false)
+///     .metadata(true)
+///     .read_dir("src")
+///     .await?;
+///
+/// while let Some(res) = dir.next().await {
+///     let entry = res?;
+///     println!("{:?}: {} bytes", entry.path(), entry.metadata().await?.len());
+/// }
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Debug, Default)]
+pub struct ReadDirOptions(Filter);
+
+impl ReadDirOptions {
+    /// Creates a blank set of options.
+    ///
+    /// All entries are yielded and no metadata is fetched up front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::fs::ReadDirOptions;
+    ///
+    /// let options = ReadDirOptions::new();
+    /// ```
+    pub fn new() -> ReadDirOptions {
+        ReadDirOptions(Filter::default())
+    }
+
+    /// Only yields entries with the given extension.
+    ///
+    /// The extension is compared without the leading dot. Calling this method multiple times
+    /// yields entries with any of the given extensions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::fs::ReadDirOptions;
+    ///
+    /// let mut options = ReadDirOptions::new();
+    /// options.extension("jpg").extension("png");
+    /// ```
+    pub fn extension<S: AsRef<OsStr>>(&mut self, ext: S) -> &mut Self {
+        self.0.extensions.push(ext.as_ref().to_owned());
+        self
+    }
+
+    /// Sets the option for yielding files.
+    ///
+    /// Files are all entries that are neither directories nor symbolic links.
+    ///
+    /// This option is initially set to `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::fs::ReadDirOptions;
+    ///
+    /// let mut options = ReadDirOptions::new();
+    /// options.files(false);
+    /// ```
+    pub fn files(&mut self, files: bool) -> &mut Self {
+        self.0.files = files;
+        self
+    }
+
+    /// Sets the option for yielding directories.
+    ///
+    /// This option is initially set to `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::fs::ReadDirOptions;
+    ///
+    /// let mut options = ReadDirOptions::new();
+    /// options.dirs(false);
+    /// ```
+    pub fn dirs(&mut self, dirs: bool) -> &mut Self {
+        self.0.dirs = dirs;
+        self
+    }
+
+    /// Sets the option for yielding symbolic links.
+    ///
+    /// Symbolic links are not followed, so a link to a directory counts as a symbolic link.
+    ///
+    /// This option is initially set to `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::fs::ReadDirOptions;
+    ///
+    /// let mut options = ReadDirOptions::new();
+    /// options.symlinks(false);
+    /// ```
+    pub fn symlinks(&mut self, symlinks: bool) -> &mut Self {
+        self.0.symlinks = symlinks;
+        self
+    }
+
+    /// Sets the option for fetching metadata along with each entry.
+    ///
+    /// When set to `true`, [`DirEntry::metadata`] and [`DirEntry::file_type`] return immediately
+    /// without another trip to the blocking pool. The metadata is read at the time the entry is
+    /// yielded and is not refreshed later.
+    ///
+    /// This option is initially set to `false`.
+    ///
+    /// [`DirEntry::metadata`]: struct.DirEntry.html#method.metadata
+    /// [`DirEntry::file_type`]: struct.DirEntry.html#method.file_type
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::fs::ReadDirOptions;
+    ///
+    /// let mut options = ReadDirOptions::new();
+    /// options.metadata(true);
+    /// ```
+    pub fn metadata(&mut self, metadata: bool) -> &mut Self {
+        self.0.metadata = metadata;
+        self
+    }
+
+    /// Returns a stream of entries in a directory with the configured options.
+    ///
+    /// # Errors
+    ///
+    /// An error will be returned in the following situations:
+    ///
+    /// * `path` does not point to an existing directory.
+    /// * The current process lacks permissions to read the contents of the directory.
+    /// * Some other I/O error occurred.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs::ReadDirOptions;
+    ///
+    /// let dir = ReadDirOptions::new().dirs(false).read_dir(".").await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub fn read_dir<P: AsRef<Path>>(&self, path: P) -> impl Future<Output = io::Result<ReadDir>> {
+        let filter = self.0.clone();
+        let path = path.as_ref().to_owned();
+        async move {
+            blocking::spawn(move || std::fs::read_dir(path))
+                .await
+                .map(|entries| ReadDir::with_filter(entries, filter))
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::fs::File;
+use crate::io;
+use crate::path::{Path, PathBuf};
+use crate::task::blocking;
+
+/// The number of attempts at finding an unused name before giving up.
+const NUM_RETRIES: u32 = 1 << 16;
+
+/// The number of random characters in a generated name.
+const NUM_RAND_CHARS: usize = 12;
+
+/// Creates a new anonymous temporary file.
+///
+/// The file is created in the directory returned by [`std::env::temp_dir`]. It is opened for
+/// reading and writing, and is only accessible by the current user.
+///
+/// The file has no name on the filesystem and is deleted by the operating system once it is
+/// closed, even if the process crashes. On Unix, the file is unlinked right after creation. On
+/// Windows, it is opened with `FILE_FLAG_DELETE_ON_CLOSE`.
+///
+/// [`std::env::temp_dir`]: https://doc.rust-lang.org/std/env/fn.temp_dir.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs;
+/// use async_std::prelude::*;
+///
+/// let mut file = fs::tempfile().await?;
+/// file.write_all(b"scratch data").await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn tempfile() -> io::Result<File> {
+    tempfile_in(std::env::temp_dir()).await
+}
+
+/// Creates a new anonymous temporary file in the specified directory.
+///
+/// See [`tempfile`] for more details.
+///
+/// [`tempfile`]: fn.tempfile.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs;
+///
+/// let file = fs::tempfile_in("/var/tmp").await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn tempfile_in<P: AsRef<Path>>(dir: P) -> io::Result<File> {
+    let dir = dir.as_ref().to_owned();
+    blocking::spawn(move || {
+        let mut options = std::fs::OpenOptions::new();
+        options.read(true).write(true).create_new(true);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        #[cfg(windows)]
+        {
+            use std::os::windows::fs::OpenOptionsExt;
+            use winapi::um::winbase::FILE_FLAG_DELETE_ON_CLOSE;
+            options.custom_flags(FILE_FLAG_DELETE_ON_CLOSE);
+        }
+
+        create_unique(&dir, |path| {
+            let file = options.open(path)?;
+
+            // Unlink the file so that it disappears once it's closed.
+            #[cfg(unix)]
+            std::fs::remove_file(path)?;
+
+            Ok(file)
+        })
+    })
+    .await
+    .map(File::from)
+}
+
+/// Creates a new temporary directory.
+///
+/// The directory is created in the directory returned by [`std::env::temp_dir`] and is only
+/// accessible by the current user.
+///
+/// The directory and all of its contents are removed when the returned [`TempDir`] is dropped.
+/// Use [`TempDir::close`] to wait for the removal and observe errors.
+///
+/// [`std::env::temp_dir`]: https://doc.rust-lang.org/std/env/fn.temp_dir.html
+/// [`TempDir`]: struct.TempDir.html
+/// [`TempDir::close`]: struct.TempDir.html#method.close
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs;
+///
+/// let dir = fs::tempdir().await?;
+/// fs::write(dir.path().join("a.txt"), b"Hello, world!").await?;
+/// dir.close().await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn tempdir() -> io::Result<TempDir> {
+    tempdir_in(std::env::temp_dir()).await
+}
+
+/// Creates a new temporary directory inside the specified directory.
+///
+/// See [`tempdir`] for more details.
+///
+/// [`tempdir`]: fn.tempdir.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs;
+///
+/// let dir = fs::tempdir_in("/var/tmp").await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn tempdir_in<P: AsRef<Path>>(dir: P) -> io::Result<TempDir> {
+    let dir = dir.as_ref().to_owned();
+    blocking::spawn(move || {
+        let mut builder = std::fs::DirBuilder::new();
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::DirBuilderExt;
+            builder.mode(0o700);
+        }
+
+        create_unique(&dir, |path| builder.create(path).map(|()| path.to_owned()))
+    })
+    .await
+    .map(|path| TempDir { path: Some(path) })
+}
+
+/// A directory that is removed when dropped.
+///
+/// This type is created by [`tempdir`] and [`tempdir_in`].
+///
+/// Dropping a `TempDir` removes the directory in the background and ignores any errors. Call
+/// [`close`] to wait for the directory to be removed.
+///
+/// [`tempdir`]: fn.tempdir.html
+/// [`tempdir_in`]: fn.tempdir_in.html
+/// [`close`]: #method.close
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct TempDir {
+    /// The path to the directory, or `None` if it has already been removed.
+    path: Option<PathBuf>,
+}
+
+impl TempDir {
+    /// Returns the path to the directory.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs;
+    ///
+    /// let dir = fs::tempdir().await?;
+    /// println!("{}", dir.path().display());
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub fn path(&self) -> &Path {
+        self.path.as_ref().unwrap()
+    }
+
+    /// Removes the directory and all of its contents.
+    ///
+    /// Unlike dropping the `TempDir`, this method waits until the directory is removed and reports
+    /// errors.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::fs;
+    ///
+    /// let dir = fs::tempdir().await?;
+    /// dir.close().await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn close(mut self) -> io::Result<()> {
+        let path = self.path.take().unwrap();
+        blocking::spawn(move || std::fs::remove_dir_all(path)).await
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        self.path()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        if let Some(path) = self.path.take() {
+            // The join handle is dropped, which lets the removal finish in the background.
+            blocking::spawn(move || {
+                let _ = std::fs::remove_dir_all(path);
+            });
+        }
+    }
+}
+
+/// Calls `create` with random paths inside `dir` until it doesn't fail with `AlreadyExists`.
+pub(crate) fn create_unique<T>(
+    dir: &Path,
+    mut create: impl FnMut(&Path) -> io::Result<T>,
+) -> io::Result<T> {
+    for _ in 0..NUM_RETRIES {
+        let path = dir.join(random_name());
+
+        match create(&path) {
+            Err(ref err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            res => return res,
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "too many temporary files exist",
+    ))
+}
+
+/// Generates a hard to guess file name.
+fn random_name() -> String {
+    const CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    // `RandomState` is seeded with random keys from the operating system.
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u32(process::id());
+    hasher.write_usize(COUNTER.fetch_add(1, Ordering::Relaxed));
+    if let Ok(time) = SystemTime::now().duration_since(UNIX_EPOCH) {
+        hasher.write_u128(time.as_nanos());
+    }
+
+    let mut name = String::from(".tmp");
+    let mut bits = hasher.finish();
+    for i in 0..NUM_RAND_CHARS {
+        if i == NUM_RAND_CHARS / 2 {
+            hasher.write_u64(bits);
+            bits = hasher.finish();
+        }
+        name.push(CHARS[(bits % CHARS.len() as u64) as usize] as char);
+        bits /= CHARS.len() as u64;
+    }
+    name
+}
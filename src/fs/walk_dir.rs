@@ -0,0 +1,308 @@
+use std::ffi::OsStr;
+use std::pin::Pin;
+
+use crate::fs::{FileType, Metadata};
+use crate::future::Future;
+use crate::io;
+use crate::path::{Path, PathBuf};
+use crate::stream::Stream;
+use crate::task::{blocking, Context, JoinHandle, Poll};
+
+/// Returns a stream that recursively walks a directory.
+///
+/// The stream yields the root itself first and then all entries below it in depth-first order.
+/// Each directory is yielded before its contents. The order of entries within a directory is
+/// platform-specific.
+///
+/// The walk can be configured with [`max_depth`] and [`follow_links`]. While walking, the
+/// consumer can call [`skip_current_dir`] to avoid descending into the directory that was just
+/// yielded.
+///
+/// Directories are read on the blocking pool, one entry at a time.
+///
+/// [`max_depth`]: struct.WalkDir.html#method.max_depth
+/// [`follow_links`]: struct.WalkDir.html#method.follow_links
+/// [`skip_current_dir`]: struct.WalkDir.html#method.skip_current_dir
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs;
+/// use async_std::prelude::*;
+///
+/// let mut entries = fs::walk_dir(".").max_depth(3);
+///
+/// while let Some(res) = entries.next().await {
+///     let entry = res?;
+///     if entry.file_name() == ".git" {
+///         entries.skip_current_dir();
+///         continue;
+///     }
+///     println!("{}", entry.path().display());
+/// }
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn walk_dir<P: AsRef<Path>>(root: P) -> WalkDir {
+    WalkDir(State::Idle(Some(Inner {
+        root: Some(root.as_ref().to_owned()),
+        stack: Vec::new(),
+        descend: None,
+        max_depth: usize::max_value(),
+        follow_links: false,
+    })))
+}
+
+/// A stream of entries in a directory tree.
+///
+/// This stream is returned by [`walk_dir`] and yields items of type
+/// [`io::Result`]`<`[`WalkEntry`]`>`.
+///
+/// [`walk_dir`]: fn.walk_dir.html
+/// [`io::Result`]: ../io/type.Result.html
+/// [`WalkEntry`]: struct.WalkEntry.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct WalkDir(State);
+
+/// The state of an asynchronous `WalkDir`.
+///
+/// The `WalkDir` can be either idle or busy performing an asynchronous operation.
+#[derive(Debug)]
+enum State {
+    Idle(Option<Inner>),
+    Busy(JoinHandle<(Inner, Option<io::Result<WalkEntry>>)>),
+}
+
+impl WalkDir {
+    /// Sets the maximum depth of entries to yield.
+    ///
+    /// The root has depth 0, its children have depth 1, and so on. By default, there is no limit.
+    ///
+    /// This option must be set before polling the stream.
+    pub fn max_depth(mut self, depth: usize) -> WalkDir {
+        self.inner_mut().max_depth = depth;
+        self
+    }
+
+    /// Configures whether to follow symbolic links.
+    ///
+    /// When set to `true`, symbolic links to directories are walked into, and entries report the
+    /// file type of the link target. A link pointing to one of its own ancestors is reported as
+    /// an error instead of being walked into again. By default, links are not followed.
+    ///
+    /// This option must be set before polling the stream.
+    pub fn follow_links(mut self, follow: bool) -> WalkDir {
+        self.inner_mut().follow_links = follow;
+        self
+    }
+
+    /// Skips the contents of the most recently yielded directory.
+    ///
+    /// If the last yielded entry is not a directory, this method has no effect.
+    pub fn skip_current_dir(&mut self) {
+        self.inner_mut().descend = None;
+    }
+
+    fn inner_mut(&mut self) -> &mut Inner {
+        match &mut self.0 {
+            State::Idle(opt) => opt.as_mut().unwrap(),
+            State::Busy(_) => panic!("`WalkDir` cannot be configured while it is being polled"),
+        }
+    }
+}
+
+impl Stream for WalkDir {
+    type Item = io::Result<WalkEntry>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match &mut self.0 {
+                State::Idle(opt) => {
+                    let mut inner = opt.take().unwrap();
+
+                    // Start the operation asynchronously.
+                    self.0 = State::Busy(blocking::spawn(move || {
+                        let next = inner.next();
+                        (inner, next)
+                    }));
+                }
+                // Poll the asynchronous operation the walk is currently blocked on.
+                State::Busy(task) => {
+                    let (inner, opt) = futures_core::ready!(Pin::new(task).poll(cx));
+                    self.0 = State::Idle(Some(inner));
+                    return Poll::Ready(opt);
+                }
+            }
+        }
+    }
+}
+
+/// The synchronous state of a directory walk.
+#[derive(Debug)]
+struct Inner {
+    /// The root, if it hasn't been yielded yet.
+    root: Option<PathBuf>,
+
+    /// Directories that are currently being read.
+    ///
+    /// Each directory is paired with its canonical path if symbolic links are followed. The
+    /// entries of the topmost directory have depth equal to the length of the stack.
+    stack: Vec<(std::fs::ReadDir, Option<std::path::PathBuf>)>,
+
+    /// The last yielded directory, which is walked into next unless skipped.
+    descend: Option<(PathBuf, usize)>,
+
+    max_depth: usize,
+    follow_links: bool,
+}
+
+impl Inner {
+    /// Finds the next entry in the walk.
+    fn next(&mut self) -> Option<io::Result<WalkEntry>> {
+        if let Some(root) = self.root.take() {
+            let res = if self.follow_links {
+                std::fs::metadata(&root)
+            } else {
+                std::fs::symlink_metadata(&root)
+            };
+            return Some(res.map(|meta| self.yield_entry(root, 0, meta.file_type())));
+        }
+
+        if let Some((dir, depth)) = self.descend.take() {
+            if let Err(err) = self.push(&dir) {
+                return Some(Err(err));
+            }
+            debug_assert_eq!(self.stack.len(), depth + 1);
+        }
+
+        loop {
+            let depth = self.stack.len();
+            let entry = match self.stack.last_mut()?.0.next() {
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+                Some(Err(err)) => return Some(Err(err)),
+                Some(Ok(entry)) => entry,
+            };
+
+            let path: PathBuf = entry.path().into();
+            let mut file_type = match entry.file_type() {
+                Ok(file_type) => file_type,
+                Err(err) => return Some(Err(err)),
+            };
+            if self.follow_links && file_type.is_symlink() {
+                file_type = match std::fs::metadata(&path) {
+                    Ok(meta) => meta.file_type(),
+                    Err(err) => return Some(Err(err)),
+                };
+            }
+            return Some(Ok(self.yield_entry(path, depth, file_type)));
+        }
+    }
+
+    /// Creates an entry and remembers to walk into it if it's a directory.
+    fn yield_entry(&mut self, path: PathBuf, depth: usize, file_type: FileType) -> WalkEntry {
+        if file_type.is_dir() && depth < self.max_depth {
+            self.descend = Some((path.clone(), depth));
+        }
+        WalkEntry {
+            path,
+            depth,
+            file_type,
+            follow_links: self.follow_links,
+        }
+    }
+
+    /// Starts reading a directory.
+    fn push(&mut self, dir: &Path) -> io::Result<()> {
+        let canonical = if self.follow_links {
+            let canonical = std::fs::canonicalize(dir)?;
+            if self
+                .stack
+                .iter()
+                .any(|(_, p)| p.as_ref() == Some(&canonical))
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("filesystem loop found at {}", dir.display()),
+                ));
+            }
+            Some(canonical)
+        } else {
+            None
+        };
+
+        let read_dir = std::fs::read_dir(dir)?;
+        self.stack.push((read_dir, canonical));
+        Ok(())
+    }
+}
+
+/// An entry yielded by [`WalkDir`].
+///
+/// [`WalkDir`]: struct.WalkDir.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Debug)]
+pub struct WalkEntry {
+    path: PathBuf,
+    depth: usize,
+    file_type: FileType,
+    follow_links: bool,
+}
+
+impl WalkEntry {
+    /// Returns the full path to this entry.
+    ///
+    /// The path is the root passed to [`walk_dir`] joined with the path of this entry relative to
+    /// the root.
+    ///
+    /// [`walk_dir`]: fn.walk_dir.html
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Consumes the entry and returns its path.
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+
+    /// Returns the bare name of this entry without the leading path.
+    pub fn file_name(&self) -> &OsStr {
+        self.path
+            .file_name()
+            .unwrap_or_else(|| self.path.as_os_str())
+    }
+
+    /// Returns the depth of this entry relative to the root.
+    ///
+    /// The root has depth 0.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the file type of this entry.
+    ///
+    /// If symbolic links are followed, this is the file type of the link target.
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// Reads the metadata for this entry.
+    ///
+    /// If symbolic links are followed, this is the metadata of the link target.
+    pub async fn metadata(&self) -> io::Result<Metadata> {
+        if self.follow_links {
+            crate::fs::metadata(&self.path).await
+        } else {
+            crate::fs::symlink_metadata(&self.path).await
+        }
+    }
+}
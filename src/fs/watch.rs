@@ -0,0 +1,1043 @@
+use std::pin::Pin;
+
+use crate::io;
+use crate::path::{Path, PathBuf};
+use crate::stream::Stream;
+use crate::task::{blocking, Context, Poll};
+
+/// Watches a file or directory for changes.
+///
+/// Returns a stream of [`Event`]s describing changes to `path`. If `path` is a directory, changes
+/// to its direct children are reported too. Subdirectories are not watched recursively.
+///
+/// The stream ends when the watched path is removed or moved away.
+///
+/// Filesystem watching is backed by `inotify(7)` on Linux and Android, FSEvents on macOS, and
+/// `ReadDirectoryChangesW` on Windows. On other platforms, this function returns an error.
+///
+/// FSEvents coalesces changes to the same path that happen in quick succession, so on macOS
+/// events may be reported in a different order than they happened.
+///
+/// [`Event`]: struct.Event.html
+///
+/// # Errors
+///
+/// An error will be returned in the following situations:
+///
+/// * `path` does not exist.
+/// * The current process lacks permissions to read `path`.
+/// * The limit on the number of watches has been reached.
+/// * Filesystem watching is not supported on this platform.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs;
+/// use async_std::prelude::*;
+///
+/// let mut events = fs::watch("config").await?;
+///
+/// while let Some(event) = events.next().await {
+///     let event = event?;
+///     println!("{:?}: {}", event.kind(), event.path().display());
+/// }
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn watch<P: AsRef<Path>>(path: P) -> io::Result<Watch> {
+    let path = path.as_ref().to_owned();
+    let (inner, path) = blocking::spawn(move || sys::Inner::new(&path).map(|i| (i, path))).await?;
+    Ok(Watch {
+        inner: sys::Watch::new(inner),
+        path,
+    })
+}
+
+/// A stream of changes to a file or directory.
+///
+/// This stream is returned by [`watch`] and yields items of type
+/// [`io::Result`]`<`[`Event`]`>`.
+///
+/// [`watch`]: fn.watch.html
+/// [`io::Result`]: ../io/type.Result.html
+/// [`Event`]: struct.Event.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct Watch {
+    /// The platform-specific event source.
+    inner: sys::Watch,
+
+    /// The path being watched.
+    path: PathBuf,
+}
+
+impl Stream for Watch {
+    type Item = io::Result<Event>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Watch { inner, path } = &mut *self;
+        inner.poll_next(cx, path)
+    }
+}
+
+/// A change to the filesystem.
+///
+/// Events are yielded by the [`Watch`] stream.
+///
+/// [`Watch`]: struct.Watch.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Event {
+    kind: EventKind,
+    path: PathBuf,
+}
+
+impl Event {
+    /// Returns the kind of change.
+    pub fn kind(&self) -> EventKind {
+        self.kind
+    }
+
+    /// Returns the path of the changed file or directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// The kind of change described by an [`Event`].
+///
+/// [`Event`]: struct.Event.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EventKind {
+    /// A file or directory was created.
+    Create,
+
+    /// The contents or metadata of a file or directory were modified.
+    Modify,
+
+    /// A file or directory was removed.
+    Remove,
+
+    /// A file or directory was moved away from this path.
+    RenameFrom,
+
+    /// A file or directory was moved to this path.
+    RenameTo,
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+mod sys {
+    use std::collections::VecDeque;
+    use std::ffi::{CString, OsStr};
+    use std::mem;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::io::RawFd;
+    use std::ptr;
+
+    use mio::unix::EventedFd;
+    use mio::{Evented, PollOpt, Ready, Token};
+
+    use super::{Event, EventKind};
+    use crate::io;
+    use crate::net::driver::Watcher;
+    use crate::path::{Path, PathBuf};
+    use crate::task::{Context, Poll};
+
+    /// The events we're interested in.
+    const MASK: u32 = libc::IN_CREATE
+        | libc::IN_MODIFY
+        | libc::IN_ATTRIB
+        | libc::IN_DELETE
+        | libc::IN_DELETE_SELF
+        | libc::IN_MOVED_FROM
+        | libc::IN_MOVED_TO
+        | libc::IN_MOVE_SELF;
+
+    /// An inotify instance watching a single path.
+    #[derive(Debug)]
+    pub struct Inner {
+        fd: RawFd,
+    }
+
+    impl Inner {
+        pub fn new(path: &Path) -> io::Result<Inner> {
+            let path = CString::new(path.as_os_str().as_bytes())?;
+
+            let fd = unsafe { libc::inotify_init1(libc::IN_NONBLOCK | libc::IN_CLOEXEC) };
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let inner = Inner { fd };
+
+            let wd = unsafe { libc::inotify_add_watch(fd, path.as_ptr(), MASK) };
+            if wd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(inner)
+        }
+
+        fn read(&self, buf: &mut [u8]) -> io::Result<usize> {
+            let n = unsafe { libc::read(self.fd, buf.as_mut_ptr() as *mut _, buf.len()) };
+            if n < 0 {
+                Err(io::Error::last_os_error())
+            } else {
+                Ok(n as usize)
+            }
+        }
+    }
+
+    impl Drop for Inner {
+        fn drop(&mut self) {
+            unsafe {
+                libc::close(self.fd);
+            }
+        }
+    }
+
+    impl Evented for Inner {
+        fn register(
+            &self,
+            poll: &mio::Poll,
+            token: Token,
+            interest: Ready,
+            opts: PollOpt,
+        ) -> io::Result<()> {
+            EventedFd(&self.fd).register(poll, token, interest, opts)
+        }
+
+        fn reregister(
+            &self,
+            poll: &mio::Poll,
+            token: Token,
+            interest: Ready,
+            opts: PollOpt,
+        ) -> io::Result<()> {
+            EventedFd(&self.fd).reregister(poll, token, interest, opts)
+        }
+
+        fn deregister(&self, poll: &mio::Poll) -> io::Result<()> {
+            EventedFd(&self.fd).deregister(poll)
+        }
+    }
+
+    /// The state of a watch stream.
+    #[derive(Debug)]
+    pub struct Watch {
+        watcher: Watcher<Inner>,
+
+        /// A buffer for reading raw events.
+        buf: Vec<u8>,
+
+        /// Parsed events that haven't been yielded yet.
+        pending: VecDeque<io::Result<Event>>,
+
+        /// Set to `true` when the watch has been removed.
+        done: bool,
+    }
+
+    impl Watch {
+        pub fn new(inner: Inner) -> Watch {
+            Watch {
+                watcher: Watcher::new(inner),
+                buf: vec![0; 4096],
+                pending: VecDeque::new(),
+                done: false,
+            }
+        }
+
+        pub fn poll_next(
+            &mut self,
+            cx: &mut Context<'_>,
+            path: &Path,
+        ) -> Poll<Option<io::Result<Event>>> {
+            loop {
+                if let Some(event) = self.pending.pop_front() {
+                    return Poll::Ready(Some(event));
+                }
+                if self.done {
+                    return Poll::Ready(None);
+                }
+
+                let Watch { watcher, buf, .. } = self;
+                let n = match futures_core::ready!(watcher.poll_read_with(cx, |i| i.read(buf))) {
+                    Ok(n) => n,
+                    Err(err) => return Poll::Ready(Some(Err(err))),
+                };
+                self.parse(n, path);
+            }
+        }
+
+        /// Parses `n` bytes of raw events in the buffer.
+        fn parse(&mut self, n: usize, path: &Path) {
+            let header = mem::size_of::<libc::inotify_event>();
+            let mut offset = 0;
+
+            while offset + header <= n {
+                let event = unsafe {
+                    ptr::read_unaligned(self.buf[offset..].as_ptr() as *const libc::inotify_event)
+                };
+                let name = &self.buf[offset + header..offset + header + event.len as usize];
+                offset += header + event.len as usize;
+
+                // The name is padded with null bytes.
+                let name = match name.iter().position(|&b| b == 0) {
+                    Some(end) => &name[..end],
+                    None => name,
+                };
+                let path: PathBuf = if name.is_empty() {
+                    path.to_owned()
+                } else {
+                    path.join(OsStr::from_bytes(name))
+                };
+
+                if event.mask & libc::IN_Q_OVERFLOW != 0 {
+                    self.pending.push_back(Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "filesystem event queue overflowed",
+                    )));
+                }
+                if event.mask & libc::IN_IGNORED != 0 {
+                    self.done = true;
+                }
+
+                let kind = if event.mask & libc::IN_CREATE != 0 {
+                    EventKind::Create
+                } else if event.mask & (libc::IN_MODIFY | libc::IN_ATTRIB) != 0 {
+                    EventKind::Modify
+                } else if event.mask & (libc::IN_DELETE | libc::IN_DELETE_SELF) != 0 {
+                    EventKind::Remove
+                } else if event.mask & (libc::IN_MOVED_FROM | libc::IN_MOVE_SELF) != 0 {
+                    EventKind::RenameFrom
+                } else if event.mask & libc::IN_MOVED_TO != 0 {
+                    EventKind::RenameTo
+                } else {
+                    continue;
+                };
+
+                // The watch can't follow a moved path, so end the stream.
+                if event.mask & libc::IN_MOVE_SELF != 0 {
+                    self.done = true;
+                }
+
+                self.pending.push_back(Ok(Event { kind, path }));
+            }
+        }
+    }
+}
+
+/// Events read by a background thread that haven't been yielded by the stream yet.
+#[cfg(any(target_os = "macos", windows))]
+#[derive(Debug, Default)]
+struct Queue {
+    state: std::sync::Mutex<QueueState>,
+}
+
+#[cfg(any(target_os = "macos", windows))]
+#[derive(Debug, Default)]
+struct QueueState {
+    /// Events along with the name of the changed child, or `None` if the watched path changed.
+    events: std::collections::VecDeque<io::Result<(EventKind, Option<std::ffi::OsString>)>>,
+
+    /// Set to `true` once no more events will be pushed.
+    done: bool,
+
+    /// The task waiting for the next event.
+    waker: Option<std::task::Waker>,
+}
+
+#[cfg(any(target_os = "macos", windows))]
+impl Queue {
+    fn push(&self, event: io::Result<(EventKind, Option<std::ffi::OsString>)>) {
+        let waker = {
+            let mut state = self.state.lock().unwrap();
+            state.events.push_back(event);
+            state.waker.take()
+        };
+        if let Some(w) = waker {
+            w.wake();
+        }
+    }
+
+    fn close(&self) {
+        let waker = {
+            let mut state = self.state.lock().unwrap();
+            state.done = true;
+            state.waker.take()
+        };
+        if let Some(w) = waker {
+            w.wake();
+        }
+    }
+
+    fn poll_next(&self, cx: &mut Context<'_>, path: &Path) -> Poll<Option<io::Result<Event>>> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(event) = state.events.pop_front() {
+            return Poll::Ready(Some(event.map(|(kind, name)| Event {
+                kind,
+                path: match name {
+                    Some(name) => path.join(Path::new(&name)),
+                    None => path.to_owned(),
+                },
+            })));
+        }
+        if state.done {
+            return Poll::Ready(None);
+        }
+
+        match &state.waker {
+            Some(w) if w.will_wake(cx.waker()) => {}
+            _ => state.waker = Some(cx.waker().clone()),
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod sys {
+    use std::ffi::{CStr, CString, OsStr};
+    use std::os::raw::{c_char, c_void};
+    use std::os::unix::ffi::OsStrExt;
+    use std::ptr;
+    use std::slice;
+    use std::sync::Arc;
+
+    use super::{Event, EventKind, Queue};
+    use crate::io;
+    use crate::path::Path;
+    use crate::task::{Context, Poll};
+
+    type FSEventStreamCallback = extern "C" fn(
+        stream: *mut c_void,
+        info: *mut c_void,
+        num_events: usize,
+        paths: *mut c_void,
+        flags: *const u32,
+        ids: *const u64,
+    );
+
+    #[repr(C)]
+    struct FSEventStreamContext {
+        version: isize,
+        info: *mut c_void,
+        retain: Option<extern "C" fn(*const c_void) -> *const c_void>,
+        release: Option<extern "C" fn(*const c_void)>,
+        copy_description: Option<extern "C" fn(*const c_void) -> *const c_void>,
+    }
+
+    #[repr(C)]
+    struct CFArrayCallBacks {
+        version: isize,
+        retain: *const c_void,
+        release: *const c_void,
+        copy_description: *const c_void,
+        equal: *const c_void,
+    }
+
+    #[link(name = "CoreFoundation", kind = "framework")]
+    extern "C" {
+        static kCFTypeArrayCallBacks: CFArrayCallBacks;
+
+        fn CFStringCreateWithFileSystemRepresentation(
+            alloc: *const c_void,
+            buffer: *const c_char,
+        ) -> *const c_void;
+        fn CFArrayCreate(
+            alloc: *const c_void,
+            values: *const *const c_void,
+            num_values: isize,
+            callbacks: *const CFArrayCallBacks,
+        ) -> *const c_void;
+        fn CFRelease(cf: *const c_void);
+    }
+
+    #[link(name = "CoreServices", kind = "framework")]
+    extern "C" {
+        fn FSEventStreamCreate(
+            alloc: *const c_void,
+            callback: FSEventStreamCallback,
+            context: *const FSEventStreamContext,
+            paths: *const c_void,
+            since_when: u64,
+            latency: f64,
+            flags: u32,
+        ) -> *mut c_void;
+        fn FSEventStreamSetDispatchQueue(stream: *mut c_void, queue: *mut c_void);
+        fn FSEventStreamStart(stream: *mut c_void) -> u8;
+        fn FSEventStreamStop(stream: *mut c_void);
+        fn FSEventStreamInvalidate(stream: *mut c_void);
+        fn FSEventStreamRelease(stream: *mut c_void);
+    }
+
+    extern "C" {
+        fn dispatch_queue_create(label: *const c_char, attr: *const c_void) -> *mut c_void;
+        fn dispatch_release(object: *mut c_void);
+    }
+
+    const SINCE_NOW: u64 = !0;
+
+    const CREATE_NO_DEFER: u32 = 0x02;
+    const CREATE_WATCH_ROOT: u32 = 0x04;
+    const CREATE_FILE_EVENTS: u32 = 0x10;
+
+    const MUST_SCAN_SUB_DIRS: u32 = 0x01;
+    const USER_DROPPED: u32 = 0x02;
+    const KERNEL_DROPPED: u32 = 0x04;
+    const ROOT_CHANGED: u32 = 0x20;
+    const ITEM_CREATED: u32 = 0x100;
+    const ITEM_REMOVED: u32 = 0x200;
+    const ITEM_INODE_META_MOD: u32 = 0x400;
+    const ITEM_RENAMED: u32 = 0x800;
+    const ITEM_MODIFIED: u32 = 0x1000;
+    const ITEM_FINDER_INFO_MOD: u32 = 0x2000;
+    const ITEM_CHANGE_OWNER: u32 = 0x4000;
+    const ITEM_XATTR_MOD: u32 = 0x8000;
+
+    /// The state shared with the callback, which runs on a dispatch queue.
+    #[derive(Debug)]
+    struct State {
+        queue: Queue,
+
+        /// The canonical watched path, since FSEvents reports canonical paths.
+        root: std::path::PathBuf,
+
+        /// Whether the watched path is a directory.
+        is_dir: bool,
+    }
+
+    /// An FSEvents stream watching a single path.
+    ///
+    /// FSEvents can only watch directories, and does so recursively, so the callback filters out
+    /// everything but the watched path and its direct children.
+    #[derive(Debug)]
+    pub struct Inner {
+        stream: *mut c_void,
+        queue: *mut c_void,
+        state: Arc<State>,
+    }
+
+    unsafe impl Send for Inner {}
+    unsafe impl Sync for Inner {}
+
+    impl Inner {
+        pub fn new(path: &Path) -> io::Result<Inner> {
+            let root = std::fs::canonicalize(path)?;
+            let is_dir = std::fs::metadata(&root)?.is_dir();
+            let dir = match root.parent() {
+                Some(parent) if !is_dir => parent.to_owned(),
+                _ => root.clone(),
+            };
+            let dir = CString::new(dir.as_os_str().as_bytes())?;
+
+            let state = Arc::new(State {
+                queue: Queue::default(),
+                root,
+                is_dir,
+            });
+            let context = FSEventStreamContext {
+                version: 0,
+                info: Arc::as_ptr(&state) as *mut c_void,
+                retain: Some(retain),
+                release: Some(release),
+                copy_description: None,
+            };
+
+            unsafe {
+                let dir = CFStringCreateWithFileSystemRepresentation(ptr::null(), dir.as_ptr());
+                if dir.is_null() {
+                    return Err(io::Error::new(io::ErrorKind::Other, "invalid path"));
+                }
+                let paths = CFArrayCreate(ptr::null(), &dir, 1, &kCFTypeArrayCallBacks);
+                CFRelease(dir);
+                if paths.is_null() {
+                    return Err(io::Error::new(io::ErrorKind::Other, "out of memory"));
+                }
+
+                let stream = FSEventStreamCreate(
+                    ptr::null(),
+                    callback,
+                    &context,
+                    paths,
+                    SINCE_NOW,
+                    0.0,
+                    CREATE_NO_DEFER | CREATE_WATCH_ROOT | CREATE_FILE_EVENTS,
+                );
+                CFRelease(paths);
+                if stream.is_null() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "failed to create an event stream",
+                    ));
+                }
+
+                let queue = dispatch_queue_create(b"async-std/watch\0".as_ptr() as _, ptr::null());
+                FSEventStreamSetDispatchQueue(stream, queue);
+                let inner = Inner {
+                    stream,
+                    queue,
+                    state,
+                };
+
+                if FSEventStreamStart(stream) == 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "failed to start the event stream",
+                    ));
+                }
+                Ok(inner)
+            }
+        }
+    }
+
+    impl Drop for Inner {
+        fn drop(&mut self) {
+            unsafe {
+                FSEventStreamStop(self.stream);
+                FSEventStreamInvalidate(self.stream);
+                FSEventStreamRelease(self.stream);
+                dispatch_release(self.queue);
+            }
+        }
+    }
+
+    extern "C" fn retain(info: *const c_void) -> *const c_void {
+        unsafe { Arc::increment_strong_count(info as *const State) };
+        info
+    }
+
+    extern "C" fn release(info: *const c_void) {
+        unsafe { Arc::decrement_strong_count(info as *const State) };
+    }
+
+    extern "C" fn callback(
+        _: *mut c_void,
+        info: *mut c_void,
+        num_events: usize,
+        paths: *mut c_void,
+        flags: *const u32,
+        _: *const u64,
+    ) {
+        let state = unsafe { &*(info as *const State) };
+        let paths = unsafe { slice::from_raw_parts(paths as *const *const c_char, num_events) };
+        let flags = unsafe { slice::from_raw_parts(flags, num_events) };
+
+        for (&path, &flags) in paths.iter().zip(flags) {
+            let path = unsafe { CStr::from_ptr(path) };
+            state.handle(std::path::Path::new(OsStr::from_bytes(path.to_bytes())), flags);
+        }
+    }
+
+    impl State {
+        /// Pushes the events described by `flags`.
+        ///
+        /// FSEvents coalesces changes to the same path, so a single set of flags may describe
+        /// several events.
+        fn handle(&self, path: &std::path::Path, flags: u32) {
+            if flags & (MUST_SCAN_SUB_DIRS | USER_DROPPED | KERNEL_DROPPED) != 0 {
+                self.queue.push(Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "filesystem event queue overflowed",
+                )));
+            }
+            if flags & ROOT_CHANGED != 0 {
+                // A parent of the watched path was moved or removed.
+                if !self.root.exists() {
+                    self.queue.push(Ok((EventKind::Remove, None)));
+                }
+                self.queue.close();
+                return;
+            }
+
+            let name = if path == self.root {
+                None
+            } else if self.is_dir && path.parent() == Some(&*self.root) {
+                path.file_name().map(|n| n.to_owned())
+            } else {
+                return;
+            };
+            let exists = path.exists();
+
+            let mut kinds = Vec::new();
+            if flags & ITEM_CREATED != 0 {
+                kinds.push(EventKind::Create);
+            }
+            if flags
+                & (ITEM_MODIFIED
+                    | ITEM_INODE_META_MOD
+                    | ITEM_FINDER_INFO_MOD
+                    | ITEM_CHANGE_OWNER
+                    | ITEM_XATTR_MOD)
+                != 0
+            {
+                kinds.push(EventKind::Modify);
+            }
+            if flags & ITEM_RENAMED != 0 {
+                kinds.push(if exists {
+                    EventKind::RenameTo
+                } else {
+                    EventKind::RenameFrom
+                });
+            }
+            if flags & ITEM_REMOVED != 0 && !exists {
+                kinds.push(EventKind::Remove);
+            }
+
+            for kind in kinds {
+                self.queue.push(Ok((kind, name.clone())));
+            }
+
+            // The watch can't follow a moved path, so end the stream.
+            if name.is_none() && !exists {
+                self.queue.close();
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct Watch(Inner);
+
+    impl Watch {
+        pub fn new(inner: Inner) -> Watch {
+            Watch(inner)
+        }
+
+        pub fn poll_next(
+            &mut self,
+            cx: &mut Context<'_>,
+            path: &Path,
+        ) -> Poll<Option<io::Result<Event>>> {
+            self.0.state.queue.poll_next(cx, path)
+        }
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use std::ffi::OsString;
+    use std::mem;
+    use std::os::windows::ffi::{OsStrExt, OsStringExt};
+    use std::ptr;
+    use std::slice;
+    use std::sync::Arc;
+    use std::thread;
+
+    use winapi::shared::minwindef::{DWORD, FALSE, TRUE};
+    use winapi::um::fileapi::{CreateFileW, OPEN_EXISTING};
+    use winapi::um::handleapi::{CloseHandle, INVALID_HANDLE_VALUE};
+    use winapi::um::ioapiset::{CancelIo, GetOverlappedResult};
+    use winapi::um::minwinbase::OVERLAPPED;
+    use winapi::um::synchapi::{CreateEventW, SetEvent, WaitForMultipleObjects};
+    use winapi::um::winbase::{
+        ReadDirectoryChangesW, FILE_FLAG_BACKUP_SEMANTICS, FILE_FLAG_OVERLAPPED, INFINITE,
+        WAIT_OBJECT_0,
+    };
+    use winapi::um::winnt::{
+        FILE_ACTION_ADDED, FILE_ACTION_MODIFIED, FILE_ACTION_REMOVED,
+        FILE_ACTION_RENAMED_NEW_NAME, FILE_ACTION_RENAMED_OLD_NAME, FILE_LIST_DIRECTORY,
+        FILE_NOTIFY_CHANGE_ATTRIBUTES, FILE_NOTIFY_CHANGE_CREATION, FILE_NOTIFY_CHANGE_DIR_NAME,
+        FILE_NOTIFY_CHANGE_FILE_NAME, FILE_NOTIFY_CHANGE_LAST_WRITE, FILE_NOTIFY_CHANGE_SIZE,
+        FILE_NOTIFY_INFORMATION, FILE_SHARE_DELETE, FILE_SHARE_READ, FILE_SHARE_WRITE, HANDLE,
+    };
+
+    use super::{Event, EventKind, Queue};
+    use crate::io;
+    use crate::path::Path;
+    use crate::task::{Context, Poll};
+
+    /// The changes we're interested in.
+    const FILTER: DWORD = FILE_NOTIFY_CHANGE_FILE_NAME
+        | FILE_NOTIFY_CHANGE_DIR_NAME
+        | FILE_NOTIFY_CHANGE_ATTRIBUTES
+        | FILE_NOTIFY_CHANGE_SIZE
+        | FILE_NOTIFY_CHANGE_LAST_WRITE
+        | FILE_NOTIFY_CHANGE_CREATION;
+
+    /// A handle that is closed on drop.
+    #[derive(Debug)]
+    struct Handle(HANDLE);
+
+    unsafe impl Send for Handle {}
+    unsafe impl Sync for Handle {}
+
+    impl Drop for Handle {
+        fn drop(&mut self) {
+            unsafe {
+                CloseHandle(self.0);
+            }
+        }
+    }
+
+    /// A thread reading changes to a single path with `ReadDirectoryChangesW`.
+    ///
+    /// Only directories can be watched, so a file is watched through its parent directory and
+    /// changes to its siblings are skipped.
+    #[derive(Debug)]
+    pub struct Inner {
+        queue: Arc<Queue>,
+
+        /// Signaled to stop the thread.
+        stop: Arc<Handle>,
+    }
+
+    impl Inner {
+        pub fn new(path: &Path) -> io::Result<Inner> {
+            let path: std::path::PathBuf = path.to_owned().into();
+            let (dir, name) = if std::fs::metadata(&path)?.is_dir() {
+                (path.clone(), None)
+            } else {
+                let dir = match path.parent() {
+                    Some(dir) if dir.as_os_str().is_empty() => ".".into(),
+                    Some(dir) => dir.to_owned(),
+                    None => ".".into(),
+                };
+                (dir, path.file_name().map(|n| n.to_owned()))
+            };
+
+            let wide: Vec<u16> = dir.as_os_str().encode_wide().chain(Some(0)).collect();
+            let dir = unsafe {
+                CreateFileW(
+                    wide.as_ptr(),
+                    FILE_LIST_DIRECTORY,
+                    FILE_SHARE_READ | FILE_SHARE_WRITE | FILE_SHARE_DELETE,
+                    ptr::null_mut(),
+                    OPEN_EXISTING,
+                    FILE_FLAG_BACKUP_SEMANTICS | FILE_FLAG_OVERLAPPED,
+                    ptr::null_mut(),
+                )
+            };
+            if dir == INVALID_HANDLE_VALUE {
+                return Err(io::Error::last_os_error());
+            }
+            let dir = Handle(dir);
+
+            let stop = Arc::new(event()?);
+            let queue = Arc::new(Queue::default());
+            let thread = Thread {
+                dir,
+                name,
+                path,
+                stop: stop.clone(),
+                queue: queue.clone(),
+            };
+            thread::Builder::new()
+                .name("async-std/watch".to_string())
+                .spawn(move || thread.run())?;
+
+            Ok(Inner { queue, stop })
+        }
+    }
+
+    impl Drop for Inner {
+        fn drop(&mut self) {
+            unsafe {
+                SetEvent(self.stop.0);
+            }
+        }
+    }
+
+    /// Creates a manual-reset event.
+    fn event() -> io::Result<Handle> {
+        let event = unsafe { CreateEventW(ptr::null_mut(), TRUE, FALSE, ptr::null()) };
+        if event.is_null() {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(Handle(event))
+        }
+    }
+
+    /// The state of the thread reading changes.
+    struct Thread {
+        dir: Handle,
+
+        /// The name of the watched file, or `None` if a directory is watched.
+        name: Option<OsString>,
+
+        /// The watched path.
+        path: std::path::PathBuf,
+
+        stop: Arc<Handle>,
+        queue: Arc<Queue>,
+    }
+
+    impl Thread {
+        fn run(self) {
+            match self.read() {
+                Ok(()) => {}
+                // The error is likely caused by the watched directory being removed.
+                Err(_) if !self.path.exists() => self.queue.push(Ok((EventKind::Remove, None))),
+                Err(err) => self.queue.push(Err(err)),
+            }
+            self.queue.close();
+        }
+
+        /// Reads changes until the watch is dropped or the watched path is gone.
+        fn read(&self) -> io::Result<()> {
+            let event = event()?;
+
+            // The buffer must be aligned to a `DWORD`.
+            let mut buf = vec![0 as DWORD; 16 * 1024];
+
+            loop {
+                let mut overlapped: OVERLAPPED = unsafe { mem::zeroed() };
+                overlapped.hEvent = event.0;
+
+                let ok = unsafe {
+                    ReadDirectoryChangesW(
+                        self.dir.0,
+                        buf.as_mut_ptr() as *mut _,
+                        (buf.len() * mem::size_of::<DWORD>()) as DWORD,
+                        FALSE,
+                        FILTER,
+                        ptr::null_mut(),
+                        &mut overlapped,
+                        None,
+                    )
+                };
+                if ok == 0 {
+                    return Err(io::Error::last_os_error());
+                }
+
+                let handles = [event.0, self.stop.0];
+                let ret = unsafe { WaitForMultipleObjects(2, handles.as_ptr(), FALSE, INFINITE) };
+
+                let mut n = 0;
+                if ret != WAIT_OBJECT_0 {
+                    // Cancel the read and wait for it, since it still refers to the buffer.
+                    unsafe {
+                        CancelIo(self.dir.0);
+                        GetOverlappedResult(self.dir.0, &mut overlapped, &mut n, TRUE);
+                    }
+                    return if ret == WAIT_OBJECT_0 + 1 {
+                        Ok(())
+                    } else {
+                        Err(io::Error::last_os_error())
+                    };
+                }
+
+                if unsafe { GetOverlappedResult(self.dir.0, &mut overlapped, &mut n, FALSE) } == 0
+                {
+                    return Err(io::Error::last_os_error());
+                }
+
+                if n == 0 {
+                    self.queue.push(Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "filesystem event queue overflowed",
+                    )));
+                } else if self.parse(buf.as_ptr() as *const u8) {
+                    return Ok(());
+                }
+            }
+        }
+
+        /// Parses raw events in the buffer, returning `true` if the watched file is gone.
+        fn parse(&self, mut ptr: *const u8) -> bool {
+            let mut gone = false;
+
+            loop {
+                let info = unsafe { &*(ptr as *const FILE_NOTIFY_INFORMATION) };
+                let name = unsafe {
+                    slice::from_raw_parts(
+                        info.FileName.as_ptr(),
+                        info.FileNameLength as usize / mem::size_of::<u16>(),
+                    )
+                };
+                let name = OsString::from_wide(name);
+
+                let kind = match info.Action {
+                    FILE_ACTION_ADDED => Some(EventKind::Create),
+                    FILE_ACTION_MODIFIED => Some(EventKind::Modify),
+                    FILE_ACTION_REMOVED => Some(EventKind::Remove),
+                    FILE_ACTION_RENAMED_OLD_NAME => Some(EventKind::RenameFrom),
+                    FILE_ACTION_RENAMED_NEW_NAME => Some(EventKind::RenameTo),
+                    _ => None,
+                };
+
+                if let Some(kind) = kind {
+                    match &self.name {
+                        None => self.queue.push(Ok((kind, Some(name)))),
+                        Some(file) if *file == name => {
+                            self.queue.push(Ok((kind, None)));
+                            if kind == EventKind::Remove || kind == EventKind::RenameFrom {
+                                gone = true;
+                            }
+                        }
+                        Some(_) => {}
+                    }
+                }
+
+                if info.NextEntryOffset == 0 {
+                    return gone;
+                }
+                ptr = unsafe { ptr.add(info.NextEntryOffset as usize) };
+            }
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct Watch(Inner);
+
+    impl Watch {
+        pub fn new(inner: Inner) -> Watch {
+            Watch(inner)
+        }
+
+        pub fn poll_next(
+            &mut self,
+            cx: &mut Context<'_>,
+            path: &Path,
+        ) -> Poll<Option<io::Result<Event>>> {
+            self.0.queue.poll_next(cx, path)
+        }
+    }
+}
+
+#[cfg(not(any(
+    target_os = "linux",
+    target_os = "android",
+    target_os = "macos",
+    windows
+)))]
+mod sys {
+    use super::Event;
+    use crate::io;
+    use crate::path::Path;
+    use crate::task::{Context, Poll};
+
+    #[derive(Debug)]
+    pub enum Inner {}
+
+    impl Inner {
+        pub fn new(_: &Path) -> io::Result<Inner> {
+            Err(io::Error::new(
+                io::ErrorKind::Other,
+                "filesystem watching is not supported on this platform",
+            ))
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct Watch(Inner);
+
+    impl Watch {
+        pub fn new(inner: Inner) -> Watch {
+            Watch(inner)
+        }
+
+        pub fn poll_next(
+            &mut self,
+            _: &mut Context<'_>,
+            _: &Path,
+        ) -> Poll<Option<io::Result<Event>>> {
+            match self.0 {}
+        }
+    }
+}
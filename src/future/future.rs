@@ -1,3 +1,9 @@
+cfg_unstable! {
+    use std::time::Duration;
+
+    use crate::time::Timeout;
+}
+
 extension_trait! {
     use std::pin::Pin;
     use std::ops::{Deref, DerefMut};
@@ -99,6 +105,39 @@ extension_trait! {
     }
 
     pub trait FutureExt: std::future::Future {
+        #[doc = r#"
+            Limits the time the future has to complete.
+
+            The returned future resolves to `Err(TimeoutError)` if this future hasn't completed
+            within `dur`.
+
+            # Examples
+
+            ```
+            # async_std::task::block_on(async {
+            #
+            use std::time::Duration;
+
+            use async_std::future;
+            use async_std::prelude::*;
+
+            let never = future::pending::<()>();
+            assert!(never.timeout(Duration::from_millis(5)).await.is_err());
+
+            let ready = future::ready(1);
+            assert_eq!(ready.timeout(Duration::from_millis(5)).await, Ok(1));
+            #
+            # })
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn timeout(self, dur: Duration) -> Timeout<Self>
+        where
+            Self: Sized,
+        {
+            Timeout::new(self, dur)
+        }
     }
 
     impl<F: Future + Unpin + ?Sized> Future for Box<F> {
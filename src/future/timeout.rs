@@ -13,6 +13,10 @@ use crate::task::{Context, Poll};
 /// If you want to await an I/O future consider using
 /// [`io::timeout`](../io/fn.timeout.html) instead.
 ///
+/// With the `unstable` feature, the same is available in method form as [`Future::timeout`].
+///
+/// [`Future::timeout`]: trait.Future.html#method.timeout
+///
 /// # Examples
 ///
 /// ```
@@ -57,7 +61,7 @@ impl<F: Future> Future for TimeoutFuture<F> {
         match self.as_mut().future().poll(cx) {
             Poll::Ready(v) => Poll::Ready(Ok(v)),
             Poll::Pending => match self.delay().poll(cx) {
-                Poll::Ready(_) => Poll::Ready(Err(TimeoutError { _private: () })),
+                Poll::Ready(_) => Poll::Ready(Err(TimeoutError::new())),
                 Poll::Pending => Poll::Pending,
             },
         }
@@ -70,6 +74,12 @@ pub struct TimeoutError {
     _private: (),
 }
 
+impl TimeoutError {
+    pub(crate) fn new() -> TimeoutError {
+        TimeoutError { _private: () }
+    }
+}
+
 impl Error for TimeoutError {}
 
 impl fmt::Display for TimeoutError {
@@ -23,14 +23,15 @@ const DEFAULT_CAPACITY: usize = 8 * 1024;
 /// times. It also provides no advantage when writing to a destination that is
 /// in memory, like a `Vec<u8>`.
 ///
-/// When the `BufWriter` is dropped, the contents of its buffer will be written
-/// out. However, any errors that happen in the process of flushing the buffer
-/// when the writer is dropped will be ignored. Code that wishes to handle such
-/// errors must manually call [`flush`] before the writer is dropped.
+/// Unlike [`std::io::BufWriter`], this type doesn't write out its buffer when it is dropped,
+/// because writing may have to wait and a destructor can't. Data that is still buffered when the
+/// `BufWriter` is dropped is lost, so call [`flush`] (or [`into_inner`], which flushes) once
+/// everything has been written.
 ///
-/// This type is an async version of [`std::io::BufReader`].
+/// This type is an async version of [`std::io::BufWriter`].
 ///
-/// [`std::io::BufReader`]: https://doc.rust-lang.org/std/io/struct.BufReader.html
+/// [`std::io::BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
+/// [`into_inner`]: #method.into_inner
 ///
 /// # Examples
 ///
@@ -66,13 +67,14 @@ const DEFAULT_CAPACITY: usize = 8 * 1024;
 ///     let arr = [i+1];
 ///     stream.write(&arr).await?;
 /// };
+/// stream.flush().await?;
 /// #
 /// # Ok(()) }) }
 /// ```
 ///
 /// By wrapping the stream with a `BufWriter`, these ten writes are all grouped
 /// together by the buffer, and will all be written out in one system call when
-/// the `stream` is dropped.
+/// the `stream` is flushed.
 ///
 /// [`Write`]: trait.Write.html
 /// [`TcpStream::write`]: ../net/struct.TcpStream.html#method.write
@@ -297,7 +299,7 @@ impl<W: Write> Write for BufWriter<W> {
 
 impl<W: Write + fmt::Debug> fmt::Debug for BufWriter<W> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("BufReader")
+        f.debug_struct("BufWriter")
             .field("writer", &self.inner)
             .field("buf", &self.buf)
             .finish()
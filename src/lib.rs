@@ -40,6 +40,15 @@
 //! version = "0.99"
 //! features = ["unstable"]
 //! ```
+//!
+//! The `#[async_std::main]`, `#[async_std::test]` and `#[async_std::bench]` attributes are
+//! available only when the `attributes` Cargo feature is enabled:
+//!
+//! ```toml
+//! [dependencies.async-std]
+//! version = "0.99"
+//! features = ["attributes"]
+//! ```
 
 #![cfg_attr(feature = "docs", feature(doc_cfg))]
 #![warn(missing_docs, missing_debug_implementations, rust_2018_idioms)]
@@ -65,6 +74,8 @@ pub mod task;
 cfg_unstable! {
     pub mod pin;
     pub mod process;
+    pub mod signal;
+    pub mod time;
 
     mod unit;
     mod vec;
@@ -77,4 +88,9 @@ cfg_unstable! {
     pub use std::{write, writeln};
 }
 
+#[cfg(feature = "attributes")]
+#[cfg_attr(feature = "docs", doc(cfg(attributes)))]
+#[doc(inline)]
+pub use async_attributes::{bench, main, test};
+
 mod macros;
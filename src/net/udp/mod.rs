@@ -449,6 +449,29 @@ impl UdpSocket {
             .get_ref()
             .leave_multicast_v6(multiaddr, interface)
     }
+
+    /// Gets the value of the `SO_ERROR` option on this socket.
+    ///
+    /// This will retrieve the stored error in the underlying socket, clearing the field in the
+    /// process. This can be useful for checking errors between calls.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::net::UdpSocket;
+    ///
+    /// let socket = UdpSocket::bind("127.0.0.1:0").await?;
+    /// if let Some(err) = socket.take_error()? {
+    ///     println!("socket error: {}", err);
+    /// }
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub fn take_error(&self) -> io::Result<Option<io::Error>> {
+        self.watcher.get_ref().take_error()
+    }
 }
 
 impl From<std::net::UdpSocket> for UdpSocket {
@@ -29,8 +29,144 @@ pub async fn symlink<P: AsRef<Path>, Q: AsRef<Path>>(src: P, dst: Q) -> io::Resu
     blocking::spawn(move || std::os::unix::fs::symlink(&src, &dst)).await
 }
 
+/// Changes the owner and group of a file or directory.
+///
+/// Passing `None` for `uid` or `gid` leaves the owner or the group unchanged. Symbolic links are
+/// followed. Changing the owner usually requires superuser privileges.
+///
+/// This function is an async version of `chown(2)`.
+///
+/// # Errors
+///
+/// An error will be returned in the following situations:
+///
+/// * `path` does not point to an existing file or directory.
+/// * The current process lacks permissions to change the ownership.
+/// * Some other I/O error occurred.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::os::unix::fs::chown;
+///
+/// chown("/srv/app/config.toml", Some(1000), Some(1000)).await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn chown<P: AsRef<Path>>(path: P, uid: Option<u32>, gid: Option<u32>) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_ref().as_os_str().as_bytes())?;
+    blocking::spawn(move || {
+        let (uid, gid) = owner(uid, gid);
+        cvt(unsafe { libc::chown(path.as_ptr(), uid, gid) })
+    })
+    .await
+}
+
+/// Changes the owner and group of an open file.
+///
+/// Passing `None` for `uid` or `gid` leaves the owner or the group unchanged. Pending writes are
+/// flushed first. Changing the owner usually requires superuser privileges.
+///
+/// This function is an async version of `fchown(2)`.
+///
+/// # Errors
+///
+/// An error will be returned in the following situations:
+///
+/// * The current process lacks permissions to change the ownership.
+/// * Some other I/O error occurred.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::fs::File;
+/// use async_std::os::unix::fs::fchown;
+///
+/// let file = File::create("/srv/app/state.json").await?;
+/// fchown(&file, Some(1000), None).await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn fchown(file: &crate::fs::File, uid: Option<u32>, gid: Option<u32>) -> io::Result<()> {
+    use std::os::unix::io::AsRawFd;
+
+    let file = file.flushed_file().await?;
+    blocking::spawn(move || {
+        let (uid, gid) = owner(uid, gid);
+        cvt(unsafe { libc::fchown(file.as_raw_fd(), uid, gid) })
+    })
+    .await
+}
+
+/// Changes the permissions of a file or directory to the given mode.
+///
+/// This is a shorthand for [`fs::set_permissions`] with [`Permissions`] built from `mode`, like
+/// `0o644`. Symbolic links are followed.
+///
+/// [`fs::set_permissions`]: ../../../fs/fn.set_permissions.html
+/// [`Permissions`]: ../../../fs/struct.Permissions.html
+///
+/// # Errors
+///
+/// An error will be returned in the following situations:
+///
+/// * `path` does not point to an existing file or directory.
+/// * The current process lacks permissions to change attributes on the file or directory.
+/// * Some other I/O error occurred.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::os::unix::fs::set_permissions_mode;
+///
+/// set_permissions_mode("id_rsa", 0o600).await?;
+/// #
+/// # Ok(()) }) }
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub async fn set_permissions_mode<P: AsRef<Path>>(path: P, mode: u32) -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let path = path.as_ref().to_owned();
+    let perm = std::fs::Permissions::from_mode(mode);
+    blocking::spawn(move || std::fs::set_permissions(&path, perm)).await
+}
+
+/// Converts optional ids into the form expected by `chown(2)`, where `-1` means no change.
+#[cfg(feature = "unstable")]
+fn owner(uid: Option<u32>, gid: Option<u32>) -> (libc::uid_t, libc::gid_t) {
+    (
+        uid.map_or(!0, |uid| uid as libc::uid_t),
+        gid.map_or(!0, |gid| gid as libc::gid_t),
+    )
+}
+
+/// Converts the return value of a system call into an `io::Result`.
+#[cfg(feature = "unstable")]
+fn cvt(ret: libc::c_int) -> io::Result<()> {
+    if ret == -1 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(())
+    }
+}
+
 cfg_not_docs! {
-    pub use std::os::unix::fs::{DirBuilderExt, DirEntryExt, OpenOptionsExt};
+    pub use std::os::unix::fs::{DirBuilderExt, DirEntryExt, OpenOptionsExt, PermissionsExt};
 }
 
 cfg_docs! {
@@ -68,4 +204,18 @@ cfg_docs! {
         /// This options overwrites any previously set custom flags.
         fn custom_flags(&mut self, flags: i32) -> &mut Self;
     }
+
+    /// Unix-specific extensions to `Permissions`.
+    pub trait PermissionsExt {
+        /// Returns the underlying raw `st_mode` bits that contain the standard
+        /// Unix permissions for this file.
+        fn mode(&self) -> u32;
+
+        /// Sets the underlying raw bits for this set of permissions.
+        fn set_mode(&mut self, mode: u32);
+
+        /// Creates a new instance of `Permissions` from the given set of Unix
+        /// permission bits.
+        fn from_mode(mode: u32) -> Self;
+    }
 }
@@ -3,3 +3,7 @@
 pub mod fs;
 pub mod io;
 pub mod net;
+
+cfg_unstable! {
+    pub mod signal;
+}
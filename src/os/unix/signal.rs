@@ -0,0 +1,204 @@
+//! Unix-specific signal handling.
+//!
+//! # Examples
+//!
+//! Reload the configuration on every `SIGHUP`:
+//!
+//! ```no_run
+//! # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::os::unix::signal::{signal, SignalKind};
+//! use async_std::prelude::*;
+//!
+//! let mut hangups = signal(SignalKind::hangup())?;
+//! while let Some(()) = hangups.next().await {
+//!     println!("reloading the configuration");
+//! }
+//! #
+//! # Ok(()) }) }
+//! ```
+
+use std::os::raw::c_int;
+use std::pin::Pin;
+
+use crate::io;
+use crate::signal::unix as driver;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A kind of Unix signal.
+///
+/// # Examples
+///
+/// ```
+/// use async_std::os::unix::signal::SignalKind;
+///
+/// let kind = SignalKind::terminate();
+/// assert_eq!(kind, SignalKind::from_raw(kind.as_raw()));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SignalKind(c_int);
+
+impl SignalKind {
+    /// Creates a signal kind from a raw signal number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::os::unix::signal::SignalKind;
+    ///
+    /// let kind = SignalKind::from_raw(10);
+    /// ```
+    pub fn from_raw(signum: c_int) -> SignalKind {
+        SignalKind(signum)
+    }
+
+    /// Returns the raw signal number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::os::unix::signal::SignalKind;
+    ///
+    /// assert_eq!(SignalKind::from_raw(10).as_raw(), 10);
+    /// ```
+    pub fn as_raw(self) -> c_int {
+        self.0
+    }
+
+    /// `SIGALRM`, sent when a timer set with `alarm(2)` expires.
+    pub fn alarm() -> SignalKind {
+        SignalKind(libc::SIGALRM)
+    }
+
+    /// `SIGCHLD`, sent when a child process exits.
+    pub fn child() -> SignalKind {
+        SignalKind(libc::SIGCHLD)
+    }
+
+    /// `SIGHUP`, sent when the terminal is closed and often used to reload the configuration.
+    pub fn hangup() -> SignalKind {
+        SignalKind(libc::SIGHUP)
+    }
+
+    /// `SIGINT`, sent when the user presses `ctrl-c`.
+    pub fn interrupt() -> SignalKind {
+        SignalKind(libc::SIGINT)
+    }
+
+    /// `SIGPIPE`, sent when writing into a pipe that has no reader.
+    pub fn pipe() -> SignalKind {
+        SignalKind(libc::SIGPIPE)
+    }
+
+    /// `SIGQUIT`, sent when the user presses `ctrl-\`.
+    pub fn quit() -> SignalKind {
+        SignalKind(libc::SIGQUIT)
+    }
+
+    /// `SIGTERM`, sent to ask the process to exit.
+    pub fn terminate() -> SignalKind {
+        SignalKind(libc::SIGTERM)
+    }
+
+    /// `SIGUSR1`, a signal with an application-defined meaning.
+    pub fn user_defined1() -> SignalKind {
+        SignalKind(libc::SIGUSR1)
+    }
+
+    /// `SIGUSR2`, a signal with an application-defined meaning.
+    pub fn user_defined2() -> SignalKind {
+        SignalKind(libc::SIGUSR2)
+    }
+
+    /// `SIGWINCH`, sent when the terminal window is resized.
+    pub fn window_change() -> SignalKind {
+        SignalKind(libc::SIGWINCH)
+    }
+}
+
+/// Creates a stream that yields an item every time a signal is delivered.
+///
+/// The first call for a kind of signal installs a handler for it that stays in place for the rest
+/// of the program, which means the default action of the signal, like terminating the process, no
+/// longer happens. Signals delivered before the stream is created are not yielded.
+///
+/// # Errors
+///
+/// An error is returned if the signal can't be handled. This is the case for `SIGKILL` and
+/// `SIGSTOP`, which can't be caught at all, and for `SIGILL`, `SIGFPE` and `SIGSEGV`, which
+/// indicate a bug that must not be ignored.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::os::unix::signal::{signal, SignalKind};
+/// use async_std::prelude::*;
+///
+/// let mut terminate = signal(SignalKind::terminate())?;
+/// terminate.next().await;
+/// println!("shutting down");
+/// #
+/// # Ok(()) }) }
+/// ```
+pub fn signal(kind: SignalKind) -> io::Result<Signal> {
+    driver::register(kind.0)?;
+    Ok(Signal {
+        kind,
+        seen: driver::count(kind.0),
+    })
+}
+
+/// A stream of deliveries of a Unix signal.
+///
+/// This stream is created by the [`signal`] function. See its documentation for more.
+///
+/// If the signal is delivered several times before the stream is polled, only one item is
+/// yielded. The stream never ends.
+///
+/// [`signal`]: fn.signal.html
+#[derive(Debug)]
+pub struct Signal {
+    kind: SignalKind,
+
+    /// The number of deliveries of the signal that have been yielded already.
+    seen: usize,
+}
+
+impl Signal {
+    /// Returns the kind of signal this stream yields.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// #
+    /// use async_std::os::unix::signal::{signal, SignalKind};
+    ///
+    /// let s = signal(SignalKind::hangup())?;
+    /// assert_eq!(s.kind(), SignalKind::hangup());
+    /// #
+    /// # Ok(()) }
+    /// ```
+    pub fn kind(&self) -> SignalKind {
+        self.kind
+    }
+}
+
+impl Stream for Signal {
+    type Item = ();
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<()>> {
+        // The driver only fails if reading from its own socket fails, which never happens.
+        match driver::poll_changed(self.kind.0, self.seen, cx) {
+            Poll::Ready(Ok(n)) => {
+                self.seen = n;
+                Poll::Ready(Some(()))
+            }
+            Poll::Ready(Err(err)) => panic!("signal driver failed: {}", err),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -1,3 +1,4 @@
 //! Platform-specific extensions for Windows.
 
+pub mod fs;
 pub mod io;
@@ -6,7 +6,8 @@ use crate::{fs, io};
 /// This struct is an async version of [`std::path::Path`].
 ///
 /// [`std::path::Path`]: https://doc.rust-lang.org/std/path/struct.Path.html
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
 pub struct Path {
     inner: std::path::Path,
 }
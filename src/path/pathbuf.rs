@@ -5,7 +5,7 @@ use crate::path::Path;
 /// This struct is an async version of [`std::path::PathBuf`].
 ///
 /// [`std::path::Path`]: https://doc.rust-lang.org/std/path/struct.PathBuf.html
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct PathBuf {
     inner: std::path::PathBuf,
 }
@@ -222,12 +222,30 @@ impl From<&str> for PathBuf {
     }
 }
 
+impl From<String> for PathBuf {
+    fn from(path: String) -> PathBuf {
+        std::path::PathBuf::from(path).into()
+    }
+}
+
+impl From<&Path> for PathBuf {
+    fn from(path: &Path) -> PathBuf {
+        path.to_path_buf()
+    }
+}
+
 impl AsRef<Path> for PathBuf {
     fn as_ref(&self) -> &Path {
         Path::new(&self.inner)
     }
 }
 
+impl AsRef<OsStr> for PathBuf {
+    fn as_ref(&self) -> &OsStr {
+        self.inner.as_ref()
+    }
+}
+
 impl AsRef<std::path::Path> for PathBuf {
     fn as_ref(&self) -> &std::path::Path {
         self.inner.as_ref()
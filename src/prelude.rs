@@ -1,6 +1,8 @@
 //! The async prelude.
 //!
-//! The prelude re-exports most commonly used traits and macros from this crate.
+//! The prelude re-exports most commonly used traits and macros from this crate, so that methods
+//! like `.next()` on streams, `.read_to_string()` on readers, and `.timeout()` on futures and
+//! streams are available with a single import.
 //!
 //! # Examples
 //!
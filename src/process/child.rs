@@ -0,0 +1,416 @@
+use std::fmt;
+use std::mem::ManuallyDrop;
+use std::pin::Pin;
+use std::time::Duration;
+
+use crate::future;
+use crate::io::{self, Read, Write};
+use crate::prelude::*;
+use crate::process::{ExitStatus, Output};
+use crate::task::{blocking, Context, Poll};
+
+#[cfg(unix)]
+use super::unix as sys;
+#[cfg(windows)]
+use super::windows as sys;
+
+/// A handle to a child process.
+///
+/// This type is an async version of [`std::process::Child`]. Waiting for the process to exit
+/// doesn't block a thread: on Unix, exits are detected through `SIGCHLD`, and on Windows, the
+/// process handle is waited on by the system thread pool.
+///
+/// [`std::process::Child`]: https://doc.rust-lang.org/std/process/struct.Child.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::prelude::*;
+/// use async_std::process::{Command, Stdio};
+///
+/// let mut child = Command::new("cat")
+///     .stdin(Stdio::piped())
+///     .stdout(Stdio::piped())
+///     .spawn()?;
+///
+/// let mut stdin = child.stdin.take().unwrap();
+/// stdin.write_all(b"hello").await?;
+/// drop(stdin);
+///
+/// let mut output = String::new();
+/// child.stdout.take().unwrap().read_to_string(&mut output).await?;
+/// assert_eq!(output, "hello");
+///
+/// assert!(child.status().await?.success());
+/// #
+/// # Ok(()) }) }
+/// ```
+pub struct Child {
+    /// The handle for writing to the child's standard input, if it has been captured.
+    pub stdin: Option<ChildStdin>,
+
+    /// The handle for reading from the child's standard output, if it has been captured.
+    pub stdout: Option<ChildStdout>,
+
+    /// The handle for reading from the child's standard error, if it has been captured.
+    pub stderr: Option<ChildStderr>,
+
+    /// Taken out in `drop` so that a killed process can be reaped in the background.
+    inner: ManuallyDrop<std::process::Child>,
+    waiter: sys::Waiter,
+    kill_on_drop: bool,
+}
+
+impl Child {
+    /// Wraps a spawned child process and its pipes.
+    pub(crate) fn new(mut inner: std::process::Child) -> io::Result<Child> {
+        let stdin = match inner.stdin.take() {
+            Some(io) => Some(ChildStdin(sys::Pipe::new(io)?)),
+            None => None,
+        };
+        let stdout = match inner.stdout.take() {
+            Some(io) => Some(ChildStdout(sys::Pipe::new(io)?)),
+            None => None,
+        };
+        let stderr = match inner.stderr.take() {
+            Some(io) => Some(ChildStderr(sys::Pipe::new(io)?)),
+            None => None,
+        };
+
+        Ok(Child {
+            stdin,
+            stdout,
+            stderr,
+            inner: ManuallyDrop::new(inner),
+            waiter: sys::Waiter::default(),
+            kill_on_drop: false,
+        })
+    }
+
+    /// Returns the OS-assigned process identifier of the child.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// #
+    /// use async_std::process::Command;
+    ///
+    /// let child = Command::new("ls").spawn()?;
+    /// println!("spawned process {}", child.id());
+    /// #
+    /// # Ok(()) }
+    /// ```
+    pub fn id(&self) -> u32 {
+        self.inner.id()
+    }
+
+    /// Sets whether the child is killed when this handle is dropped.
+    ///
+    /// By default, dropping a `Child` lets the process keep running. With this option set, a
+    /// process that is still running when the handle is dropped is killed and reaped, so it
+    /// can't be left behind as an orphan.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// #
+    /// use async_std::process::Command;
+    ///
+    /// let mut child = Command::new("sleep").arg("100").spawn()?;
+    /// child.kill_on_drop(true);
+    ///
+    /// // The process is killed here.
+    /// drop(child);
+    /// #
+    /// # Ok(()) }
+    /// ```
+    pub fn kill_on_drop(&mut self, kill: bool) {
+        self.kill_on_drop = kill;
+    }
+
+    /// Forces the child to exit.
+    ///
+    /// On Unix, this sends `SIGKILL`. On Windows, the process is terminated with
+    /// `TerminateProcess`. Use [`status`] afterwards to reap the process.
+    ///
+    /// An error is returned if the child has already exited and been reaped.
+    ///
+    /// [`status`]: #method.status
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::process::Command;
+    ///
+    /// let mut child = Command::new("sleep").arg("100").spawn()?;
+    /// child.kill()?;
+    /// assert!(!child.status().await?.success());
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub fn kill(&mut self) -> io::Result<()> {
+        self.inner.kill()
+    }
+
+    /// Asks the child to exit, and kills it if it's still running after a grace period.
+    ///
+    /// On Unix, the child is sent `SIGTERM`, and then `SIGKILL` if it doesn't exit within
+    /// `grace`. Windows has no equivalent of `SIGTERM`, so the child is killed right away.
+    ///
+    /// Returns the exit status of the child. If it has already exited, its exit status is
+    /// returned and no signal is sent.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::process::Command;
+    ///
+    /// let mut child = Command::new("sleep").arg("100").spawn()?;
+    /// let status = child.terminate_then_kill(Duration::from_secs(5)).await?;
+    /// assert!(!status.success());
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn terminate_then_kill(&mut self, grace: Duration) -> io::Result<ExitStatus> {
+        // Signals must not be sent to a reaped child because its pid may have been reused.
+        if let Some(status) = self.try_status()? {
+            return Ok(status);
+        }
+        sys::terminate(&mut self.inner)?;
+
+        match future::timeout(grace, self.status()).await {
+            Ok(res) => res,
+            Err(_) => {
+                if let Some(status) = self.try_status()? {
+                    return Ok(status);
+                }
+                self.kill()?;
+                self.status().await
+            }
+        }
+    }
+
+    /// Returns the exit status if the child has already exited.
+    ///
+    /// This method never waits.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// #
+    /// use async_std::process::Command;
+    ///
+    /// let mut child = Command::new("sleep").arg("1").spawn()?;
+    /// assert!(child.try_status()?.is_none());
+    /// #
+    /// # Ok(()) }
+    /// ```
+    pub fn try_status(&mut self) -> io::Result<Option<ExitStatus>> {
+        self.inner.try_wait()
+    }
+
+    /// Waits for the child to exit and returns its exit status.
+    ///
+    /// The child's standard input is not closed before waiting, so a child that reads from it
+    /// may wait forever. Drop or take [`stdin`] first to avoid that.
+    ///
+    /// [`stdin`]: #structfield.stdin
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::process::Command;
+    ///
+    /// let mut child = Command::new("ls").spawn()?;
+    /// let status = child.status().await?;
+    /// assert!(status.success());
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn status(&mut self) -> io::Result<ExitStatus> {
+        let Child { inner, waiter, .. } = self;
+        future::poll_fn(|cx| waiter.poll_status(inner, cx)).await
+    }
+
+    /// Waits for the child to exit and collects its output.
+    ///
+    /// The standard input is closed first. The standard output and error are read concurrently
+    /// while waiting, but only if they were captured with [`Stdio::piped`].
+    ///
+    /// [`Stdio::piped`]: struct.Stdio.html#method.piped
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::process::{Command, Stdio};
+    ///
+    /// let child = Command::new("echo")
+    ///     .arg("hello")
+    ///     .stdout(Stdio::piped())
+    ///     .spawn()?;
+    ///
+    /// let output = child.output().await?;
+    /// assert_eq!(output.stdout, b"hello\n");
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn output(mut self) -> io::Result<Output> {
+        drop(self.stdin.take());
+
+        let stdout = read_to_end(self.stdout.take());
+        let stderr = read_to_end(self.stderr.take());
+        let status = self.status();
+
+        let (stdout, stderr, status) = future::try_join!(stdout, stderr, status).await?;
+        Ok(Output {
+            status,
+            stdout,
+            stderr,
+        })
+    }
+}
+
+/// Reads everything from a pipe, if there is one.
+async fn read_to_end<T: Read + Unpin>(io: Option<T>) -> io::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    if let Some(mut io) = io {
+        io.read_to_end(&mut buf).await?;
+    }
+    Ok(buf)
+}
+
+impl fmt::Debug for Child {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Child")
+            .field("id", &self.id())
+            .field("stdin", &self.stdin)
+            .field("stdout", &self.stdout)
+            .field("stderr", &self.stderr)
+            .field("kill_on_drop", &self.kill_on_drop)
+            .finish()
+    }
+}
+
+impl Drop for Child {
+    fn drop(&mut self) {
+        let mut inner = unsafe { ManuallyDrop::take(&mut self.inner) };
+        if self.kill_on_drop {
+            if let Ok(None) = inner.try_wait() {
+                // Reap the killed process on the blocking pool rather than leaving a zombie
+                // behind or blocking the current thread until it exits.
+                if inner.kill().is_ok() {
+                    blocking::spawn(move || {
+                        let _ = inner.wait();
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// A handle to a child process's standard input.
+///
+/// Dropping this handle closes the pipe, which signals the end of input to the child.
+///
+/// This type is an async version of [`std::process::ChildStdin`].
+///
+/// [`std::process::ChildStdin`]: https://doc.rust-lang.org/std/process/struct.ChildStdin.html
+#[derive(Debug)]
+pub struct ChildStdin(sys::Pipe<std::process::ChildStdin>);
+
+/// A handle to a child process's standard output.
+///
+/// This type is an async version of [`std::process::ChildStdout`].
+///
+/// [`std::process::ChildStdout`]: https://doc.rust-lang.org/std/process/struct.ChildStdout.html
+#[derive(Debug)]
+pub struct ChildStdout(sys::Pipe<std::process::ChildStdout>);
+
+/// A handle to a child process's standard error.
+///
+/// This type is an async version of [`std::process::ChildStderr`].
+///
+/// [`std::process::ChildStderr`]: https://doc.rust-lang.org/std/process/struct.ChildStderr.html
+#[derive(Debug)]
+pub struct ChildStderr(sys::Pipe<std::process::ChildStderr>);
+
+impl Write for ChildStdin {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.0.poll_write(cx, buf)
+    }
+
+    #[cfg(unix)]
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    #[cfg(windows)]
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.0.poll_flush(cx)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl Read for ChildStdout {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.0.poll_read(cx, buf)
+    }
+}
+
+impl Read for ChildStderr {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.0.poll_read(cx, buf)
+    }
+}
+
+cfg_unix! {
+    use crate::os::unix::io::{AsRawFd, RawFd};
+
+    impl AsRawFd for ChildStdin {
+        fn as_raw_fd(&self) -> RawFd {
+            self.0.as_raw_fd()
+        }
+    }
+
+    impl AsRawFd for ChildStdout {
+        fn as_raw_fd(&self) -> RawFd {
+            self.0.as_raw_fd()
+        }
+    }
+
+    impl AsRawFd for ChildStderr {
+        fn as_raw_fd(&self) -> RawFd {
+            self.0.as_raw_fd()
+        }
+    }
+}
@@ -0,0 +1,345 @@
+use std::ffi::OsStr;
+
+use crate::io;
+use crate::path::Path;
+use crate::process::{Child, ExitStatus, Output, Stdio};
+
+/// A builder for spawning processes.
+///
+/// This type is an async version of [`std::process::Command`].
+///
+/// [`std::process::Command`]: https://doc.rust-lang.org/std/process/struct.Command.html
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::process::Command;
+///
+/// let output = Command::new("echo").arg("hello").output().await?;
+/// assert_eq!(output.stdout, b"hello\n");
+/// #
+/// # Ok(()) }) }
+/// ```
+#[derive(Debug)]
+pub struct Command {
+    inner: std::process::Command,
+
+    /// Whether stdin, stdout and stderr were configured explicitly.
+    stdin_set: bool,
+    stdout_set: bool,
+    stderr_set: bool,
+}
+
+impl Command {
+    /// Constructs a new `Command` for launching the program at path `program`.
+    ///
+    /// By default, the spawned process inherits the current process's environment, working
+    /// directory and standard I/O streams.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_std::process::Command;
+    ///
+    /// let command = Command::new("ls");
+    /// ```
+    pub fn new<S: AsRef<OsStr>>(program: S) -> Command {
+        Command {
+            inner: std::process::Command::new(program),
+            stdin_set: false,
+            stdout_set: false,
+            stderr_set: false,
+        }
+    }
+
+    /// Adds an argument to pass to the program.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_std::process::Command;
+    ///
+    /// let mut command = Command::new("ls");
+    /// command.arg("-l").arg("-a");
+    /// ```
+    pub fn arg<S: AsRef<OsStr>>(&mut self, arg: S) -> &mut Command {
+        self.inner.arg(arg);
+        self
+    }
+
+    /// Adds multiple arguments to pass to the program.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_std::process::Command;
+    ///
+    /// let mut command = Command::new("ls");
+    /// command.args(&["-l", "-a"]);
+    /// ```
+    pub fn args<I, S>(&mut self, args: I) -> &mut Command
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        self.inner.args(args);
+        self
+    }
+
+    /// Inserts or updates an environment variable.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_std::process::Command;
+    ///
+    /// let mut command = Command::new("ls");
+    /// command.env("PATH", "/bin");
+    /// ```
+    pub fn env<K, V>(&mut self, key: K, val: V) -> &mut Command
+    where
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        self.inner.env(key, val);
+        self
+    }
+
+    /// Inserts or updates multiple environment variables.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::collections::HashMap;
+    ///
+    /// use async_std::process::Command;
+    ///
+    /// let mut vars = HashMap::new();
+    /// vars.insert("PATH", "/bin");
+    ///
+    /// let mut command = Command::new("ls");
+    /// command.envs(&vars);
+    /// ```
+    pub fn envs<I, K, V>(&mut self, vars: I) -> &mut Command
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<OsStr>,
+        V: AsRef<OsStr>,
+    {
+        self.inner.envs(vars);
+        self
+    }
+
+    /// Removes an environment variable.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_std::process::Command;
+    ///
+    /// let mut command = Command::new("ls");
+    /// command.env_remove("PATH");
+    /// ```
+    pub fn env_remove<K: AsRef<OsStr>>(&mut self, key: K) -> &mut Command {
+        self.inner.env_remove(key);
+        self
+    }
+
+    /// Removes all environment variables.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_std::process::Command;
+    ///
+    /// let mut command = Command::new("ls");
+    /// command.env_clear();
+    /// ```
+    pub fn env_clear(&mut self) -> &mut Command {
+        self.inner.env_clear();
+        self
+    }
+
+    /// Sets the working directory of the process.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_std::process::Command;
+    ///
+    /// let mut command = Command::new("ls");
+    /// command.current_dir("/");
+    /// ```
+    pub fn current_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Command {
+        self.inner.current_dir(dir.as_ref());
+        self
+    }
+
+    /// Sets the configuration for the standard input of the process.
+    ///
+    /// Use [`Stdio::piped`] to write into the process through [`Child::stdin`].
+    ///
+    /// [`Stdio::piped`]: struct.Stdio.html#method.piped
+    /// [`Child::stdin`]: struct.Child.html#structfield.stdin
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_std::process::{Command, Stdio};
+    ///
+    /// let mut command = Command::new("cat");
+    /// command.stdin(Stdio::null());
+    /// ```
+    pub fn stdin<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Command {
+        self.inner.stdin(cfg);
+        self.stdin_set = true;
+        self
+    }
+
+    /// Sets the configuration for the standard output of the process.
+    ///
+    /// Use [`Stdio::piped`] to read from the process through [`Child::stdout`].
+    ///
+    /// [`Stdio::piped`]: struct.Stdio.html#method.piped
+    /// [`Child::stdout`]: struct.Child.html#structfield.stdout
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_std::process::{Command, Stdio};
+    ///
+    /// let mut command = Command::new("ls");
+    /// command.stdout(Stdio::piped());
+    /// ```
+    pub fn stdout<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Command {
+        self.inner.stdout(cfg);
+        self.stdout_set = true;
+        self
+    }
+
+    /// Sets the configuration for the standard error of the process.
+    ///
+    /// Use [`Stdio::piped`] to read from the process through [`Child::stderr`].
+    ///
+    /// [`Stdio::piped`]: struct.Stdio.html#method.piped
+    /// [`Child::stderr`]: struct.Child.html#structfield.stderr
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use async_std::process::{Command, Stdio};
+    ///
+    /// let mut command = Command::new("ls");
+    /// command.stderr(Stdio::null());
+    /// ```
+    pub fn stderr<T: Into<Stdio>>(&mut self, cfg: T) -> &mut Command {
+        self.inner.stderr(cfg);
+        self.stderr_set = true;
+        self
+    }
+
+    /// Spawns the process and returns a handle to it.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::process::Command;
+    ///
+    /// let mut child = Command::new("ls").spawn()?;
+    /// child.status().await?;
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub fn spawn(&mut self) -> io::Result<Child> {
+        Child::new(self.inner.spawn()?)
+    }
+
+    /// Spawns the process and waits for it to exit.
+    ///
+    /// The process inherits the standard I/O streams unless they were configured otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::process::Command;
+    ///
+    /// let status = Command::new("ls").status().await?;
+    /// assert!(status.success());
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn status(&mut self) -> io::Result<ExitStatus> {
+        let mut child = self.spawn()?;
+
+        // Close stdin so that the process doesn't wait for input that never comes.
+        child.stdin.take();
+        child.status().await
+    }
+
+    /// Spawns the process, waits for it to exit and collects its output.
+    ///
+    /// Unless configured otherwise, the standard output and error are captured and the standard
+    /// input is connected to `/dev/null`.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+    /// #
+    /// use async_std::process::Command;
+    ///
+    /// let output = Command::new("ls").output().await?;
+    /// println!("{}", String::from_utf8_lossy(&output.stdout));
+    /// #
+    /// # Ok(()) }) }
+    /// ```
+    pub async fn output(&mut self) -> io::Result<Output> {
+        if !self.stdin_set {
+            self.inner.stdin(Stdio::null());
+        }
+        if !self.stdout_set {
+            self.inner.stdout(Stdio::piped());
+        }
+        if !self.stderr_set {
+            self.inner.stderr(Stdio::piped());
+        }
+
+        let child = self.inner.spawn();
+
+        // Go back to the defaults so that only this call is affected.
+        if !self.stdin_set {
+            self.inner.stdin(Stdio::inherit());
+        }
+        if !self.stdout_set {
+            self.inner.stdout(Stdio::inherit());
+        }
+        if !self.stderr_set {
+            self.inner.stderr(Stdio::inherit());
+        }
+
+        Child::new(child?)?.output().await
+    }
+}
+
+impl From<std::process::Command> for Command {
+    /// Converts a `std::process::Command` into its asynchronous equivalent.
+    ///
+    /// The standard I/O streams are treated as not configured, which only matters for
+    /// [`output`].
+    ///
+    /// [`output`]: #method.output
+    fn from(inner: std::process::Command) -> Command {
+        Command {
+            inner,
+            stdin_set: false,
+            stdout_set: false,
+            stderr_set: false,
+        }
+    }
+}
@@ -6,9 +6,33 @@
 //! This is an async version of [`std::process`].
 //!
 //! [`std::process`]: https://doc.rust-lang.org/std/process/index.html
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::process::Command;
+//!
+//! let output = Command::new("git").arg("status").output().await?;
+//! println!("{}", String::from_utf8_lossy(&output.stdout));
+//! #
+//! # Ok(()) }) }
+//! ```
 
 // Re-export structs.
-pub use std::process::{ExitStatus, Output};
+pub use std::process::{ExitStatus, Output, Stdio};
 
 // Re-export functions.
 pub use std::process::{abort, exit, id};
+
+pub use child::{Child, ChildStderr, ChildStdin, ChildStdout};
+pub use command::Command;
+
+mod child;
+mod command;
+
+#[cfg(unix)]
+mod unix;
+#[cfg(windows)]
+mod windows;
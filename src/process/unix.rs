@@ -0,0 +1,136 @@
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::process::ExitStatus;
+
+use mio::unix::EventedFd;
+use mio::{Evented, PollOpt, Ready, Token};
+
+use crate::io;
+use crate::net::driver::Watcher;
+use crate::signal::unix as signal;
+use crate::task::{Context, Poll};
+
+/// A pipe to a child process that is driven by the reactor.
+#[derive(Debug)]
+pub struct Pipe<T: AsRawFd> {
+    watcher: Watcher<Fd<T>>,
+}
+
+impl<T: AsRawFd> Pipe<T> {
+    /// Puts the pipe into non-blocking mode and registers it in the reactor.
+    pub fn new(io: T) -> io::Result<Pipe<T>> {
+        set_nonblocking(io.as_raw_fd())?;
+        Ok(Pipe {
+            watcher: Watcher::new(Fd(io)),
+        })
+    }
+
+    pub fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        self.watcher
+            .poll_read_with(cx, |fd| read(fd.0.as_raw_fd(), buf))
+    }
+
+    pub fn poll_write(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        self.watcher
+            .poll_write_with(cx, |fd| write(fd.0.as_raw_fd(), buf))
+    }
+
+    pub fn as_raw_fd(&self) -> RawFd {
+        self.watcher.get_ref().0.as_raw_fd()
+    }
+}
+
+/// A file descriptor that can be registered in the reactor.
+#[derive(Debug)]
+struct Fd<T>(T);
+
+impl<T: AsRawFd> Evented for Fd<T> {
+    fn register(
+        &self,
+        poll: &mio::Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.0.as_raw_fd()).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &mio::Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.0.as_raw_fd()).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &mio::Poll) -> io::Result<()> {
+        EventedFd(&self.0.as_raw_fd()).deregister(poll)
+    }
+}
+
+/// Waits for a child process to exit.
+///
+/// Exits are detected through `SIGCHLD`, so no thread is blocked while waiting.
+#[derive(Debug, Default)]
+pub struct Waiter;
+
+impl Waiter {
+    /// Polls for the exit status of a child process.
+    pub fn poll_status(
+        &mut self,
+        child: &mut std::process::Child,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<ExitStatus>> {
+        signal::register(libc::SIGCHLD)?;
+
+        loop {
+            // Any `SIGCHLD` delivered after this point changes the count, so it can't be missed.
+            let count = signal::count(libc::SIGCHLD);
+
+            if let Some(status) = child.try_wait()? {
+                return Poll::Ready(Ok(status));
+            }
+
+            futures_core::ready!(signal::poll_changed(libc::SIGCHLD, count, cx))?;
+        }
+    }
+}
+
+/// Sends `SIGTERM` to a child process that hasn't been reaped yet.
+pub fn terminate(child: &mut std::process::Child) -> io::Result<()> {
+    cvt(unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGTERM) })?;
+    Ok(())
+}
+
+fn set_nonblocking(fd: RawFd) -> io::Result<()> {
+    let flags = cvt(unsafe { libc::fcntl(fd, libc::F_GETFL) })?;
+    cvt(unsafe { libc::fcntl(fd, libc::F_SETFL, flags | libc::O_NONBLOCK) })?;
+    Ok(())
+}
+
+fn read(fd: RawFd, buf: &mut [u8]) -> io::Result<usize> {
+    let n = unsafe { libc::read(fd, buf.as_mut_ptr() as *mut _, buf.len()) };
+    if n < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(n as usize)
+    }
+}
+
+fn write(fd: RawFd, buf: &[u8]) -> io::Result<usize> {
+    let n = unsafe { libc::write(fd, buf.as_ptr() as *const _, buf.len()) };
+    if n < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(n as usize)
+    }
+}
+
+fn cvt(ret: libc::c_int) -> io::Result<libc::c_int> {
+    if ret == -1 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(ret)
+    }
+}
@@ -0,0 +1,278 @@
+use std::os::windows::io::AsRawHandle;
+use std::pin::Pin;
+use std::process::ExitStatus;
+use std::ptr;
+use std::sync::Mutex;
+
+use winapi::shared::ntdef::{BOOLEAN, HANDLE, PVOID};
+use winapi::um::handleapi::INVALID_HANDLE_VALUE;
+use winapi::um::threadpoollegacyapiset::UnregisterWaitEx;
+use winapi::um::winbase::{RegisterWaitForSingleObject, INFINITE};
+use winapi::um::winnt::WT_EXECUTEONLYONCE;
+
+use crate::future::Future;
+use crate::io;
+use crate::task::{blocking, Context, JoinHandle, Poll, Waker};
+
+/// A pipe to a child process whose operations run on the blocking thread pool.
+#[derive(Debug)]
+pub struct Pipe<T> {
+    state: State<T>,
+}
+
+/// The state of a pipe.
+///
+/// The pipe can be either idle or busy performing an asynchronous operation.
+#[derive(Debug)]
+enum State<T> {
+    /// The pipe is idle.
+    Idle(Option<Inner<T>>),
+
+    /// The pipe is blocked on an asynchronous operation.
+    ///
+    /// Awaiting this operation will result in the new state of the pipe.
+    Busy(JoinHandle<State<T>>),
+}
+
+/// Inner representation of a pipe.
+#[derive(Debug)]
+struct Inner<T> {
+    /// The blocking pipe handle.
+    io: T,
+
+    /// The read or write buffer.
+    buf: Vec<u8>,
+
+    /// The result of the last asynchronous operation on the pipe.
+    last_op: Option<Operation>,
+}
+
+/// Possible results of an asynchronous operation on a pipe.
+#[derive(Debug)]
+enum Operation {
+    Read(io::Result<usize>),
+    Write(io::Result<usize>),
+    Flush(io::Result<()>),
+}
+
+impl<T: Send + 'static> Pipe<T> {
+    pub fn new(io: T) -> io::Result<Pipe<T>> {
+        Ok(Pipe {
+            state: State::Idle(Some(Inner {
+                io,
+                buf: Vec::new(),
+                last_op: None,
+            })),
+        })
+    }
+}
+
+impl<T: std::io::Read + Send + 'static> Pipe<T> {
+    pub fn poll_read(&mut self, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+        let state = &mut self.state;
+
+        loop {
+            match state {
+                State::Idle(opt) => {
+                    let inner = opt.as_mut().unwrap();
+
+                    // Check if the operation has completed.
+                    if let Some(Operation::Read(res)) = inner.last_op.take() {
+                        let n = res?;
+
+                        // If more data was read than fits into the buffer, let's retry the read
+                        // operation.
+                        if n <= buf.len() {
+                            // Copy the read data into the buffer and return.
+                            buf[..n].copy_from_slice(&inner.buf[..n]);
+                            return Poll::Ready(Ok(n));
+                        }
+                    } else {
+                        let mut inner = opt.take().unwrap();
+
+                        // Set the length of the inner buffer to the length of the provided buffer.
+                        inner.buf.resize(buf.len(), 0);
+
+                        // Start the operation asynchronously.
+                        *state = State::Busy(blocking::spawn(move || {
+                            let res = std::io::Read::read(&mut inner.io, &mut inner.buf);
+                            inner.last_op = Some(Operation::Read(res));
+                            State::Idle(Some(inner))
+                        }));
+                    }
+                }
+                // Poll the asynchronous operation the pipe is currently blocked on.
+                State::Busy(task) => *state = futures_core::ready!(Pin::new(task).poll(cx)),
+            }
+        }
+    }
+}
+
+impl<T: std::io::Write + Send + 'static> Pipe<T> {
+    pub fn poll_write(&mut self, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let state = &mut self.state;
+
+        loop {
+            match state {
+                State::Idle(opt) => {
+                    let inner = opt.as_mut().unwrap();
+
+                    // Check if the operation has completed.
+                    if let Some(Operation::Write(res)) = inner.last_op.take() {
+                        let n = res?;
+
+                        // If more data was written than is available in the buffer, let's retry
+                        // the write operation.
+                        if n <= buf.len() {
+                            return Poll::Ready(Ok(n));
+                        }
+                    } else {
+                        let mut inner = opt.take().unwrap();
+
+                        // Copy the data to write into the inner buffer.
+                        inner.buf.clear();
+                        inner.buf.extend_from_slice(buf);
+
+                        // Start the operation asynchronously.
+                        *state = State::Busy(blocking::spawn(move || {
+                            let res = std::io::Write::write(&mut inner.io, &inner.buf);
+                            inner.last_op = Some(Operation::Write(res));
+                            State::Idle(Some(inner))
+                        }));
+                    }
+                }
+                // Poll the asynchronous operation the pipe is currently blocked on.
+                State::Busy(task) => *state = futures_core::ready!(Pin::new(task).poll(cx)),
+            }
+        }
+    }
+
+    pub fn poll_flush(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let state = &mut self.state;
+
+        loop {
+            match state {
+                State::Idle(opt) => {
+                    let inner = opt.as_mut().unwrap();
+
+                    // Check if the operation has completed.
+                    if let Some(Operation::Flush(res)) = inner.last_op.take() {
+                        return Poll::Ready(res);
+                    } else {
+                        let mut inner = opt.take().unwrap();
+
+                        // Start the operation asynchronously.
+                        *state = State::Busy(blocking::spawn(move || {
+                            let res = std::io::Write::flush(&mut inner.io);
+                            inner.last_op = Some(Operation::Flush(res));
+                            State::Idle(Some(inner))
+                        }));
+                    }
+                }
+                // Poll the asynchronous operation the pipe is currently blocked on.
+                State::Busy(task) => *state = futures_core::ready!(Pin::new(task).poll(cx)),
+            }
+        }
+    }
+}
+
+/// Waits for a child process to exit using a registered wait on its handle.
+#[derive(Debug, Default)]
+pub struct Waiter {
+    registration: Option<Registration>,
+}
+
+impl Waiter {
+    /// Polls for the exit status of a child process.
+    ///
+    /// The process handle is waited on by the system thread pool, so no thread of ours is
+    /// blocked while waiting.
+    pub fn poll_status(
+        &mut self,
+        child: &mut std::process::Child,
+        cx: &mut Context<'_>,
+    ) -> Poll<io::Result<ExitStatus>> {
+        if let Some(status) = child.try_wait()? {
+            self.registration = None;
+            return Poll::Ready(Ok(status));
+        }
+
+        if self.registration.is_none() {
+            self.registration = Some(Registration::new(child.as_raw_handle() as HANDLE)?);
+        }
+        let registration = self.registration.as_ref().unwrap();
+        *registration.waker().lock().unwrap() = Some(cx.waker().clone());
+
+        // Check again in case the process exited before the waker was stored.
+        if let Some(status) = child.try_wait()? {
+            self.registration = None;
+            return Poll::Ready(Ok(status));
+        }
+        Poll::Pending
+    }
+}
+
+/// Terminates a child process.
+///
+/// Windows has no way of asking a process to exit, so it is killed right away.
+pub fn terminate(child: &mut std::process::Child) -> io::Result<()> {
+    child.kill()
+}
+
+/// A wait registered with `RegisterWaitForSingleObject`.
+#[derive(Debug)]
+struct Registration {
+    /// The wait handle.
+    wait: HANDLE,
+
+    /// The waker to wake once the process exits, owned by this registration.
+    waker: *mut Mutex<Option<Waker>>,
+}
+
+unsafe impl Send for Registration {}
+unsafe impl Sync for Registration {}
+
+impl Registration {
+    fn new(process: HANDLE) -> io::Result<Registration> {
+        let waker = Box::into_raw(Box::new(Mutex::new(None)));
+        let mut wait = ptr::null_mut();
+
+        let ok = unsafe {
+            RegisterWaitForSingleObject(
+                &mut wait,
+                process,
+                Some(callback),
+                waker as PVOID,
+                INFINITE,
+                WT_EXECUTEONLYONCE,
+            )
+        };
+        if ok == 0 {
+            unsafe { drop(Box::from_raw(waker)) };
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(Registration { wait, waker })
+    }
+
+    fn waker(&self) -> &Mutex<Option<Waker>> {
+        unsafe { &*self.waker }
+    }
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        unsafe {
+            // Wait for a running callback to finish before freeing the waker.
+            UnregisterWaitEx(self.wait, INVALID_HANDLE_VALUE);
+            drop(Box::from_raw(self.waker));
+        }
+    }
+}
+
+unsafe extern "system" fn callback(ptr: PVOID, _timed_out: BOOLEAN) {
+    let waker = &*(ptr as *const Mutex<Option<Waker>>);
+    if let Some(w) = waker.lock().unwrap().take() {
+        w.wake();
+    }
+}
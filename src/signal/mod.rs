@@ -0,0 +1,66 @@
+//! Asynchronous signal handling.
+//!
+//! This module provides [`ctrl_c`], which works on all platforms. Unix signals in general can be
+//! received through [`os::unix::signal`].
+//!
+//! [`ctrl_c`]: fn.ctrl_c.html
+//! [`os::unix::signal`]: ../os/unix/signal/index.html
+//!
+//! # Examples
+//!
+//! Shut down gracefully on `ctrl-c`:
+//!
+//! ```no_run
+//! # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+//! #
+//! use async_std::signal;
+//!
+//! signal::ctrl_c().await?;
+//! println!("shutting down");
+//! #
+//! # Ok(()) }) }
+//! ```
+
+use crate::io;
+
+#[cfg(unix)]
+pub(crate) mod unix;
+#[cfg(windows)]
+mod windows;
+
+/// Waits for the next `ctrl-c`.
+///
+/// The first call installs a handler that stays in place for the rest of the program, so from
+/// then on, `ctrl-c` no longer terminates the process. A `ctrl-c` that arrives while no task is
+/// waiting is ignored.
+///
+/// On Unix, this waits for `SIGINT`. On Windows, it waits for `CTRL_C_EVENT` or
+/// `CTRL_BREAK_EVENT`.
+///
+/// # Examples
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> { async_std::task::block_on(async {
+/// #
+/// use async_std::signal;
+///
+/// signal::ctrl_c().await?;
+/// println!("received ctrl-c");
+/// #
+/// # Ok(()) }) }
+/// ```
+pub async fn ctrl_c() -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use crate::os::unix::signal::{signal, SignalKind};
+        use crate::prelude::*;
+
+        signal(SignalKind::interrupt())?.next().await;
+        Ok(())
+    }
+
+    #[cfg(windows)]
+    {
+        windows::ctrl_c().await
+    }
+}
@@ -0,0 +1,163 @@
+//! The signal driver.
+//!
+//! Signal handlers can't do much, so the handler installed here only counts deliveries of each
+//! signal and writes a byte into a socket that is registered in the reactor. Tasks waiting for a
+//! signal remember the count they have last seen and are woken up whenever the socket becomes
+//! readable, at which point they check whether the count has changed.
+
+use std::io::Read;
+use std::os::unix::io::AsRawFd;
+use std::ptr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use mio_uds::UnixStream;
+
+use crate::io;
+use crate::net::driver::Watcher;
+use crate::task::{Context, Poll};
+
+/// The number of signals that can be handled, including the unused signal number zero.
+const SIGNAL_COUNT: usize = 65;
+
+/// Global state of the signal driver.
+struct Driver {
+    /// The end of the socket pair that becomes readable when a signal is delivered.
+    receiver: Watcher<UnixStream>,
+
+    /// The end of the socket pair the signal handler writes into.
+    sender: UnixStream,
+
+    /// The number of times each signal has been delivered.
+    counts: Vec<AtomicUsize>,
+
+    /// Whether a handler has been installed for each signal.
+    installed: Mutex<Vec<bool>>,
+}
+
+lazy_static! {
+    static ref DRIVER: Driver = {
+        let (receiver, sender) = UnixStream::pair().expect("cannot create the signal driver");
+
+        Driver {
+            receiver: Watcher::new(receiver),
+            sender,
+            counts: (0..SIGNAL_COUNT).map(|_| AtomicUsize::new(0)).collect(),
+            installed: Mutex::new(vec![false; SIGNAL_COUNT]),
+        }
+    };
+}
+
+/// Installs the signal handler for `signum` if it hasn't been installed yet.
+///
+/// The handler stays installed for the rest of the program, which means the default action of
+/// the signal, like terminating the process, no longer happens.
+pub fn register(signum: libc::c_int) -> io::Result<()> {
+    let forbidden = [
+        libc::SIGILL,
+        libc::SIGFPE,
+        libc::SIGKILL,
+        libc::SIGSEGV,
+        libc::SIGSTOP,
+    ];
+    if signum <= 0 || signum as usize >= SIGNAL_COUNT || forbidden.contains(&signum) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("cannot handle signal {}", signum),
+        ));
+    }
+
+    let driver = &*DRIVER;
+    let mut installed = driver.installed.lock().unwrap();
+    if installed[signum as usize] {
+        return Ok(());
+    }
+
+    unsafe {
+        let mut action: libc::sigaction = std::mem::zeroed();
+        action.sa_sigaction = handler as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        action.sa_flags = libc::SA_RESTART;
+        if signum == libc::SIGCHLD {
+            action.sa_flags |= libc::SA_NOCLDSTOP;
+        }
+        libc::sigemptyset(&mut action.sa_mask);
+
+        if libc::sigaction(signum, &action, ptr::null_mut()) == -1 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    installed[signum as usize] = true;
+    Ok(())
+}
+
+/// Returns the number of times `signum` has been delivered since its handler was installed.
+pub fn count(signum: libc::c_int) -> usize {
+    DRIVER.counts[signum as usize].load(Ordering::SeqCst)
+}
+
+/// Polls until `signum` has been delivered a number of times other than `seen`.
+///
+/// Returns the new count.
+pub fn poll_changed(
+    signum: libc::c_int,
+    seen: usize,
+    cx: &mut Context<'_>,
+) -> Poll<io::Result<usize>> {
+    let driver = &*DRIVER;
+
+    driver.receiver.poll_read_with(cx, |mut receiver| {
+        // Drain the socket so that the next signal makes it readable again.
+        let mut buf = [0; 64];
+        loop {
+            match receiver.read(&mut buf) {
+                Ok(0) => break,
+                Ok(_) => {}
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        match driver.counts[signum as usize].load(Ordering::SeqCst) {
+            n if n != seen => Ok(n),
+            _ => Err(io::ErrorKind::WouldBlock.into()),
+        }
+    })
+}
+
+extern "C" fn handler(signum: libc::c_int) {
+    // The handler may interrupt code that is about to read `errno`, so leave it unchanged.
+    let errno = unsafe { *errno_location() };
+
+    // The driver has been initialized before any handler was installed.
+    let driver = &*DRIVER;
+    driver.counts[signum as usize].fetch_add(1, Ordering::SeqCst);
+
+    // Only async-signal-safe functions may be called here, so just wake up the reactor. If the
+    // socket is full, the reactor has already been woken up.
+    unsafe {
+        libc::write(driver.sender.as_raw_fd(), &1u8 as *const u8 as *const _, 1);
+        *errno_location() = errno;
+    }
+}
+
+/// Returns a pointer to `errno` of the current thread.
+unsafe fn errno_location() -> *mut libc::c_int {
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "redox",
+        target_os = "fuchsia",
+        target_os = "dragonfly"
+    ))]
+    return libc::__errno_location();
+
+    #[cfg(any(target_os = "android", target_os = "netbsd", target_os = "openbsd"))]
+    return libc::__errno();
+
+    #[cfg(any(target_os = "macos", target_os = "ios", target_os = "freebsd"))]
+    return libc::__error();
+
+    #[cfg(any(target_os = "solaris", target_os = "illumos"))]
+    return libc::___errno();
+}
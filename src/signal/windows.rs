@@ -0,0 +1,66 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+use winapi::shared::minwindef::{BOOL, DWORD, FALSE, TRUE};
+use winapi::um::consoleapi::SetConsoleCtrlHandler;
+use winapi::um::wincon::{CTRL_BREAK_EVENT, CTRL_C_EVENT};
+
+use crate::future;
+use crate::io;
+use crate::task::{Poll, Waker};
+
+/// The number of times `ctrl-c` has been pressed since the handler was installed.
+static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+lazy_static! {
+    /// Tasks waiting for `ctrl-c`, or `None` if the handler hasn't been installed yet.
+    static ref WAKERS: Mutex<Option<Vec<Waker>>> = Mutex::new(None);
+}
+
+/// Waits for the next `ctrl-c`.
+pub async fn ctrl_c() -> io::Result<()> {
+    {
+        let mut wakers = WAKERS.lock().unwrap();
+        if wakers.is_none() {
+            if unsafe { SetConsoleCtrlHandler(Some(handler), TRUE) } == 0 {
+                return Err(io::Error::last_os_error());
+            }
+            *wakers = Some(Vec::new());
+        }
+    }
+
+    let seen = COUNT.load(Ordering::SeqCst);
+    future::poll_fn(|cx| {
+        let mut wakers = WAKERS.lock().unwrap();
+
+        // The handler increments the count before taking the lock, so checking it while holding
+        // the lock can't miss a wakeup.
+        if COUNT.load(Ordering::SeqCst) != seen {
+            return Poll::Ready(Ok(()));
+        }
+
+        let wakers = wakers.as_mut().unwrap();
+        if wakers.iter().all(|w| !w.will_wake(cx.waker())) {
+            wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+/// Runs on a thread created by the system whenever a console control event occurs.
+unsafe extern "system" fn handler(event: DWORD) -> BOOL {
+    match event {
+        CTRL_C_EVENT | CTRL_BREAK_EVENT => {
+            COUNT.fetch_add(1, Ordering::SeqCst);
+            if let Some(wakers) = WAKERS.lock().unwrap().as_mut() {
+                for w in wakers.drain(..) {
+                    w.wake();
+                }
+            }
+            TRUE
+        }
+        _ => FALSE,
+    }
+}
@@ -57,6 +57,6 @@ cfg_unstable! {
     pub use interval::{interval, Interval};
     pub use into_stream::IntoStream;
     pub use product::Product;
-    pub use stream::Merge;
+    pub use stream::{Merge, Throttle};
     pub use sum::Sum;
 }
@@ -93,13 +93,17 @@ use std::marker::PhantomData;
 
 cfg_unstable! {
     use std::pin::Pin;
+    use std::time::Duration;
 
     use crate::future::Future;
     use crate::stream::FromStream;
+    use crate::time::Timeout;
 
     pub use merge::Merge;
+    pub use throttle::Throttle;
 
     mod merge;
+    mod throttle;
 }
 
 extension_trait! {
@@ -1315,6 +1319,81 @@ extension_trait! {
             Merge::new(self, other)
         }
 
+        #[doc = r#"
+            Limits the time the stream has to yield each item.
+
+            The returned stream yields `Err(TimeoutError)` whenever no item has arrived for `dur`.
+            The stream is not closed when that happens, and the timer starts again.
+
+            # Examples
+
+            ```
+            # async_std::task::block_on(async {
+            #
+            use std::time::Duration;
+
+            use async_std::prelude::*;
+            use async_std::stream;
+
+            let mut s = stream::repeat(1).timeout(Duration::from_millis(5));
+            assert_eq!(s.next().await, Some(Ok(1)));
+
+            let mut s = stream::empty::<()>().timeout(Duration::from_millis(5));
+            assert_eq!(s.next().await, None);
+            #
+            # })
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn timeout(self, dur: Duration) -> Timeout<Self>
+        where
+            Self: Sized,
+        {
+            Timeout::new(self, dur)
+        }
+
+        #[doc = r#"
+            Limits the stream to at most one item per `duration`.
+
+            After an item is yielded, the underlying stream isn't polled again until `duration` has
+            passed. No items are dropped: items that arrive in the meantime wait in the underlying
+            stream, which makes this useful for rate-limiting work such as accepting connections
+            from [`TcpListener::incoming`].
+
+            [`TcpListener::incoming`]: ../net/struct.TcpListener.html#method.incoming
+
+            # Examples
+
+            ```
+            # async_std::task::block_on(async {
+            #
+            use std::time::{Duration, Instant};
+
+            use async_std::prelude::*;
+            use async_std::stream;
+
+            let start = Instant::now();
+            let mut s = stream::repeat(1).take(3).throttle(Duration::from_millis(10));
+
+            assert_eq!(s.next().await, Some(1));
+            assert_eq!(s.next().await, Some(1));
+            assert_eq!(s.next().await, Some(1));
+            assert_eq!(s.next().await, None);
+            assert!(start.elapsed() >= Duration::from_millis(20));
+            #
+            # })
+            ```
+        "#]
+        #[cfg(feature = "unstable")]
+        #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+        fn throttle(self, duration: Duration) -> Throttle<Self>
+        where
+            Self: Sized,
+        {
+            Throttle::new(self, duration)
+        }
+
         #[doc = r#"
             Lexicographically compares the elements of this `Stream` with those
             of another.
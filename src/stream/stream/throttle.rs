@@ -0,0 +1,57 @@
+use std::pin::Pin;
+use std::time::Duration;
+
+use futures_timer::Delay;
+
+use crate::future::Future;
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A stream that yields at most one item per period of time.
+///
+/// This stream is returned by [`Stream::throttle`].
+///
+/// [`Stream::throttle`]: trait.Stream.html#method.throttle
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Debug)]
+pub struct Throttle<S> {
+    stream: S,
+    duration: Duration,
+
+    /// Fires once the next item may be yielded, or `None` if an item may be yielded right away.
+    delay: Option<Delay>,
+}
+
+impl<S: Unpin> Unpin for Throttle<S> {}
+
+impl<S: Stream> Throttle<S> {
+    pin_utils::unsafe_pinned!(stream: S);
+    pin_utils::unsafe_unpinned!(delay: Option<Delay>);
+
+    pub(super) fn new(stream: S, duration: Duration) -> Self {
+        Throttle {
+            stream,
+            duration,
+            delay: None,
+        }
+    }
+}
+
+impl<S: Stream> Stream for Throttle<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        if let Some(delay) = self.as_mut().delay().as_mut() {
+            futures_core::ready!(Pin::new(delay).poll(cx));
+            *self.as_mut().delay() = None;
+        }
+
+        let next = futures_core::ready!(self.as_mut().stream().poll_next(cx));
+        if next.is_some() {
+            let delay = Delay::new(self.duration);
+            *self.as_mut().delay() = Some(delay);
+        }
+        Poll::Ready(next)
+    }
+}
@@ -78,7 +78,7 @@ impl Barrier {
     /// # Examples
     ///
     /// ```
-    /// use std::sync::Barrier;
+    /// use async_std::sync::Barrier;
     ///
     /// let barrier = Barrier::new(10);
     /// ```
@@ -258,4 +258,36 @@ mod test {
             });
         }
     }
+
+    #[test]
+    fn test_barrier_reuse() {
+        task::block_on(async move {
+            const N: usize = 5;
+            const PHASES: usize = 3;
+
+            let barrier = Arc::new(Barrier::new(N));
+            let (tx, mut rx) = unbounded();
+
+            for _ in 0..N {
+                let c = barrier.clone();
+                let mut tx = tx.clone();
+                task::spawn(async move {
+                    for phase in 0..PHASES {
+                        let res = c.wait().await;
+                        tx.send((phase, res.is_leader())).await.unwrap();
+                    }
+                });
+            }
+            drop(tx);
+
+            // Every phase completes with exactly one leader.
+            let mut leaders = [0; PHASES];
+            while let Some((phase, is_leader)) = rx.next().await {
+                if is_leader {
+                    leaders[phase] += 1;
+                }
+            }
+            assert_eq!(leaders, [1; PHASES]);
+        });
+    }
 }
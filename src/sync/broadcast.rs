@@ -0,0 +1,574 @@
+//! A channel that delivers every message to every receiver.
+//!
+//! The channel keeps the last `cap` messages. Sending never waits: once the channel is full, the
+//! oldest message is dropped to make room for the new one. A receiver that falls so far behind
+//! that it misses messages is said to *lag*, and its [`LagPolicy`] decides whether it gets an
+//! error telling how many messages were missed or silently skips to the oldest message still
+//! available.
+//!
+//! This is useful for fanning out events or shutdown signals to many tasks.
+//!
+//! [`LagPolicy`]: enum.LagPolicy.html
+//!
+//! # Examples
+//!
+//! ```
+//! # async_std::task::block_on(async {
+//! #
+//! use async_std::sync::broadcast;
+//! use async_std::task;
+//!
+//! let (s, r1) = broadcast::channel(16);
+//! let r2 = s.subscribe();
+//!
+//! let t1 = task::spawn(async move { r1.recv().await });
+//! let t2 = task::spawn(async move { r2.recv().await });
+//!
+//! s.send("shutdown").unwrap();
+//!
+//! assert_eq!(t1.await, Ok("shutdown"));
+//! assert_eq!(t2.await, Ok("shutdown"));
+//! #
+//! # })
+//! ```
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use slab::Slab;
+
+use crate::task::{Poll, Waker};
+
+/// Creates a bounded broadcast channel.
+///
+/// The channel keeps up to `cap` messages that receivers haven't seen yet. Each receiver sees
+/// every message sent after it was created, as long as it keeps up.
+///
+/// # Panics
+///
+/// This function panics if `cap` is zero.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::broadcast;
+///
+/// let (s, r) = broadcast::channel(2);
+/// s.send(1).unwrap();
+/// s.send(2).unwrap();
+///
+/// assert_eq!(r.recv().await, Ok(1));
+/// assert_eq!(r.recv().await, Ok(2));
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn channel<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(cap > 0, "capacity must be positive");
+
+    let mut receivers = Slab::new();
+    let key = receivers.insert(None);
+
+    let channel = Arc::new(Channel {
+        cap,
+        state: Mutex::new(State {
+            buffer: VecDeque::with_capacity(cap),
+            head: 0,
+            sender_count: 1,
+            receivers,
+        }),
+    });
+
+    let s = Sender {
+        channel: channel.clone(),
+    };
+    let r = Receiver {
+        channel,
+        pos: Mutex::new(0),
+        lag_policy: LagPolicy::Error,
+        key,
+    };
+    (s, r)
+}
+
+/// What a [`Receiver`] does when it has missed messages.
+///
+/// [`Receiver`]: struct.Receiver.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum LagPolicy {
+    /// Return an error with the number of missed messages, then continue with the oldest message
+    /// still available.
+    ///
+    /// This is the default.
+    #[default]
+    Error,
+
+    /// Silently continue with the oldest message still available.
+    Skip,
+}
+
+/// The sending side of a broadcast channel.
+///
+/// This type is created by the [`channel`] function. See its documentation for more details.
+///
+/// [`channel`]: fn.channel.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Sender<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends a message to all receivers.
+    ///
+    /// This method never waits. If the channel is full, the oldest message is dropped. On success,
+    /// the number of receivers that will see the message is returned. If there are no receivers,
+    /// the message is returned back in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast;
+    ///
+    /// let (s, r) = broadcast::channel(1);
+    /// let _r2 = r.clone();
+    /// assert_eq!(s.send(1), Ok(2));
+    /// ```
+    pub fn send(&self, msg: T) -> Result<usize, SendError<T>> {
+        let mut state = self.channel.state.lock().unwrap();
+        if state.receivers.is_empty() {
+            return Err(SendError(msg));
+        }
+
+        if state.buffer.len() == self.channel.cap {
+            state.buffer.pop_front();
+            state.head += 1;
+        }
+        state.buffer.push_back(msg);
+
+        state.wake_receivers();
+        Ok(state.receivers.len())
+    }
+
+    /// Creates a new receiver that sees all messages sent from now on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::broadcast;
+    ///
+    /// let (s, r) = broadcast::channel(4);
+    /// drop(r);
+    ///
+    /// let r = s.subscribe();
+    /// s.send(1).unwrap();
+    /// assert_eq!(r.recv().await, Ok(1));
+    /// #
+    /// # })
+    /// ```
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut state = self.channel.state.lock().unwrap();
+        let key = state.receivers.insert(None);
+        Receiver {
+            channel: self.channel.clone(),
+            pos: Mutex::new(state.tail()),
+            lag_policy: LagPolicy::default(),
+            key,
+        }
+    }
+
+    /// Returns the number of receivers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast;
+    ///
+    /// let (s, r) = broadcast::channel::<i32>(1);
+    /// assert_eq!(s.receiver_count(), 1);
+    ///
+    /// drop(r);
+    /// assert_eq!(s.receiver_count(), 0);
+    /// ```
+    pub fn receiver_count(&self) -> usize {
+        self.channel.state.lock().unwrap().receivers.len()
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.channel.state.lock().unwrap().sender_count += 1;
+        Sender {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.channel.state.lock().unwrap();
+        state.sender_count -= 1;
+
+        // Wake up receivers so they can see the channel is closed.
+        if state.sender_count == 0 {
+            state.wake_receivers();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Sender { .. }")
+    }
+}
+
+/// The receiving side of a broadcast channel.
+///
+/// Cloning a receiver creates a new one at the same position, so both see the same messages from
+/// then on.
+///
+/// This type is created by the [`channel`] function or the [`Sender::subscribe`] method. See the
+/// [module documentation] for more details.
+///
+/// [`channel`]: fn.channel.html
+/// [`Sender::subscribe`]: struct.Sender.html#method.subscribe
+/// [module documentation]: index.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Receiver<T> {
+    channel: Arc<Channel<T>>,
+
+    /// The sequence number of the next message to receive.
+    pos: Mutex<u64>,
+
+    lag_policy: LagPolicy,
+
+    /// The key of this receiver's waker slot.
+    key: usize,
+}
+
+impl<T: Clone> Receiver<T> {
+    /// Receives the next message.
+    ///
+    /// If the receiver has missed messages and its [`LagPolicy`] is [`LagPolicy::Error`], an
+    /// error with the number of missed messages is returned first. Once all senders are dropped
+    /// and all remaining messages are received, an error is returned.
+    ///
+    /// [`LagPolicy`]: enum.LagPolicy.html
+    /// [`LagPolicy::Error`]: enum.LagPolicy.html#variant.Error
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::broadcast::{self, RecvError};
+    ///
+    /// let (s, r) = broadcast::channel(2);
+    /// for i in 0..3 {
+    ///     s.send(i).unwrap();
+    /// }
+    /// drop(s);
+    ///
+    /// assert_eq!(r.recv().await, Err(RecvError::Lagged(1)));
+    /// assert_eq!(r.recv().await, Ok(1));
+    /// assert_eq!(r.recv().await, Ok(2));
+    /// assert_eq!(r.recv().await, Err(RecvError::Closed));
+    /// #
+    /// # })
+    /// ```
+    pub async fn recv(&self) -> Result<T, RecvError> {
+        crate::future::poll_fn(|cx| {
+            let mut state = self.channel.state.lock().unwrap();
+            match self.try_recv_locked(&state) {
+                Ok(msg) => Poll::Ready(Ok(msg)),
+                Err(TryRecvError::Lagged(n)) => Poll::Ready(Err(RecvError::Lagged(n))),
+                Err(TryRecvError::Closed) => Poll::Ready(Err(RecvError::Closed)),
+                Err(TryRecvError::Empty) => {
+                    let w = cx.waker();
+                    match &mut state.receivers[self.key] {
+                        Some(old) if old.will_wake(w) => {}
+                        opt_waker => *opt_waker = Some(w.clone()),
+                    }
+                    Poll::Pending
+                }
+            }
+        })
+        .await
+    }
+
+    /// Attempts to receive the next message without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast::{self, TryRecvError};
+    ///
+    /// let (s, r) = broadcast::channel(1);
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Empty));
+    ///
+    /// s.send(1).unwrap();
+    /// assert_eq!(r.try_recv(), Ok(1));
+    ///
+    /// drop(s);
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Closed));
+    /// ```
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        let state = self.channel.state.lock().unwrap();
+        self.try_recv_locked(&state)
+    }
+
+    fn try_recv_locked(&self, state: &State<T>) -> Result<T, TryRecvError> {
+        let mut pos = self.pos.lock().unwrap();
+
+        if *pos < state.head {
+            let missed = state.head - *pos;
+            *pos = state.head;
+            if self.lag_policy == LagPolicy::Error {
+                return Err(TryRecvError::Lagged(missed));
+            }
+        }
+
+        match state.buffer.get((*pos - state.head) as usize) {
+            Some(msg) => {
+                *pos += 1;
+                Ok(msg.clone())
+            }
+            None if state.sender_count == 0 => Err(TryRecvError::Closed),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Returns the lag policy of this receiver.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast::{self, LagPolicy};
+    ///
+    /// let (_s, r) = broadcast::channel::<i32>(1);
+    /// assert_eq!(r.lag_policy(), LagPolicy::Error);
+    /// ```
+    pub fn lag_policy(&self) -> LagPolicy {
+        self.lag_policy
+    }
+
+    /// Sets what this receiver does when it has missed messages.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::broadcast::{self, LagPolicy};
+    ///
+    /// let (s, mut r) = broadcast::channel(2);
+    /// r.set_lag_policy(LagPolicy::Skip);
+    ///
+    /// for i in 0..3 {
+    ///     s.send(i).unwrap();
+    /// }
+    ///
+    /// // The first message was dropped to make room and is skipped.
+    /// assert_eq!(r.recv().await, Ok(1));
+    /// #
+    /// # })
+    /// ```
+    pub fn set_lag_policy(&mut self, policy: LagPolicy) {
+        self.lag_policy = policy;
+    }
+
+    /// Returns the number of messages this receiver hasn't received yet.
+    ///
+    /// This includes messages that were missed and will be skipped or reported as lag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast;
+    ///
+    /// let (s, r) = broadcast::channel(4);
+    /// s.send(1).unwrap();
+    /// s.send(2).unwrap();
+    /// assert_eq!(r.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        let state = self.channel.state.lock().unwrap();
+        (state.tail() - *self.pos.lock().unwrap()) as usize
+    }
+
+    /// Returns `true` if there are no messages this receiver hasn't received yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::broadcast;
+    ///
+    /// let (s, r) = broadcast::channel(4);
+    /// assert!(r.is_empty());
+    ///
+    /// s.send(1).unwrap();
+    /// assert!(!r.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        let mut state = self.channel.state.lock().unwrap();
+        let key = state.receivers.insert(None);
+        Receiver {
+            channel: self.channel.clone(),
+            pos: Mutex::new(*self.pos.lock().unwrap()),
+            lag_policy: self.lag_policy,
+            key,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.channel.state.lock().unwrap();
+        state.receivers.remove(self.key);
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Receiver { .. }")
+    }
+}
+
+/// An error returned from the [`Sender::send`] method when there are no receivers.
+///
+/// The message is returned back.
+///
+/// [`Sender::send`]: struct.Sender.html#method.send
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct SendError<T>(pub T);
+
+impl<T> SendError<T> {
+    /// Returns the message that could not be sent.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Error for SendError<T> {}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "sending into a channel without receivers".fmt(f)
+    }
+}
+
+/// An error returned from the [`Receiver::recv`] method.
+///
+/// [`Receiver::recv`]: struct.Receiver.html#method.recv
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecvError {
+    /// The receiver missed this many messages.
+    Lagged(u64),
+
+    /// All senders were dropped and there are no more messages.
+    Closed,
+}
+
+impl Error for RecvError {}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecvError::Lagged(n) => write!(f, "receiver lagged behind by {} messages", n),
+            RecvError::Closed => "receiving from a closed channel".fmt(f),
+        }
+    }
+}
+
+/// An error returned from the [`Receiver::try_recv`] method.
+///
+/// [`Receiver::try_recv`]: struct.Receiver.html#method.try_recv
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TryRecvError {
+    /// There are no new messages.
+    Empty,
+
+    /// The receiver missed this many messages.
+    Lagged(u64),
+
+    /// All senders were dropped and there are no more messages.
+    Closed,
+}
+
+impl Error for TryRecvError {}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => "receiving from an empty channel".fmt(f),
+            TryRecvError::Lagged(n) => write!(f, "receiver lagged behind by {} messages", n),
+            TryRecvError::Closed => "receiving from a closed channel".fmt(f),
+        }
+    }
+}
+
+/// The channel shared by senders and receivers.
+struct Channel<T> {
+    /// The maximum number of messages kept in the buffer.
+    cap: usize,
+
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    /// The last `cap` messages.
+    buffer: VecDeque<T>,
+
+    /// The sequence number of the first message in the buffer.
+    head: u64,
+
+    sender_count: usize,
+
+    /// Wakers of receivers waiting for a message, one slot per receiver.
+    receivers: Slab<Option<Waker>>,
+}
+
+impl<T> State<T> {
+    /// Returns the sequence number of the next message to be sent.
+    fn tail(&self) -> u64 {
+        self.head + self.buffer.len() as u64
+    }
+
+    fn wake_receivers(&mut self) {
+        for (_, opt_waker) in self.receivers.iter_mut() {
+            if let Some(w) = opt_waker.take() {
+                w.wake();
+            }
+        }
+    }
+}
@@ -0,0 +1,773 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use slab::Slab;
+
+use crate::future::Future;
+use crate::stream::Stream;
+use crate::task::{Context, Poll, Waker};
+
+/// Creates a bounded multi-producer multi-consumer channel.
+///
+/// The channel holds up to `cap` messages. Once it is full, [`send`] waits until a receiver makes
+/// room, which slows down producers that outpace their consumers. Both halves can be cloned to
+/// share them between tasks, and each message is received by exactly one receiver.
+///
+/// The channel is closed once all senders or all receivers are dropped.
+///
+/// [`send`]: struct.Sender.html#method.send
+///
+/// # Panics
+///
+/// This function panics if `cap` is zero.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::channel;
+/// use async_std::task;
+///
+/// let (s, r) = channel(1);
+///
+/// task::spawn(async move {
+///     // The second send waits until the first message is received.
+///     s.send(1).await.unwrap();
+///     s.send(2).await.unwrap();
+/// });
+///
+/// assert_eq!(r.recv().await, Ok(1));
+/// assert_eq!(r.recv().await, Ok(2));
+/// assert!(r.recv().await.is_err());
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn channel<T>(cap: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(cap > 0, "capacity must be positive");
+    Channel::create(Some(cap))
+}
+
+/// Creates an unbounded multi-producer multi-consumer channel.
+///
+/// Sending never waits because the channel grows as needed. Both halves can be cloned to share
+/// them between tasks, and each message is received by exactly one receiver.
+///
+/// The channel is closed once all senders or all receivers are dropped.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::prelude::*;
+/// use async_std::sync::unbounded;
+///
+/// let (s, mut r) = unbounded();
+///
+/// for i in 0..3 {
+///     s.try_send(i).unwrap();
+/// }
+/// drop(s);
+///
+/// // The receiver is also a stream of messages.
+/// let mut sum = 0;
+/// while let Some(i) = r.next().await {
+///     sum += i;
+/// }
+/// assert_eq!(sum, 3);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn unbounded<T>() -> (Sender<T>, Receiver<T>) {
+    Channel::create(None)
+}
+
+/// The sending side of a channel.
+///
+/// This type is created by the [`channel`] and [`unbounded`] functions. See their documentation
+/// for more details.
+///
+/// [`channel`]: fn.channel.html
+/// [`unbounded`]: fn.unbounded.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Sender<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends a message into the channel.
+    ///
+    /// If the channel is full, this method waits until there is room for the message. If the
+    /// channel is closed, the message is returned in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, r) = channel(1);
+    /// s.send(1).await.unwrap();
+    ///
+    /// drop(r);
+    /// assert_eq!(s.send(2).await.unwrap_err().into_inner(), 2);
+    /// #
+    /// # })
+    /// ```
+    pub async fn send(&self, msg: T) -> Result<(), SendError<T>> {
+        pub struct SendFuture<'a, T> {
+            channel: &'a Channel<T>,
+            msg: Option<T>,
+            opt_key: Option<usize>,
+        }
+
+        // The message is never pinned.
+        impl<T> Unpin for SendFuture<'_, T> {}
+
+        impl<T> Future for SendFuture<'_, T> {
+            type Output = Result<(), SendError<T>>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                let mut state = this.channel.lock();
+
+                let msg = this.msg.take().unwrap();
+                match state.try_push(msg) {
+                    Ok(()) => {}
+                    Err(TrySendError::Full(msg)) => {
+                        this.msg = Some(msg);
+                        state
+                            .senders_blocked
+                            .register(&mut this.opt_key, cx.waker());
+                        return Poll::Pending;
+                    }
+                    Err(TrySendError::Disconnected(msg)) => {
+                        return Poll::Ready(Err(SendError(msg)));
+                    }
+                }
+
+                if let Some(key) = this.opt_key.take() {
+                    state.senders_blocked.remove(key);
+                }
+                Poll::Ready(Ok(()))
+            }
+        }
+
+        impl<T> Drop for SendFuture<'_, T> {
+            fn drop(&mut self) {
+                if let Some(key) = self.opt_key {
+                    let mut state = self.channel.lock();
+
+                    // If we were woken up but gave up, let another sender take the free slot.
+                    if state.senders_blocked.remove(key) {
+                        state.senders_blocked.notify_one();
+                    }
+                }
+            }
+        }
+
+        SendFuture {
+            channel: &self.channel,
+            msg: Some(msg),
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Attempts to send a message into the channel without waiting.
+    ///
+    /// If the channel is full or closed, the message is returned in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::{channel, TrySendError};
+    ///
+    /// let (s, r) = channel(1);
+    /// assert_eq!(s.try_send(1), Ok(()));
+    /// assert_eq!(s.try_send(2), Err(TrySendError::Full(2)));
+    ///
+    /// drop(r);
+    /// assert_eq!(s.try_send(3), Err(TrySendError::Disconnected(3)));
+    /// ```
+    pub fn try_send(&self, msg: T) -> Result<(), TrySendError<T>> {
+        self.channel.lock().try_push(msg)
+    }
+
+    /// Returns the channel capacity, or `None` if the channel is unbounded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::{channel, unbounded};
+    ///
+    /// let (s, _) = channel::<i32>(5);
+    /// assert_eq!(s.capacity(), Some(5));
+    ///
+    /// let (s, _) = unbounded::<i32>();
+    /// assert_eq!(s.capacity(), None);
+    /// ```
+    pub fn capacity(&self) -> Option<usize> {
+        self.channel.lock().cap
+    }
+
+    /// Returns the number of messages in the channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, _r) = channel(5);
+    /// s.try_send(1).unwrap();
+    /// assert_eq!(s.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.channel.lock().queue.len()
+    }
+
+    /// Returns `true` if the channel is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, _r) = channel::<i32>(5);
+    /// assert!(s.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.channel.lock().queue.is_empty()
+    }
+
+    /// Returns `true` if the channel is full.
+    ///
+    /// Unbounded channels are never full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, _r) = channel(1);
+    /// s.try_send(1).unwrap();
+    /// assert!(s.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.channel.lock().is_full()
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Sender<T> {
+        self.channel.lock().sender_count += 1;
+        Sender {
+            channel: self.channel.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.channel.lock();
+        state.sender_count -= 1;
+
+        // Receivers waiting for messages must learn that none are coming.
+        if state.sender_count == 0 {
+            state.receivers_blocked.notify_all();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Sender { .. }")
+    }
+}
+
+/// The receiving side of a channel.
+///
+/// This type is created by the [`channel`] and [`unbounded`] functions. See their documentation
+/// for more details.
+///
+/// The receiver is also a [`Stream`] of messages that ends once the channel is closed and empty.
+///
+/// [`channel`]: fn.channel.html
+/// [`unbounded`]: fn.unbounded.html
+/// [`Stream`]: ../stream/trait.Stream.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Receiver<T> {
+    channel: Arc<Channel<T>>,
+
+    /// The key of this receiver while it is blocked as a stream.
+    opt_key: Option<usize>,
+}
+
+impl<T> Receiver<T> {
+    /// Receives a message from the channel.
+    ///
+    /// If the channel is empty, this method waits until a message is sent. An error is returned
+    /// once the channel is empty and all senders have been dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, r) = channel(1);
+    /// s.send(1).await.unwrap();
+    /// drop(s);
+    ///
+    /// assert_eq!(r.recv().await, Ok(1));
+    /// assert!(r.recv().await.is_err());
+    /// #
+    /// # })
+    /// ```
+    pub async fn recv(&self) -> Result<T, RecvError> {
+        pub struct RecvFuture<'a, T> {
+            channel: &'a Channel<T>,
+            opt_key: Option<usize>,
+        }
+
+        impl<T> Future for RecvFuture<'_, T> {
+            type Output = Result<T, RecvError>;
+
+            fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+                let this = &mut *self;
+                this.channel.poll_recv(&mut this.opt_key, cx)
+            }
+        }
+
+        impl<T> Drop for RecvFuture<'_, T> {
+            fn drop(&mut self) {
+                if let Some(key) = self.opt_key {
+                    self.channel.cancel_recv(key);
+                }
+            }
+        }
+
+        RecvFuture {
+            channel: &self.channel,
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Attempts to receive a message from the channel without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::{channel, TryRecvError};
+    ///
+    /// let (s, r) = channel(1);
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Empty));
+    ///
+    /// s.try_send(1).unwrap();
+    /// drop(s);
+    /// assert_eq!(r.try_recv(), Ok(1));
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Disconnected));
+    /// ```
+    pub fn try_recv(&self) -> Result<T, TryRecvError> {
+        self.channel.lock().try_pop()
+    }
+
+    /// Returns the channel capacity, or `None` if the channel is unbounded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::channel;
+    ///
+    /// let (_, r) = channel::<i32>(5);
+    /// assert_eq!(r.capacity(), Some(5));
+    /// ```
+    pub fn capacity(&self) -> Option<usize> {
+        self.channel.lock().cap
+    }
+
+    /// Returns the number of messages in the channel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, r) = channel(5);
+    /// s.try_send(1).unwrap();
+    /// assert_eq!(r.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.channel.lock().queue.len()
+    }
+
+    /// Returns `true` if the channel is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::channel;
+    ///
+    /// let (_s, r) = channel::<i32>(5);
+    /// assert!(r.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.channel.lock().queue.is_empty()
+    }
+
+    /// Returns `true` if the channel is full.
+    ///
+    /// Unbounded channels are never full.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::channel;
+    ///
+    /// let (s, r) = channel(1);
+    /// s.try_send(1).unwrap();
+    /// assert!(r.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.channel.lock().is_full()
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        self.channel.lock().receiver_count += 1;
+        Receiver {
+            channel: self.channel.clone(),
+            opt_key: None,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        if let Some(key) = self.opt_key {
+            self.channel.cancel_recv(key);
+        }
+
+        let mut state = self.channel.lock();
+        state.receiver_count -= 1;
+
+        // Senders waiting for room must learn that their messages can't be delivered.
+        if state.receiver_count == 0 {
+            state.senders_blocked.notify_all();
+        }
+    }
+}
+
+impl<T> Stream for Receiver<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        this.channel
+            .poll_recv(&mut this.opt_key, cx)
+            .map(|res| res.ok())
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Receiver { .. }")
+    }
+}
+
+/// An error returned from the [`Sender::send`] method.
+///
+/// The message could not be sent because the channel is closed. The message is returned back.
+///
+/// [`Sender::send`]: struct.Sender.html#method.send
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct SendError<T>(pub T);
+
+impl<T> SendError<T> {
+    /// Returns the message that could not be sent.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Error for SendError<T> {}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("SendError(..)")
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "sending into a closed channel".fmt(f)
+    }
+}
+
+/// An error returned from the [`Sender::try_send`] method.
+///
+/// The message is returned back.
+///
+/// [`Sender::try_send`]: struct.Sender.html#method.try_send
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum TrySendError<T> {
+    /// The channel is full.
+    Full(T),
+
+    /// The channel is closed.
+    Disconnected(T),
+}
+
+impl<T> TrySendError<T> {
+    /// Returns the message that could not be sent.
+    pub fn into_inner(self) -> T {
+        match self {
+            TrySendError::Full(msg) => msg,
+            TrySendError::Disconnected(msg) => msg,
+        }
+    }
+}
+
+impl<T> Error for TrySendError<T> {}
+
+impl<T> fmt::Debug for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => f.pad("Full(..)"),
+            TrySendError::Disconnected(_) => f.pad("Disconnected(..)"),
+        }
+    }
+}
+
+impl<T> fmt::Display for TrySendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrySendError::Full(_) => "sending into a full channel".fmt(f),
+            TrySendError::Disconnected(_) => "sending into a closed channel".fmt(f),
+        }
+    }
+}
+
+/// An error returned from the [`Receiver::recv`] method.
+///
+/// The channel is empty and all senders have been dropped.
+///
+/// [`Receiver::recv`]: struct.Receiver.html#method.recv
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RecvError;
+
+impl Error for RecvError {}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "receiving from an empty and closed channel".fmt(f)
+    }
+}
+
+/// An error returned from the [`Receiver::try_recv`] method.
+///
+/// [`Receiver::try_recv`]: struct.Receiver.html#method.try_recv
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TryRecvError {
+    /// The channel is empty.
+    Empty,
+
+    /// The channel is empty and closed.
+    Disconnected,
+}
+
+impl Error for TryRecvError {}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => "receiving from an empty channel".fmt(f),
+            TryRecvError::Disconnected => "receiving from an empty and closed channel".fmt(f),
+        }
+    }
+}
+
+/// The state shared by all senders and receivers of a channel.
+struct Channel<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    /// Messages in the channel.
+    queue: VecDeque<T>,
+
+    /// The number of live senders.
+    sender_count: usize,
+
+    /// The number of live receivers.
+    receiver_count: usize,
+
+    /// Senders waiting for room in the channel.
+    senders_blocked: Blocked,
+
+    /// Receivers waiting for messages.
+    receivers_blocked: Blocked,
+
+    /// The capacity, or `None` if the channel is unbounded.
+    cap: Option<usize>,
+}
+
+impl<T> Channel<T> {
+    fn create(cap: Option<usize>) -> (Sender<T>, Receiver<T>) {
+        let channel = Arc::new(Channel {
+            state: Mutex::new(State {
+                queue: VecDeque::with_capacity(cap.unwrap_or(0)),
+                sender_count: 1,
+                receiver_count: 1,
+                senders_blocked: Blocked::new(),
+                receivers_blocked: Blocked::new(),
+                cap,
+            }),
+        });
+
+        let s = Sender {
+            channel: channel.clone(),
+        };
+        let r = Receiver {
+            channel,
+            opt_key: None,
+        };
+        (s, r)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state.lock().unwrap()
+    }
+
+    /// Polls a receive operation registered under `opt_key`.
+    fn poll_recv(
+        &self,
+        opt_key: &mut Option<usize>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<T, RecvError>> {
+        let mut state = self.lock();
+
+        let res = match state.try_pop() {
+            Ok(msg) => Ok(msg),
+            Err(TryRecvError::Disconnected) => Err(RecvError),
+            Err(TryRecvError::Empty) => {
+                state.receivers_blocked.register(opt_key, cx.waker());
+                return Poll::Pending;
+            }
+        };
+
+        if let Some(key) = opt_key.take() {
+            state.receivers_blocked.remove(key);
+        }
+        Poll::Ready(res)
+    }
+
+    /// Cancels a blocked receive operation.
+    fn cancel_recv(&self, key: usize) {
+        let mut state = self.lock();
+
+        // If we were woken up but gave up, let another receiver take the message.
+        if state.receivers_blocked.remove(key) {
+            state.receivers_blocked.notify_one();
+        }
+    }
+}
+
+impl<T> State<T> {
+    fn is_full(&self) -> bool {
+        self.cap.is_some_and(|cap| self.queue.len() >= cap)
+    }
+
+    fn try_push(&mut self, msg: T) -> Result<(), TrySendError<T>> {
+        if self.receiver_count == 0 {
+            return Err(TrySendError::Disconnected(msg));
+        }
+        if self.is_full() {
+            return Err(TrySendError::Full(msg));
+        }
+
+        self.queue.push_back(msg);
+        self.receivers_blocked.notify_one();
+        Ok(())
+    }
+
+    fn try_pop(&mut self) -> Result<T, TryRecvError> {
+        match self.queue.pop_front() {
+            Some(msg) => {
+                self.senders_blocked.notify_one();
+                Ok(msg)
+            }
+            None if self.sender_count == 0 => Err(TryRecvError::Disconnected),
+            None => Err(TryRecvError::Empty),
+        }
+    }
+}
+
+/// A list of blocked tasks.
+///
+/// An entry without a waker belongs to a task that has been woken up but hasn't been polled yet.
+struct Blocked {
+    entries: Slab<Option<Waker>>,
+}
+
+impl Blocked {
+    fn new() -> Blocked {
+        Blocked {
+            entries: Slab::new(),
+        }
+    }
+
+    /// Inserts or updates the entry of a blocked task.
+    fn register(&mut self, opt_key: &mut Option<usize>, w: &Waker) {
+        match *opt_key {
+            None => *opt_key = Some(self.entries.insert(Some(w.clone()))),
+            Some(key) => match &mut self.entries[key] {
+                Some(old) if old.will_wake(w) => {}
+                opt_waker => *opt_waker = Some(w.clone()),
+            },
+        }
+    }
+
+    /// Removes an entry, returning `true` if its task had been woken up.
+    fn remove(&mut self, key: usize) -> bool {
+        self.entries.remove(key).is_none()
+    }
+
+    /// Wakes up one blocked task that hasn't been woken up yet.
+    fn notify_one(&mut self) {
+        if let Some((_, opt_waker)) = self.entries.iter_mut().find(|(_, w)| w.is_some()) {
+            opt_waker.take().unwrap().wake();
+        }
+    }
+
+    /// Wakes up all blocked tasks.
+    fn notify_all(&mut self) {
+        for (_, opt_waker) in self.entries.iter_mut() {
+            if let Some(w) = opt_waker.take() {
+                w.wake();
+            }
+        }
+    }
+}
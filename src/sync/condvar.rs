@@ -0,0 +1,342 @@
+use std::fmt;
+use std::pin::Pin;
+use std::time::Duration;
+
+use slab::Slab;
+
+use crate::future::{self, Future};
+use crate::sync::mutex::guard_lock;
+use crate::sync::MutexGuard;
+use crate::task::{Context, Poll, Waker};
+
+/// A condition variable.
+///
+/// Condition variables let tasks wait for some condition on data protected by a [`Mutex`] to
+/// become true. Waiting releases the lock, and the lock is acquired again before the wait
+/// returns. Like with threads, a wait may return without a notification, so the condition should
+/// always be checked in a loop, or with [`wait_until`].
+///
+/// This type is an async version of [`std::sync::Condvar`].
+///
+/// [`Mutex`]: struct.Mutex.html
+/// [`wait_until`]: #method.wait_until
+/// [`std::sync::Condvar`]: https://doc.rust-lang.org/std/sync/struct.Condvar.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::{Arc, Condvar, Mutex};
+/// use async_std::task;
+///
+/// let pair = Arc::new((Mutex::new(false), Condvar::new()));
+/// let pair2 = pair.clone();
+///
+/// // Inside of our lock, spawn a new task, and then wait for it to start.
+/// task::spawn(async move {
+///     let (lock, cvar) = &*pair2;
+///     let mut started = lock.lock().await;
+///     *started = true;
+///     // We notify the condvar that the value has changed.
+///     cvar.notify_one();
+/// });
+///
+/// // Wait for the task to start up.
+/// let (lock, cvar) = &*pair;
+/// let mut started = lock.lock().await;
+/// while !*started {
+///     started = cvar.wait(started).await;
+/// }
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Condvar {
+    blocked: std::sync::Mutex<Slab<Entry>>,
+}
+
+/// An entry for a waiting task.
+struct Entry {
+    /// The waker of the waiting task, if it has been polled.
+    waker: Option<Waker>,
+
+    /// Set when the task has been notified.
+    notified: bool,
+}
+
+impl Condvar {
+    /// Creates a new condition variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Condvar;
+    ///
+    /// let cvar = Condvar::new();
+    /// ```
+    pub fn new() -> Condvar {
+        Condvar {
+            blocked: std::sync::Mutex::new(Slab::new()),
+        }
+    }
+
+    /// Releases the lock and waits until this condition variable is notified.
+    ///
+    /// The lock is acquired again before this method returns. Notifications sent before the lock
+    /// is released are not seen by this call.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{Arc, Condvar, Mutex};
+    /// use async_std::task;
+    ///
+    /// let pair = Arc::new((Mutex::new(false), Condvar::new()));
+    /// let pair2 = pair.clone();
+    ///
+    /// task::spawn(async move {
+    ///     let (lock, cvar) = &*pair2;
+    ///     *lock.lock().await = true;
+    ///     cvar.notify_one();
+    /// });
+    ///
+    /// let (lock, cvar) = &*pair;
+    /// let mut started = lock.lock().await;
+    /// while !*started {
+    ///     started = cvar.wait(started).await;
+    /// }
+    /// #
+    /// # })
+    /// ```
+    pub async fn wait<'a, T>(&self, guard: MutexGuard<'a, T>) -> MutexGuard<'a, T> {
+        let mutex = guard_lock(&guard);
+
+        // Register before unlocking so that no notification can slip in between.
+        let wait = self.register();
+        drop(guard);
+
+        wait.await;
+        mutex.lock().await
+    }
+
+    /// Waits until the condition returns `true`, releasing the lock while waiting.
+    ///
+    /// The condition is checked with the lock held, first right away and then every time this
+    /// condition variable is notified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{Arc, Condvar, Mutex};
+    /// use async_std::task;
+    ///
+    /// let pair = Arc::new((Mutex::new(0), Condvar::new()));
+    /// let pair2 = pair.clone();
+    ///
+    /// task::spawn(async move {
+    ///     let (lock, cvar) = &*pair2;
+    ///     for _ in 0..3 {
+    ///         *lock.lock().await += 1;
+    ///         cvar.notify_all();
+    ///     }
+    /// });
+    ///
+    /// let (lock, cvar) = &*pair;
+    /// let n = cvar.wait_until(lock.lock().await, |n| *n == 3).await;
+    /// assert_eq!(*n, 3);
+    /// #
+    /// # })
+    /// ```
+    pub async fn wait_until<'a, T, F>(
+        &self,
+        mut guard: MutexGuard<'a, T>,
+        mut condition: F,
+    ) -> MutexGuard<'a, T>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        while !condition(&mut *guard) {
+            guard = self.wait(guard).await;
+        }
+        guard
+    }
+
+    /// Releases the lock and waits until this condition variable is notified or the timeout
+    /// elapses.
+    ///
+    /// The lock is acquired again before this method returns. The returned [`WaitTimeoutResult`]
+    /// tells whether the timeout elapsed.
+    ///
+    /// [`WaitTimeoutResult`]: struct.WaitTimeoutResult.html
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::sync::{Condvar, Mutex};
+    ///
+    /// let lock = Mutex::new(());
+    /// let cvar = Condvar::new();
+    ///
+    /// let (_guard, res) = cvar
+    ///     .wait_timeout(lock.lock().await, Duration::from_millis(10))
+    ///     .await;
+    /// assert!(res.timed_out());
+    /// #
+    /// # })
+    /// ```
+    pub async fn wait_timeout<'a, T>(
+        &self,
+        guard: MutexGuard<'a, T>,
+        dur: Duration,
+    ) -> (MutexGuard<'a, T>, WaitTimeoutResult) {
+        let mutex = guard_lock(&guard);
+
+        let wait = self.register();
+        drop(guard);
+
+        let timed_out = future::timeout(dur, wait).await.is_err();
+        (mutex.lock().await, WaitTimeoutResult(timed_out))
+    }
+
+    /// Wakes up one waiting task.
+    ///
+    /// If no task is waiting, the notification is lost.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Condvar;
+    ///
+    /// let cvar = Condvar::new();
+    /// cvar.notify_one();
+    /// ```
+    pub fn notify_one(&self) {
+        let mut blocked = self.blocked.lock().unwrap();
+        notify_one(&mut blocked);
+    }
+
+    /// Wakes up all waiting tasks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Condvar;
+    ///
+    /// let cvar = Condvar::new();
+    /// cvar.notify_all();
+    /// ```
+    pub fn notify_all(&self) {
+        let mut blocked = self.blocked.lock().unwrap();
+        for (_, entry) in blocked.iter_mut() {
+            notify(entry);
+        }
+    }
+
+    /// Adds a waiting task and returns a future that completes once it is notified.
+    fn register(&self) -> Wait<'_> {
+        let key = self.blocked.lock().unwrap().insert(Entry {
+            waker: None,
+            notified: false,
+        });
+        Wait {
+            condvar: self,
+            opt_key: Some(key),
+        }
+    }
+}
+
+impl Default for Condvar {
+    fn default() -> Condvar {
+        Condvar::new()
+    }
+}
+
+impl fmt::Debug for Condvar {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Condvar { .. }")
+    }
+}
+
+/// A type indicating whether a timed wait on a condition variable returned due to a time out or
+/// not.
+///
+/// It is returned by the [`wait_timeout`] method.
+///
+/// [`wait_timeout`]: struct.Condvar.html#method.wait_timeout
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct WaitTimeoutResult(bool);
+
+impl WaitTimeoutResult {
+    /// Returns `true` if the wait was known to have timed out.
+    pub fn timed_out(&self) -> bool {
+        self.0
+    }
+}
+
+/// A future that completes once its task is notified.
+struct Wait<'a> {
+    condvar: &'a Condvar,
+    opt_key: Option<usize>,
+}
+
+impl Future for Wait<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let key = self.opt_key.unwrap();
+        let mut blocked = self.condvar.blocked.lock().unwrap();
+
+        if blocked[key].notified {
+            blocked.remove(key);
+            drop(blocked);
+            self.opt_key = None;
+            Poll::Ready(())
+        } else {
+            let w = cx.waker();
+            match &mut blocked[key].waker {
+                Some(old) if old.will_wake(w) => {}
+                opt_waker => *opt_waker = Some(w.clone()),
+            }
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for Wait<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.opt_key {
+            let mut blocked = self.condvar.blocked.lock().unwrap();
+
+            // If we were notified but gave up, pass the notification on to another task.
+            if blocked.remove(key).notified {
+                notify_one(&mut blocked);
+            }
+        }
+    }
+}
+
+/// Notifies the first task that hasn't been notified yet.
+fn notify_one(blocked: &mut Slab<Entry>) {
+    if let Some((_, entry)) = blocked.iter_mut().find(|(_, entry)| !entry.notified) {
+        notify(entry);
+    }
+}
+
+fn notify(entry: &mut Entry) {
+    entry.notified = true;
+    if let Some(w) = entry.waker.take() {
+        w.wake();
+    }
+}
@@ -40,5 +40,25 @@ mod rwlock;
 
 cfg_unstable! {
     pub use barrier::{Barrier, BarrierWaitResult};
+    pub use channel::{
+        channel, unbounded, Receiver, RecvError, SendError, Sender, TryRecvError, TrySendError,
+    };
+    pub use condvar::{Condvar, WaitTimeoutResult};
+    pub use notify::{Notified, Notify};
+    pub use once_cell::{Lazy, OnceCell};
+    pub use rwlock::RwLockUpgradableReadGuard;
+    pub use semaphore::{OwnedSemaphorePermit, Semaphore, SemaphorePermit};
+    pub use wait_group::WaitGroup;
+
+    pub mod broadcast;
+    pub mod oneshot;
+    pub mod watch;
+
     mod barrier;
+    mod channel;
+    mod condvar;
+    mod notify;
+    mod once_cell;
+    mod semaphore;
+    mod wait_group;
 }
@@ -1,4 +1,5 @@
 use std::cell::UnsafeCell;
+use std::collections::VecDeque;
 use std::fmt;
 use std::ops::{Deref, DerefMut};
 use std::pin::Pin;
@@ -12,7 +13,7 @@ use crate::task::{Context, Poll, Waker};
 /// Set if the mutex is locked.
 const LOCK: usize = 1;
 
-/// Set if there are tasks blocked on the mutex.
+/// Set if there are tasks queued up for the mutex.
 const BLOCKED: usize = 1 << 1;
 
 /// A mutual exclusion primitive for protecting shared data.
@@ -21,6 +22,16 @@ const BLOCKED: usize = 1 << 1;
 ///
 /// [`std::sync::Mutex`]: https://doc.rust-lang.org/std/sync/struct.Mutex.html
 ///
+/// # Fairness
+///
+/// Tasks waiting for the lock are queued up in the order they called [`lock`]. When the lock is
+/// released while tasks are waiting, it is handed over directly to the first one in line, so a
+/// task that keeps locking and unlocking in a loop can't starve the others. For the same reason,
+/// [`try_lock`] fails while other tasks are waiting, even if the lock has just been released.
+///
+/// [`lock`]: #method.lock
+/// [`try_lock`]: #method.try_lock
+///
 /// # Examples
 ///
 /// ```
@@ -50,7 +61,7 @@ const BLOCKED: usize = 1 << 1;
 /// ```
 pub struct Mutex<T> {
     state: AtomicUsize,
-    blocked: std::sync::Mutex<Slab<Option<Waker>>>,
+    blocked: std::sync::Mutex<Blocked>,
     value: UnsafeCell<T>,
 }
 
@@ -70,7 +81,7 @@ impl<T> Mutex<T> {
     pub fn new(t: T) -> Mutex<T> {
         Mutex {
             state: AtomicUsize::new(0),
-            blocked: std::sync::Mutex::new(Slab::new()),
+            blocked: std::sync::Mutex::new(Blocked::new()),
             value: UnsafeCell::new(t),
         }
     }
@@ -105,51 +116,52 @@ impl<T> Mutex<T> {
         pub struct LockFuture<'a, T> {
             mutex: &'a Mutex<T>,
             opt_key: Option<usize>,
-            acquired: bool,
         }
 
         impl<'a, T> Future for LockFuture<'a, T> {
             type Output = MutexGuard<'a, T>;
 
             fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-                match self.mutex.try_lock() {
-                    Some(guard) => {
-                        self.acquired = true;
-                        Poll::Ready(guard)
-                    }
+                let mutex = self.mutex;
+
+                match self.opt_key {
                     None => {
-                        let mut blocked = self.mutex.blocked.lock().unwrap();
-
-                        // Register the current task.
-                        match self.opt_key {
-                            None => {
-                                // Insert a new entry into the list of blocked tasks.
-                                let w = cx.waker().clone();
-                                let key = blocked.insert(Some(w));
-                                self.opt_key = Some(key);
-
-                                if blocked.len() == 1 {
-                                    self.mutex.state.fetch_or(BLOCKED, Ordering::Relaxed);
-                                }
-                            }
-                            Some(key) => {
-                                // There is already an entry in the list of blocked tasks. Just
-                                // reset the waker if it was removed.
-                                if blocked[key].is_none() {
-                                    let w = cx.waker().clone();
-                                    blocked[key] = Some(w);
-                                }
-                            }
+                        if let Some(guard) = mutex.try_lock() {
+                            return Poll::Ready(guard);
+                        }
+
+                        let mut blocked = mutex.blocked.lock().unwrap();
+
+                        // Check again because the mutex may have been unlocked before the list
+                        // of blocked tasks was locked. An unlocking task must acquire the list
+                        // once our flag is set, so it can't miss the entry we're about to add.
+                        if mutex.state.fetch_or(BLOCKED, Ordering::Acquire) & LOCK == 0 {
+                            // The mutex is unlocked, so nobody else is waiting for it.
+                            debug_assert!(blocked.is_empty());
+                            mutex.state.store(LOCK, Ordering::Relaxed);
+                            return Poll::Ready(MutexGuard(mutex));
                         }
 
-                        // Try locking again because it's possible the mutex got unlocked just
-                        // before the current task was registered as a blocked task.
-                        match self.mutex.try_lock() {
-                            Some(guard) => {
-                                self.acquired = true;
-                                Poll::Ready(guard)
+                        // Join the back of the queue.
+                        let key = blocked.insert(cx.waker().clone());
+                        self.opt_key = Some(key);
+                        Poll::Pending
+                    }
+                    Some(key) => {
+                        let mut blocked = mutex.blocked.lock().unwrap();
+
+                        if blocked.entries[key].granted {
+                            // The previous owner handed the lock over to us.
+                            blocked.remove(key);
+                            self.opt_key = None;
+                            Poll::Ready(MutexGuard(mutex))
+                        } else {
+                            // Keep waiting, but make sure to wake the current task.
+                            let w = cx.waker();
+                            if !blocked.entries[key].waker.will_wake(w) {
+                                blocked.entries[key].waker = w.clone();
                             }
-                            None => Poll::Pending,
+                            Poll::Pending
                         }
                     }
                 }
@@ -160,18 +172,12 @@ impl<T> Mutex<T> {
             fn drop(&mut self) {
                 if let Some(key) = self.opt_key {
                     let mut blocked = self.mutex.blocked.lock().unwrap();
-                    let opt_waker = blocked.remove(key);
 
-                    if opt_waker.is_none() && !self.acquired {
-                        // We were awoken but didn't acquire the lock. Wake up another task.
-                        if let Some((_, opt_waker)) = blocked.iter_mut().next() {
-                            if let Some(w) = opt_waker.take() {
-                                w.wake();
-                            }
-                        }
-                    }
-
-                    if blocked.is_empty() {
+                    if blocked.remove(key) {
+                        // The lock was handed over to us, but we don't want it anymore. Pass it
+                        // on to the next task in line.
+                        self.mutex.hand_over(&mut blocked);
+                    } else if blocked.is_empty() {
                         self.mutex.state.fetch_and(!BLOCKED, Ordering::Relaxed);
                     }
                 }
@@ -181,7 +187,6 @@ impl<T> Mutex<T> {
         LockFuture {
             mutex: self,
             opt_key: None,
-            acquired: false,
         }
         .await
     }
@@ -189,7 +194,8 @@ impl<T> Mutex<T> {
     /// Attempts to acquire the lock.
     ///
     /// If the lock could not be acquired at this time, then [`None`] is returned. Otherwise, a
-    /// guard is returned that releases the lock when dropped.
+    /// guard is returned that releases the lock when dropped. This method doesn't jump the queue:
+    /// it fails while other tasks are waiting for the lock.
     ///
     /// [`None`]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
     ///
@@ -220,7 +226,12 @@ impl<T> Mutex<T> {
     /// # })
     /// ```
     pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
-        if self.state.fetch_or(LOCK, Ordering::Acquire) & LOCK == 0 {
+        // Fails if the mutex is locked or if other tasks are queued up for it.
+        if self
+            .state
+            .compare_exchange(0, LOCK, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+        {
             Some(MutexGuard(self))
         } else {
             None
@@ -262,6 +273,22 @@ impl<T> Mutex<T> {
     pub fn get_mut(&mut self) -> &mut T {
         unsafe { &mut *self.value.get() }
     }
+
+    /// Hands the lock over to the first blocked task, or unlocks the mutex if there is none.
+    ///
+    /// This must only be called by the current owner of the lock.
+    fn hand_over(&self, blocked: &mut Blocked) {
+        match blocked.grant_next() {
+            // The lock stays locked and now belongs to the woken task.
+            Some(w) => {
+                if blocked.is_empty() {
+                    self.state.store(LOCK, Ordering::Release);
+                }
+                w.wake();
+            }
+            None => self.state.store(0, Ordering::Release),
+        }
+    }
 }
 
 impl<T: fmt::Debug> fmt::Debug for Mutex<T> {
@@ -298,23 +325,27 @@ impl<T: Default> Default for Mutex<T> {
 /// A guard that releases the lock when dropped.
 pub struct MutexGuard<'a, T>(&'a Mutex<T>);
 
+/// Returns the mutex a guard belongs to.
+#[cfg(feature = "unstable")]
+pub(crate) fn guard_lock<'a, T>(guard: &MutexGuard<'a, T>) -> &'a Mutex<T> {
+    guard.0
+}
+
 unsafe impl<T: Send> Send for MutexGuard<'_, T> {}
 unsafe impl<T: Sync> Sync for MutexGuard<'_, T> {}
 
 impl<T> Drop for MutexGuard<'_, T> {
     fn drop(&mut self) {
-        let state = self.0.state.fetch_and(!LOCK, Ordering::AcqRel);
-
-        // If there are any blocked tasks, wake one of them up.
-        if state & BLOCKED != 0 {
-            let mut blocked = self.0.blocked.lock().unwrap();
-
-            if let Some((_, opt_waker)) = blocked.iter_mut().next() {
-                // If there is no waker in this entry, that means it was already woken.
-                if let Some(w) = opt_waker.take() {
-                    w.wake();
-                }
-            }
+        let mutex = self.0;
+
+        // If there are no blocked tasks, simply unlock the mutex.
+        if mutex
+            .state
+            .compare_exchange(LOCK, 0, Ordering::Release, Ordering::Relaxed)
+            .is_err()
+        {
+            let mut blocked = mutex.blocked.lock().unwrap();
+            mutex.hand_over(&mut blocked);
         }
     }
 }
@@ -344,3 +375,62 @@ impl<T> DerefMut for MutexGuard<'_, T> {
         unsafe { &mut *self.0.value.get() }
     }
 }
+
+/// The queue of tasks blocked on a mutex.
+struct Blocked {
+    /// Entries for blocked tasks.
+    entries: Slab<Entry>,
+
+    /// Keys of entries that are still waiting, in the order the tasks arrived.
+    queue: VecDeque<usize>,
+}
+
+/// An entry for a blocked task.
+struct Entry {
+    /// The waker of the blocked task.
+    waker: Waker,
+
+    /// Set when the lock has been handed over to the task.
+    granted: bool,
+}
+
+impl Blocked {
+    fn new() -> Blocked {
+        Blocked {
+            entries: Slab::new(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Returns `true` if no tasks are waiting for the lock.
+    fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Adds a task to the back of the queue and returns its key.
+    fn insert(&mut self, waker: Waker) -> usize {
+        let key = self.entries.insert(Entry {
+            waker,
+            granted: false,
+        });
+        self.queue.push_back(key);
+        key
+    }
+
+    /// Removes an entry, returning `true` if the lock had been handed over to it.
+    fn remove(&mut self, key: usize) -> bool {
+        let entry = self.entries.remove(key);
+        if !entry.granted {
+            self.queue.retain(|&k| k != key);
+        }
+        entry.granted
+    }
+
+    /// Hands the lock over to the first task in the queue and returns its waker.
+    fn grant_next(&mut self) -> Option<Waker> {
+        let key = self.queue.pop_front()?;
+        let entry = &mut self.entries[key];
+        entry.granted = true;
+        Some(entry.waker.clone())
+    }
+}
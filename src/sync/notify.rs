@@ -0,0 +1,292 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+use slab::Slab;
+
+use crate::future::Future;
+use crate::task::{Context, Poll, Waker};
+
+/// Notifies tasks to wake up.
+///
+/// This is a building block for custom synchronization that doesn't need to pass any data along,
+/// like waking up a task that flushes a buffer whenever new data is queued.
+///
+/// Tasks wait with [`notified`]. [`notify_one`] wakes up the task that has been waiting the
+/// longest. If no task is waiting, it stores a permit instead, and the next call to [`notified`]
+/// completes right away, so a notification sent just before a task starts waiting is not lost.
+/// [`notify_waiters`] wakes up all tasks that are waiting at the moment, without storing a
+/// permit.
+///
+/// [`notified`]: #method.notified
+/// [`notify_one`]: #method.notify_one
+/// [`notify_waiters`]: #method.notify_waiters
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::{Arc, Notify};
+/// use async_std::task;
+///
+/// let notify = Arc::new(Notify::new());
+/// let notify2 = notify.clone();
+///
+/// let flusher = task::spawn(async move {
+///     notify2.notified().await;
+///     println!("flushing");
+/// });
+///
+/// notify.notify_one();
+/// flusher.await;
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Notify {
+    state: Mutex<State>,
+}
+
+struct State {
+    /// Set by `notify_one` when no task was waiting.
+    permit: bool,
+
+    /// Waiting tasks.
+    entries: Slab<Entry>,
+
+    /// Keys of waiting tasks that haven't been notified yet, in the order they started waiting.
+    queue: VecDeque<usize>,
+}
+
+/// An entry for a waiting task.
+struct Entry {
+    /// The waker of the waiting task, if it has been polled.
+    waker: Option<Waker>,
+
+    /// Set when the task has been notified.
+    notified: Option<Notification>,
+}
+
+/// How a task was notified.
+#[derive(Clone, Copy, PartialEq)]
+enum Notification {
+    One,
+    All,
+}
+
+impl Notify {
+    /// Creates a new `Notify`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Notify;
+    ///
+    /// let notify = Notify::new();
+    /// ```
+    pub fn new() -> Notify {
+        Notify {
+            state: Mutex::new(State {
+                permit: false,
+                entries: Slab::new(),
+                queue: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Returns a future that completes once this task is notified.
+    ///
+    /// The task starts waiting when this method is called, not when the future is first polled.
+    /// This way, the future can be created before checking some condition, and a notification
+    /// sent in between is not missed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Notify;
+    ///
+    /// let notify = Notify::new();
+    ///
+    /// let notified = notify.notified();
+    /// notify.notify_waiters();
+    /// notified.await;
+    /// #
+    /// # })
+    /// ```
+    pub fn notified(&self) -> Notified<'_> {
+        let mut state = self.state.lock().unwrap();
+
+        let notified = if state.permit {
+            state.permit = false;
+            Some(Notification::One)
+        } else {
+            None
+        };
+
+        let key = state.entries.insert(Entry {
+            waker: None,
+            notified,
+        });
+        if notified.is_none() {
+            state.queue.push_back(key);
+        }
+
+        Notified {
+            notify: self,
+            opt_key: Some(key),
+        }
+    }
+
+    /// Wakes up the task that has been waiting the longest.
+    ///
+    /// If no task is waiting, a permit is stored and the next call to [`notified`] completes
+    /// right away. At most one permit is stored.
+    ///
+    /// [`notified`]: #method.notified
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Notify;
+    ///
+    /// let notify = Notify::new();
+    ///
+    /// // The permit makes the next wait complete immediately.
+    /// notify.notify_one();
+    /// notify.notified().await;
+    /// #
+    /// # })
+    /// ```
+    pub fn notify_one(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.notify_one();
+    }
+
+    /// Wakes up all tasks that are currently waiting.
+    ///
+    /// No permit is stored, so tasks that start waiting afterwards are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Notify;
+    ///
+    /// let notify = Notify::new();
+    ///
+    /// let a = notify.notified();
+    /// let b = notify.notified();
+    /// notify.notify_waiters();
+    ///
+    /// a.await;
+    /// b.await;
+    /// #
+    /// # })
+    /// ```
+    pub fn notify_waiters(&self) {
+        let mut state = self.state.lock().unwrap();
+        while let Some(key) = state.queue.pop_front() {
+            state.entries[key].notify(Notification::All);
+        }
+    }
+}
+
+impl Default for Notify {
+    fn default() -> Notify {
+        Notify::new()
+    }
+}
+
+impl fmt::Debug for Notify {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Notify { .. }")
+    }
+}
+
+impl State {
+    fn notify_one(&mut self) {
+        match self.queue.pop_front() {
+            Some(key) => self.entries[key].notify(Notification::One),
+            None => self.permit = true,
+        }
+    }
+}
+
+impl Entry {
+    fn notify(&mut self, notification: Notification) {
+        self.notified = Some(notification);
+        if let Some(w) = self.waker.take() {
+            w.wake();
+        }
+    }
+}
+
+/// A future that completes once its task is notified.
+///
+/// This future is created by the [`notified`] method on [`Notify`]. See its documentation for
+/// more.
+///
+/// [`notified`]: struct.Notify.html#method.notified
+/// [`Notify`]: struct.Notify.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Notified<'a> {
+    notify: &'a Notify,
+    opt_key: Option<usize>,
+}
+
+impl Future for Notified<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let key = match self.opt_key {
+            Some(key) => key,
+            None => return Poll::Ready(()),
+        };
+        let mut state = self.notify.state.lock().unwrap();
+
+        if state.entries[key].notified.is_some() {
+            state.entries.remove(key);
+            drop(state);
+            self.opt_key = None;
+            Poll::Ready(())
+        } else {
+            let w = cx.waker();
+            match &mut state.entries[key].waker {
+                Some(old) if old.will_wake(w) => {}
+                opt_waker => *opt_waker = Some(w.clone()),
+            }
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for Notified<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.opt_key {
+            let mut state = self.notify.state.lock().unwrap();
+
+            match state.entries.remove(key).notified {
+                // We were picked by `notify_one` but gave up, so pass the notification on.
+                Some(Notification::One) => state.notify_one(),
+                Some(Notification::All) => {}
+                None => state.queue.retain(|&k| k != key),
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Notified<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Notified { .. }")
+    }
+}
@@ -0,0 +1,411 @@
+use std::cell::UnsafeCell;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::future::Future;
+use crate::sync::Mutex;
+
+/// A cell that is written to only once, with an async initializer.
+///
+/// If several tasks try to initialize the cell at the same time, only one initializer runs and
+/// the other tasks wait for its result. If the running initializer is cancelled or fails, the next
+/// waiting task runs its own initializer instead.
+///
+/// This is useful for one-time setup of connection pools and clients that are shared across
+/// tasks.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::{Arc, OnceCell};
+/// use async_std::task;
+///
+/// let cell = Arc::new(OnceCell::new());
+/// let mut tasks = Vec::new();
+///
+/// for i in 0..4 {
+///     let cell = cell.clone();
+///     tasks.push(task::spawn(async move {
+///         // Only one of the initializers runs.
+///         *cell.get_or_init(|| async move { i }).await
+///     }));
+/// }
+///
+/// let first = tasks.remove(0).await;
+/// for t in tasks {
+///     assert_eq!(t.await, first);
+/// }
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct OnceCell<T> {
+    /// Set once the value has been written.
+    initialized: AtomicBool,
+
+    /// Held by the task running an initializer.
+    lock: Mutex<()>,
+
+    value: UnsafeCell<Option<T>>,
+}
+
+unsafe impl<T: Send> Send for OnceCell<T> {}
+unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+
+impl<T> OnceCell<T> {
+    /// Creates a new empty cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::OnceCell;
+    ///
+    /// let cell = OnceCell::<i32>::new();
+    /// assert!(cell.get().is_none());
+    /// ```
+    pub fn new() -> OnceCell<T> {
+        OnceCell {
+            initialized: AtomicBool::new(false),
+            lock: Mutex::new(()),
+            value: UnsafeCell::new(None),
+        }
+    }
+
+    /// Returns the value, or `None` if the cell is not initialized yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    /// assert_eq!(cell.get(), None);
+    ///
+    /// cell.get_or_init(|| async { 1 }).await;
+    /// assert_eq!(cell.get(), Some(&1));
+    /// #
+    /// # })
+    /// ```
+    pub fn get(&self) -> Option<&T> {
+        if self.initialized.load(Ordering::Acquire) {
+            // The value is never written again once initialized.
+            unsafe { (*self.value.get()).as_ref() }
+        } else {
+            None
+        }
+    }
+
+    /// Returns a mutable reference to the value, or `None` if the cell is not initialized yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::OnceCell;
+    ///
+    /// let mut cell = OnceCell::new();
+    /// cell.set(1).await.unwrap();
+    ///
+    /// *cell.get_mut().unwrap() += 1;
+    /// assert_eq!(cell.get(), Some(&2));
+    /// #
+    /// # })
+    /// ```
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.value.get_mut().as_mut()
+    }
+
+    /// Sets the value if the cell is not initialized yet.
+    ///
+    /// If an initializer is running, this method waits for it. If the cell ends up initialized by
+    /// someone else, the value is returned back in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    /// assert_eq!(cell.set(1).await, Ok(()));
+    /// assert_eq!(cell.set(2).await, Err(2));
+    /// #
+    /// # })
+    /// ```
+    pub async fn set(&self, value: T) -> Result<(), T> {
+        if self.initialized.load(Ordering::Acquire) {
+            return Err(value);
+        }
+
+        let _guard = self.lock.lock().await;
+        if self.initialized.load(Ordering::Acquire) {
+            return Err(value);
+        }
+
+        self.store(value);
+        Ok(())
+    }
+
+    /// Returns the value, initializing the cell with `f` if it is empty.
+    ///
+    /// Concurrent calls run only one initializer at a time, and the others wait for it. If the
+    /// running initializer is cancelled, the next caller runs its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    ///
+    /// assert_eq!(*cell.get_or_init(|| async { 1 }).await, 1);
+    /// assert_eq!(*cell.get_or_init(|| async { 2 }).await, 1);
+    /// #
+    /// # })
+    /// ```
+    pub async fn get_or_init<F, Fut>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        if let Some(value) = self.get() {
+            return value;
+        }
+
+        let _guard = self.lock.lock().await;
+        if let Some(value) = self.get() {
+            return value;
+        }
+
+        let value = f().await;
+        self.store(value)
+    }
+
+    /// Returns the value, initializing the cell with the fallible `f` if it is empty.
+    ///
+    /// If `f` returns an error, the cell stays empty and the error is returned. Concurrent calls
+    /// run only one initializer at a time, like with [`get_or_init`].
+    ///
+    /// [`get_or_init`]: #method.get_or_init
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    ///
+    /// let res = cell.get_or_try_init(|| async { Err("unavailable") }).await;
+    /// assert_eq!(res, Err("unavailable"));
+    /// assert_eq!(cell.get(), None);
+    ///
+    /// let res = cell.get_or_try_init(|| async { Ok::<_, &str>(1) }).await;
+    /// assert_eq!(res, Ok(&1));
+    /// #
+    /// # })
+    /// ```
+    pub async fn get_or_try_init<F, Fut, E>(&self, f: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if let Some(value) = self.get() {
+            return Ok(value);
+        }
+
+        let _guard = self.lock.lock().await;
+        if let Some(value) = self.get() {
+            return Ok(value);
+        }
+
+        let value = f().await?;
+        Ok(self.store(value))
+    }
+
+    /// Takes the value out of the cell, leaving it empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::OnceCell;
+    ///
+    /// let mut cell = OnceCell::new();
+    /// cell.set(1).await.unwrap();
+    ///
+    /// assert_eq!(cell.take(), Some(1));
+    /// assert_eq!(cell.get(), None);
+    /// #
+    /// # })
+    /// ```
+    pub fn take(&mut self) -> Option<T> {
+        *self.initialized.get_mut() = false;
+        self.value.get_mut().take()
+    }
+
+    /// Consumes the cell, returning the value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::OnceCell;
+    ///
+    /// let cell = OnceCell::new();
+    /// cell.set("hello").await.unwrap();
+    /// assert_eq!(cell.into_inner(), Some("hello"));
+    /// #
+    /// # })
+    /// ```
+    pub fn into_inner(self) -> Option<T> {
+        self.value.into_inner()
+    }
+
+    /// Writes the value. Must be called with the lock held while the cell is empty.
+    fn store(&self, value: T) -> &T {
+        let slot = unsafe { &mut *self.value.get() };
+        *slot = Some(value);
+        self.initialized.store(true, Ordering::Release);
+        slot.as_ref().unwrap()
+    }
+}
+
+impl<T> Default for OnceCell<T> {
+    fn default() -> OnceCell<T> {
+        OnceCell::new()
+    }
+}
+
+impl<T> From<T> for OnceCell<T> {
+    fn from(value: T) -> OnceCell<T> {
+        OnceCell {
+            initialized: AtomicBool::new(true),
+            lock: Mutex::new(()),
+            value: UnsafeCell::new(Some(value)),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OnceCell<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.get() {
+            Some(value) => f.debug_tuple("OnceCell").field(value).finish(),
+            None => f.write_str("OnceCell(<uninit>)"),
+        }
+    }
+}
+
+/// A value that is initialized with an async function on first access.
+///
+/// The initializer is run by the first task that calls [`get`]. Other tasks calling [`get`] in
+/// the meantime wait for it. If the initializer is cancelled, the next task runs it again, so it
+/// has to be a `Fn` rather than a `FnOnce`.
+///
+/// [`get`]: #method.get
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::Lazy;
+///
+/// let config = Lazy::new(|| async {
+///     // Load the config from somewhere.
+///     vec!["example.com", "example.org"]
+/// });
+///
+/// assert_eq!(config.get().await.len(), 2);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Lazy<T, F> {
+    cell: OnceCell<T>,
+    init: F,
+}
+
+impl<T, F> Lazy<T, F> {
+    /// Creates a new lazy value with the given initializer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Lazy;
+    ///
+    /// let lazy = Lazy::new(|| async { 92 });
+    /// ```
+    pub fn new<Fut>(init: F) -> Lazy<T, F>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        Lazy {
+            cell: OnceCell::new(),
+            init,
+        }
+    }
+
+    /// Returns the value, running the initializer if this is the first access.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Lazy;
+    ///
+    /// let lazy = Lazy::new(|| async { 92 });
+    /// assert_eq!(*lazy.get().await, 92);
+    /// #
+    /// # })
+    /// ```
+    pub async fn get<Fut>(&self) -> &T
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        self.cell.get_or_init(&self.init).await
+    }
+
+    /// Returns the value if it has been initialized already.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Lazy;
+    ///
+    /// let lazy = Lazy::new(|| async { 92 });
+    /// assert_eq!(lazy.try_get(), None);
+    ///
+    /// lazy.get().await;
+    /// assert_eq!(lazy.try_get(), Some(&92));
+    /// #
+    /// # })
+    /// ```
+    pub fn try_get(&self) -> Option<&T> {
+        self.cell.get()
+    }
+}
+
+impl<T: fmt::Debug, F> fmt::Debug for Lazy<T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lazy").field("cell", &self.cell).finish()
+    }
+}
@@ -0,0 +1,332 @@
+//! A channel for sending a single value between tasks.
+//!
+//! This is the cheapest way to hand a single result from one task to another, like the response
+//! to a request or the acknowledgement of a shutdown. The [`Receiver`] is a future that resolves
+//! to the value, or to an error if the [`Sender`] is dropped without sending anything.
+//!
+//! [`Receiver`]: struct.Receiver.html
+//! [`Sender`]: struct.Sender.html
+//!
+//! # Examples
+//!
+//! ```
+//! # async_std::task::block_on(async {
+//! #
+//! use async_std::sync::oneshot;
+//! use async_std::task;
+//!
+//! let (s, r) = oneshot::channel();
+//!
+//! task::spawn(async move {
+//!     s.send(42).unwrap();
+//! });
+//!
+//! assert_eq!(r.await, Ok(42));
+//! #
+//! # })
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use crate::future::Future;
+use crate::task::{Context, Poll, Waker};
+
+/// Creates a new oneshot channel.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::oneshot;
+///
+/// let (s, r) = oneshot::channel();
+/// s.send("done").unwrap();
+/// assert_eq!(r.await, Ok("done"));
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let inner = Arc::new(Inner {
+        state: Mutex::new(State {
+            value: None,
+            sender_alive: true,
+            receiver_alive: true,
+            receiver_waker: None,
+            sender_waker: None,
+        }),
+    });
+
+    let s = Sender {
+        inner: inner.clone(),
+    };
+    let r = Receiver { inner };
+    (s, r)
+}
+
+/// The sending side of a oneshot channel.
+///
+/// This type is created by the [`channel`] function. See its documentation for more details.
+///
+/// [`channel`]: fn.channel.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Sender<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Sends the value to the receiver.
+    ///
+    /// If the receiver has been dropped or closed, the value is returned back in the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::oneshot;
+    ///
+    /// let (s, r) = oneshot::channel();
+    /// drop(r);
+    /// assert_eq!(s.send(1), Err(1));
+    /// ```
+    pub fn send(self, value: T) -> Result<(), T> {
+        let mut state = self.inner.state.lock().unwrap();
+        if !state.receiver_alive {
+            return Err(value);
+        }
+
+        state.value = Some(value);
+        if let Some(w) = state.receiver_waker.take() {
+            w.wake();
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the receiver has been dropped or closed.
+    ///
+    /// A task computing a value can check this to stop early when nobody is interested in the
+    /// result anymore.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::oneshot;
+    ///
+    /// let (s, r) = oneshot::channel::<i32>();
+    /// assert!(!s.is_closed());
+    ///
+    /// drop(r);
+    /// assert!(s.is_closed());
+    /// ```
+    pub fn is_closed(&self) -> bool {
+        !self.inner.state.lock().unwrap().receiver_alive
+    }
+
+    /// Waits until the receiver is dropped or closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::oneshot;
+    /// use async_std::task;
+    ///
+    /// let (s, r) = oneshot::channel::<i32>();
+    /// task::spawn(async move { drop(r) });
+    ///
+    /// s.closed().await;
+    /// assert!(s.is_closed());
+    /// #
+    /// # })
+    /// ```
+    pub async fn closed(&self) {
+        crate::future::poll_fn(|cx| {
+            let mut state = self.inner.state.lock().unwrap();
+            if state.receiver_alive {
+                state.sender_waker = Some(cx.waker().clone());
+                Poll::Pending
+            } else {
+                Poll::Ready(())
+            }
+        })
+        .await
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.sender_alive = false;
+        if let Some(w) = state.receiver_waker.take() {
+            w.wake();
+        }
+    }
+}
+
+impl<T> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Sender { .. }")
+    }
+}
+
+/// The receiving side of a oneshot channel.
+///
+/// This type is a future that resolves to the sent value. If the [`Sender`] is dropped without
+/// sending a value, it resolves to a [`RecvError`].
+///
+/// This type is created by the [`channel`] function. See its documentation for more details.
+///
+/// [`Sender`]: struct.Sender.html
+/// [`RecvError`]: struct.RecvError.html
+/// [`channel`]: fn.channel.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Receiver<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> Receiver<T> {
+    /// Attempts to receive the value without waiting.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::oneshot::{self, TryRecvError};
+    ///
+    /// let (s, mut r) = oneshot::channel();
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Empty));
+    ///
+    /// s.send(1).unwrap();
+    /// assert_eq!(r.try_recv(), Ok(1));
+    /// assert_eq!(r.try_recv(), Err(TryRecvError::Disconnected));
+    /// ```
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let mut state = self.inner.state.lock().unwrap();
+        match state.value.take() {
+            Some(value) => Ok(value),
+            None if state.sender_alive => Err(TryRecvError::Empty),
+            None => Err(TryRecvError::Disconnected),
+        }
+    }
+
+    /// Closes the channel without dropping the receiver.
+    ///
+    /// Sending fails from now on, but a value that was already sent can still be received.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::oneshot;
+    ///
+    /// let (s, mut r) = oneshot::channel();
+    /// r.close();
+    /// assert_eq!(s.send(1), Err(1));
+    /// ```
+    pub fn close(&mut self) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.close();
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = Result<T, RecvError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.inner.state.lock().unwrap();
+        match state.value.take() {
+            Some(value) => Poll::Ready(Ok(value)),
+            None if !state.sender_alive => Poll::Ready(Err(RecvError)),
+            None => {
+                state.receiver_waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.close();
+    }
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Receiver { .. }")
+    }
+}
+
+/// An error returned when the [`Sender`] is dropped without sending a value.
+///
+/// [`Sender`]: struct.Sender.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RecvError;
+
+impl Error for RecvError {}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "sender dropped without sending a value".fmt(f)
+    }
+}
+
+/// An error returned from the [`Receiver::try_recv`] method.
+///
+/// [`Receiver::try_recv`]: struct.Receiver.html#method.try_recv
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TryRecvError {
+    /// No value has been sent yet.
+    Empty,
+
+    /// The sender was dropped without sending a value, or the value was already received.
+    Disconnected,
+}
+
+impl Error for TryRecvError {}
+
+impl fmt::Display for TryRecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TryRecvError::Empty => "no value has been sent yet".fmt(f),
+            TryRecvError::Disconnected => "sender dropped without sending a value".fmt(f),
+        }
+    }
+}
+
+struct Inner<T> {
+    state: Mutex<State<T>>,
+}
+
+struct State<T> {
+    /// The sent value, until it is received.
+    value: Option<T>,
+
+    sender_alive: bool,
+    receiver_alive: bool,
+
+    /// The waker of the task waiting for the value.
+    receiver_waker: Option<Waker>,
+
+    /// The waker of the task waiting for the receiver to close.
+    sender_waker: Option<Waker>,
+}
+
+impl<T> State<T> {
+    fn close(&mut self) {
+        self.receiver_alive = false;
+        if let Some(w) = self.sender_waker.take() {
+            w.wake();
+        }
+    }
+}
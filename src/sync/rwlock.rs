@@ -7,6 +7,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use slab::Slab;
 
 use crate::future::Future;
+use crate::sync::{Mutex, MutexGuard};
 use crate::task::{Context, Poll, Waker};
 
 /// Set if a write lock is held.
@@ -30,6 +31,13 @@ const READ_COUNT_MASK: usize = !(ONE_READ - 1);
 ///
 /// [`std::sync::RwLock`]: https://doc.rust-lang.org/std/sync/struct.RwLock.html
 ///
+/// # Writer preference
+///
+/// Once a task is waiting for a write lock, new read locks can't be acquired until the write is
+/// done. This keeps a steady stream of reads from starving writes. Writes are served in the
+/// order they were requested. Note that it also means a task that already holds a read lock and
+/// tries to acquire another one may deadlock if a write is requested in between.
+///
 /// # Examples
 ///
 /// ```
@@ -57,6 +65,7 @@ pub struct RwLock<T> {
     state: AtomicUsize,
     reads: std::sync::Mutex<Slab<Option<Waker>>>,
     writes: std::sync::Mutex<Slab<Option<Waker>>>,
+    writer: Mutex<()>,
     value: UnsafeCell<T>,
 }
 
@@ -78,6 +87,7 @@ impl<T> RwLock<T> {
             state: AtomicUsize::new(0),
             reads: std::sync::Mutex::new(Slab::new()),
             writes: std::sync::Mutex::new(Slab::new()),
+            writer: Mutex::new(()),
             value: UnsafeCell::new(t),
         }
     }
@@ -184,7 +194,6 @@ impl<T> RwLock<T> {
                             if let Some((_, opt_waker)) = writes.iter_mut().next() {
                                 if let Some(w) = opt_waker.take() {
                                     w.wake();
-                                    return;
                                 }
                             }
                         }
@@ -204,9 +213,11 @@ impl<T> RwLock<T> {
     /// Attempts to acquire a read lock.
     ///
     /// If a read lock could not be acquired at this time, then [`None`] is returned. Otherwise, a
-    /// guard is returned that releases the lock when dropped.
+    /// guard is returned that releases the lock when dropped. Like [`read`], this method fails
+    /// while a write is waiting for the lock.
     ///
     /// [`None`]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
+    /// [`read`]: #method.read
     ///
     /// # Examples
     ///
@@ -228,8 +239,9 @@ impl<T> RwLock<T> {
         let mut state = self.state.load(Ordering::Acquire);
 
         loop {
-            // If a write lock is currently held, then a read lock cannot be acquired.
-            if state & WRITE_LOCK != 0 {
+            // If a write lock is currently held or a write is waiting for one, then a read lock
+            // cannot be acquired.
+            if state & (WRITE_LOCK | BLOCKED_WRITES) != 0 {
                 return None;
             }
 
@@ -267,6 +279,18 @@ impl<T> RwLock<T> {
     /// # })
     /// ```
     pub async fn write(&self) -> RwLockWriteGuard<'_, T> {
+        let writer = self.writer.lock().await;
+        self.lock_write_state().await;
+        RwLockWriteGuard {
+            lock: self,
+            _writer: writer,
+        }
+    }
+
+    /// Sets the write lock in the state once all reads are released.
+    ///
+    /// This must only be called while holding the writer mutex.
+    async fn lock_write_state(&self) {
         pub struct LockFuture<'a, T> {
             lock: &'a RwLock<T>,
             opt_key: Option<usize>,
@@ -274,49 +298,45 @@ impl<T> RwLock<T> {
         }
 
         impl<'a, T> Future for LockFuture<'a, T> {
-            type Output = RwLockWriteGuard<'a, T>;
+            type Output = ();
 
             fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-                match self.lock.try_write() {
-                    Some(guard) => {
-                        self.acquired = true;
-                        Poll::Ready(guard)
-                    }
-                    None => {
-                        let mut writes = self.lock.writes.lock().unwrap();
+                if self.lock.try_write_state() {
+                    self.acquired = true;
+                    Poll::Ready(())
+                } else {
+                    let mut writes = self.lock.writes.lock().unwrap();
 
-                        // Register the current task.
-                        match self.opt_key {
-                            None => {
-                                // Insert a new entry into the list of blocked writes.
-                                let w = cx.waker().clone();
-                                let key = writes.insert(Some(w));
-                                self.opt_key = Some(key);
+                    // Register the current task.
+                    match self.opt_key {
+                        None => {
+                            // Insert a new entry into the list of blocked writes.
+                            let w = cx.waker().clone();
+                            let key = writes.insert(Some(w));
+                            self.opt_key = Some(key);
 
-                                if writes.len() == 1 {
-                                    self.lock.state.fetch_or(BLOCKED_WRITES, Ordering::Relaxed);
-                                }
-                            }
-                            Some(key) => {
-                                // There is already an entry in the list of blocked writes. Just
-                                // reset the waker if it was removed.
-                                if writes[key].is_none() {
-                                    let w = cx.waker().clone();
-                                    writes[key] = Some(w);
-                                }
+                            if writes.len() == 1 {
+                                self.lock.state.fetch_or(BLOCKED_WRITES, Ordering::Relaxed);
                             }
                         }
-
-                        // Try locking again because it's possible the lock got unlocked just
-                        // before the current task was registered as a blocked task.
-                        match self.lock.try_write() {
-                            Some(guard) => {
-                                self.acquired = true;
-                                Poll::Ready(guard)
+                        Some(key) => {
+                            // There is already an entry in the list of blocked writes. Just
+                            // reset the waker if it was removed.
+                            if writes[key].is_none() {
+                                let w = cx.waker().clone();
+                                writes[key] = Some(w);
                             }
-                            None => Poll::Pending,
                         }
                     }
+
+                    // Try locking again because it's possible the lock got unlocked just
+                    // before the current task was registered as a blocked task.
+                    if self.lock.try_write_state() {
+                        self.acquired = true;
+                        Poll::Ready(())
+                    } else {
+                        Poll::Pending
+                    }
                 }
             }
         }
@@ -325,7 +345,7 @@ impl<T> RwLock<T> {
             fn drop(&mut self) {
                 if let Some(key) = self.opt_key {
                     let mut writes = self.lock.writes.lock().unwrap();
-                    let opt_waker = writes.remove(key);
+                    writes.remove(key);
 
                     if writes.is_empty() {
                         self.lock
@@ -333,22 +353,15 @@ impl<T> RwLock<T> {
                             .fetch_and(!BLOCKED_WRITES, Ordering::Relaxed);
                     }
 
-                    if opt_waker.is_none() && !self.acquired {
-                        // We were awoken but didn't acquire the lock. Wake up another write.
-                        if let Some((_, opt_waker)) = writes.iter_mut().next() {
-                            if let Some(w) = opt_waker.take() {
-                                w.wake();
-                                return;
-                            }
-                        }
+                    if !self.acquired && writes.is_empty() {
                         drop(writes);
 
-                        // There are no blocked writes. Wake a blocked read instead.
+                        // Reads may have been held back because we were waiting. Wake one of
+                        // them up, which in turn wakes up the others.
                         let mut reads = self.lock.reads.lock().unwrap();
                         if let Some((_, opt_waker)) = reads.iter_mut().next() {
                             if let Some(w) = opt_waker.take() {
                                 w.wake();
-                                return;
                             }
                         }
                     }
@@ -388,12 +401,27 @@ impl<T> RwLock<T> {
     /// # })
     /// ```
     pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        let writer = self.writer.try_lock()?;
+        if self.try_write_state() {
+            Some(RwLockWriteGuard {
+                lock: self,
+                _writer: writer,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Attempts to set the write lock in the state.
+    ///
+    /// This must only be called while holding the writer mutex.
+    fn try_write_state(&self) -> bool {
         let mut state = self.state.load(Ordering::Acquire);
 
         loop {
             // If any kind of lock is currently held, then a write lock cannot be acquired.
             if state & (WRITE_LOCK | READ_COUNT_MASK) != 0 {
-                return None;
+                return false;
             }
 
             // Set the write lock.
@@ -403,12 +431,82 @@ impl<T> RwLock<T> {
                 Ordering::AcqRel,
                 Ordering::Acquire,
             ) {
-                Ok(_) => return Some(RwLockWriteGuard(self)),
+                Ok(_) => return true,
                 Err(s) => state = s,
             }
         }
     }
 
+    /// Acquires an upgradable read lock.
+    ///
+    /// An upgradable read lock can be held alongside regular read locks, but excludes writes and
+    /// other upgradable read locks. It can later be [upgraded] into a write lock without letting
+    /// any other write happen in between, which makes it useful for checking a condition before
+    /// deciding to modify the data.
+    ///
+    /// Returns a guard that releases the lock when dropped.
+    ///
+    /// [upgraded]: struct.RwLockUpgradableReadGuard.html#method.upgrade
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{RwLock, RwLockUpgradableReadGuard};
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let n = lock.upgradable_read().await;
+    /// assert!(lock.try_read().is_some());
+    /// assert!(lock.try_write().is_none());
+    ///
+    /// if *n == 1 {
+    ///     let mut n = RwLockUpgradableReadGuard::upgrade(n).await;
+    ///     *n = 2;
+    /// }
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn upgradable_read(&self) -> RwLockUpgradableReadGuard<'_, T> {
+        let writer = self.writer.lock().await;
+
+        // Reads are only held back by writes, which are excluded by the writer mutex.
+        let read = self.read().await;
+        RwLockUpgradableReadGuard { read, writer }
+    }
+
+    /// Attempts to acquire an upgradable read lock.
+    ///
+    /// If an upgradable read lock could not be acquired at this time, then [`None`] is returned.
+    /// Otherwise, a guard is returned that releases the lock when dropped.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::RwLock;
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let n = lock.upgradable_read().await;
+    /// assert!(lock.try_upgradable_read().is_none());
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn try_upgradable_read(&self) -> Option<RwLockUpgradableReadGuard<'_, T>> {
+        let writer = self.writer.try_lock()?;
+        let read = self.try_read()?;
+        Some(RwLockUpgradableReadGuard { read, writer })
+    }
+
     /// Consumes the lock, returning the underlying data.
     ///
     /// # Examples
@@ -522,27 +620,26 @@ impl<T> Deref for RwLockReadGuard<'_, T> {
 }
 
 /// A guard that releases the write lock when dropped.
-pub struct RwLockWriteGuard<'a, T>(&'a RwLock<T>);
+pub struct RwLockWriteGuard<'a, T> {
+    lock: &'a RwLock<T>,
+
+    /// Keeps other writes out until this guard is dropped.
+    _writer: MutexGuard<'a, ()>,
+}
 
 unsafe impl<T: Send> Send for RwLockWriteGuard<'_, T> {}
 unsafe impl<T: Sync> Sync for RwLockWriteGuard<'_, T> {}
 
 impl<T> Drop for RwLockWriteGuard<'_, T> {
     fn drop(&mut self) {
-        let state = self.0.state.fetch_and(!WRITE_LOCK, Ordering::AcqRel);
+        let state = self.lock.state.fetch_and(!WRITE_LOCK, Ordering::AcqRel);
 
-        let mut guard = None;
-
-        // Check if there are any blocked reads or writes.
+        // Wake up a blocked read, which in turn wakes up the others. Blocked writes are woken up
+        // by the writer mutex once this guard is gone.
         if state & BLOCKED_READS != 0 {
-            guard = Some(self.0.reads.lock().unwrap());
-        } else if state & BLOCKED_WRITES != 0 {
-            guard = Some(self.0.writes.lock().unwrap());
-        }
+            let mut reads = self.lock.reads.lock().unwrap();
 
-        // Wake up a single blocked task.
-        if let Some(mut guard) = guard {
-            if let Some((_, opt_waker)) = guard.iter_mut().next() {
+            if let Some((_, opt_waker)) = reads.iter_mut().next() {
                 // If there is no waker in this entry, that means it was already woken.
                 if let Some(w) = opt_waker.take() {
                     w.wake();
@@ -568,12 +665,113 @@ impl<T> Deref for RwLockWriteGuard<'_, T> {
     type Target = T;
 
     fn deref(&self) -> &T {
-        unsafe { &*self.0.value.get() }
+        unsafe { &*self.lock.value.get() }
     }
 }
 
 impl<T> DerefMut for RwLockWriteGuard<'_, T> {
     fn deref_mut(&mut self) -> &mut T {
-        unsafe { &mut *self.0.value.get() }
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+/// A guard that releases the upgradable read lock when dropped.
+///
+/// This guard is created by [`RwLock::upgradable_read`].
+///
+/// [`RwLock::upgradable_read`]: struct.RwLock.html#method.upgradable_read
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct RwLockUpgradableReadGuard<'a, T> {
+    read: RwLockReadGuard<'a, T>,
+
+    /// Keeps writes and other upgradable reads out until this guard is dropped.
+    writer: MutexGuard<'a, ()>,
+}
+
+#[cfg(feature = "unstable")]
+impl<'a, T> RwLockUpgradableReadGuard<'a, T> {
+    /// Upgrades the lock into a write lock.
+    ///
+    /// Waits until all regular read locks are released. No other write can happen in between,
+    /// so anything read through this guard is still up to date once the write lock is acquired.
+    /// New read locks can't be acquired while this method is waiting.
+    ///
+    /// If the returned future is dropped before completing, the lock is released.
+    ///
+    /// This is an associated function so that it doesn't shadow methods on the protected data.
+    /// Call it as `RwLockUpgradableReadGuard::upgrade(guard)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{RwLock, RwLockUpgradableReadGuard};
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let n = lock.upgradable_read().await;
+    /// let mut n = RwLockUpgradableReadGuard::upgrade(n).await;
+    /// *n = 2;
+    /// #
+    /// # })
+    /// ```
+    pub async fn upgrade(guard: Self) -> RwLockWriteGuard<'a, T> {
+        let RwLockUpgradableReadGuard { read, writer } = guard;
+        let lock = read.0;
+        drop(read);
+
+        lock.lock_write_state().await;
+        RwLockWriteGuard {
+            lock,
+            _writer: writer,
+        }
+    }
+
+    /// Downgrades the lock into a regular read lock.
+    ///
+    /// This lets writes and other upgradable reads proceed once the remaining reads are released.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{RwLock, RwLockUpgradableReadGuard};
+    ///
+    /// let lock = RwLock::new(1);
+    ///
+    /// let n = lock.upgradable_read().await;
+    /// let n = RwLockUpgradableReadGuard::downgrade(n);
+    /// assert!(lock.try_upgradable_read().is_some());
+    /// #
+    /// # })
+    /// ```
+    pub fn downgrade(guard: Self) -> RwLockReadGuard<'a, T> {
+        guard.read
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: fmt::Debug> fmt::Debug for RwLockUpgradableReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T: fmt::Display> fmt::Display for RwLockUpgradableReadGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+#[cfg(feature = "unstable")]
+impl<T> Deref for RwLockUpgradableReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.read
     }
 }
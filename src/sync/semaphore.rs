@@ -0,0 +1,502 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use slab::Slab;
+
+use crate::future::Future;
+use crate::task::{Context, Poll, Waker};
+
+/// A counting semaphore.
+///
+/// A semaphore holds a number of permits. Tasks acquire permits before doing some work and
+/// release them when done, which limits how many tasks can do that work at the same time. This
+/// is useful for capping the number of open connections or in-flight requests.
+///
+/// Permits are released when the returned guard is dropped. Use [`acquire_owned`] to get a guard
+/// that can be moved into a spawned task.
+///
+/// # Fairness
+///
+/// Tasks waiting for permits are served in the order they called an acquire method. A task
+/// asking for many permits at once holds back the tasks behind it until enough permits are
+/// available, so it can't be starved by a stream of smaller requests.
+///
+/// [`acquire_owned`]: #method.acquire_owned
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::sync::{Arc, Semaphore};
+/// use async_std::task;
+///
+/// // At most two tasks do work at the same time.
+/// let semaphore = Arc::new(Semaphore::new(2));
+/// let mut tasks = vec![];
+///
+/// for i in 0..10 {
+///     let permit = semaphore.clone().acquire_owned().await;
+///     tasks.push(task::spawn(async move {
+///         println!("working on {}", i);
+///         drop(permit);
+///     }));
+/// }
+///
+/// for t in tasks {
+///     t.await;
+/// }
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Semaphore {
+    state: std::sync::Mutex<State>,
+}
+
+struct State {
+    /// The number of available permits.
+    permits: usize,
+
+    /// Entries for blocked tasks.
+    entries: Slab<Entry>,
+
+    /// Keys of entries that are still waiting, in the order the tasks arrived.
+    queue: VecDeque<usize>,
+}
+
+/// An entry for a blocked task.
+struct Entry {
+    /// The waker of the blocked task.
+    waker: Waker,
+
+    /// The number of permits the task asked for.
+    needed: usize,
+
+    /// Set when the permits have been handed over to the task.
+    granted: bool,
+}
+
+impl Semaphore {
+    /// Creates a new semaphore with the given number of permits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(10);
+    /// ```
+    pub fn new(permits: usize) -> Semaphore {
+        Semaphore {
+            state: std::sync::Mutex::new(State {
+                permits,
+                entries: Slab::new(),
+                queue: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// Returns the number of permits that can be acquired right now.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(3);
+    /// let _permit = semaphore.try_acquire().unwrap();
+    /// assert_eq!(semaphore.available_permits(), 2);
+    /// ```
+    pub fn available_permits(&self) -> usize {
+        self.state.lock().unwrap().permits
+    }
+
+    /// Adds permits to the semaphore, waking up tasks that can now acquire them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(0);
+    /// semaphore.add_permits(2);
+    /// assert_eq!(semaphore.available_permits(), 2);
+    /// ```
+    pub fn add_permits(&self, n: usize) {
+        self.state.lock().unwrap().release(n);
+    }
+
+    /// Acquires a permit.
+    ///
+    /// Returns a guard that releases the permit when dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    ///
+    /// let permit = semaphore.acquire().await;
+    /// assert!(semaphore.try_acquire().is_none());
+    ///
+    /// drop(permit);
+    /// assert!(semaphore.try_acquire().is_some());
+    /// #
+    /// # })
+    /// ```
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.acquire_many(1).await
+    }
+
+    /// Acquires a number of permits at once.
+    ///
+    /// Returns a guard that releases all of them when dropped. Asking for more permits than the
+    /// semaphore will ever have waits forever.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(5);
+    ///
+    /// let permit = semaphore.acquire_many(3).await;
+    /// assert_eq!(permit.count(), 3);
+    /// assert_eq!(semaphore.available_permits(), 2);
+    /// #
+    /// # })
+    /// ```
+    pub async fn acquire_many(&self, n: usize) -> SemaphorePermit<'_> {
+        Acquire {
+            semaphore: self,
+            needed: n,
+            opt_key: None,
+        }
+        .await;
+        SemaphorePermit {
+            semaphore: self,
+            count: n,
+        }
+    }
+
+    /// Attempts to acquire a permit.
+    ///
+    /// If a permit could not be acquired at this time, then [`None`] is returned. Otherwise, a
+    /// guard is returned that releases the permit when dropped. This method doesn't jump the
+    /// queue: it fails while other tasks are waiting for permits.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(1);
+    ///
+    /// let permit = semaphore.try_acquire().unwrap();
+    /// assert!(semaphore.try_acquire().is_none());
+    /// ```
+    pub fn try_acquire(&self) -> Option<SemaphorePermit<'_>> {
+        self.try_acquire_many(1)
+    }
+
+    /// Attempts to acquire a number of permits at once.
+    ///
+    /// If the permits could not be acquired at this time, then [`None`] is returned. Otherwise, a
+    /// guard is returned that releases all of them when dropped.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::Semaphore;
+    ///
+    /// let semaphore = Semaphore::new(2);
+    ///
+    /// assert!(semaphore.try_acquire_many(3).is_none());
+    /// assert!(semaphore.try_acquire_many(2).is_some());
+    /// ```
+    pub fn try_acquire_many(&self, n: usize) -> Option<SemaphorePermit<'_>> {
+        if self.state.lock().unwrap().try_take(n) {
+            Some(SemaphorePermit {
+                semaphore: self,
+                count: n,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Acquires a permit that keeps the semaphore alive.
+    ///
+    /// Unlike [`acquire`], the returned guard doesn't borrow the semaphore, so it can be moved
+    /// into a spawned task.
+    ///
+    /// [`acquire`]: #method.acquire
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::{Arc, Semaphore};
+    /// use async_std::task;
+    ///
+    /// let semaphore = Arc::new(Semaphore::new(1));
+    /// let permit = semaphore.clone().acquire_owned().await;
+    ///
+    /// task::spawn(async move {
+    ///     // The permit is released when the task is done.
+    ///     drop(permit);
+    /// })
+    /// .await;
+    ///
+    /// assert_eq!(semaphore.available_permits(), 1);
+    /// #
+    /// # })
+    /// ```
+    pub async fn acquire_owned(self: Arc<Self>) -> OwnedSemaphorePermit {
+        Acquire {
+            semaphore: &self,
+            needed: 1,
+            opt_key: None,
+        }
+        .await;
+        OwnedSemaphorePermit {
+            semaphore: self,
+            count: 1,
+        }
+    }
+
+    /// Attempts to acquire a permit that keeps the semaphore alive.
+    ///
+    /// If a permit could not be acquired at this time, then [`None`] is returned.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/std/option/enum.Option.html#variant.None
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::{Arc, Semaphore};
+    ///
+    /// let semaphore = Arc::new(Semaphore::new(1));
+    ///
+    /// let permit = semaphore.clone().try_acquire_owned().unwrap();
+    /// assert!(semaphore.clone().try_acquire_owned().is_none());
+    /// ```
+    pub fn try_acquire_owned(self: Arc<Self>) -> Option<OwnedSemaphorePermit> {
+        if self.state.lock().unwrap().try_take(1) {
+            Some(OwnedSemaphorePermit {
+                semaphore: self,
+                count: 1,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Debug for Semaphore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Semaphore")
+            .field("permits", &self.available_permits())
+            .finish()
+    }
+}
+
+/// A guard that releases permits when dropped.
+///
+/// This guard is created by [`Semaphore::acquire`] and similar methods.
+///
+/// [`Semaphore::acquire`]: struct.Semaphore.html#method.acquire
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[must_use = "permits are released immediately if the guard is not held"]
+pub struct SemaphorePermit<'a> {
+    semaphore: &'a Semaphore,
+    count: usize,
+}
+
+impl SemaphorePermit<'_> {
+    /// Returns the number of permits held by this guard.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Drops the guard without releasing its permits.
+    ///
+    /// This permanently lowers the number of permits in the semaphore.
+    pub fn forget(mut self) {
+        self.count = 0;
+    }
+}
+
+impl Drop for SemaphorePermit<'_> {
+    fn drop(&mut self) {
+        if self.count > 0 {
+            self.semaphore.add_permits(self.count);
+        }
+    }
+}
+
+impl fmt::Debug for SemaphorePermit<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SemaphorePermit")
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+/// A guard that releases a permit when dropped and keeps the semaphore alive.
+///
+/// This guard is created by [`Semaphore::acquire_owned`].
+///
+/// [`Semaphore::acquire_owned`]: struct.Semaphore.html#method.acquire_owned
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[must_use = "permits are released immediately if the guard is not held"]
+pub struct OwnedSemaphorePermit {
+    semaphore: Arc<Semaphore>,
+    count: usize,
+}
+
+impl OwnedSemaphorePermit {
+    /// Returns the number of permits held by this guard.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Drops the guard without releasing its permits.
+    ///
+    /// This permanently lowers the number of permits in the semaphore.
+    pub fn forget(mut self) {
+        self.count = 0;
+    }
+}
+
+impl Drop for OwnedSemaphorePermit {
+    fn drop(&mut self) {
+        if self.count > 0 {
+            self.semaphore.add_permits(self.count);
+        }
+    }
+}
+
+impl fmt::Debug for OwnedSemaphorePermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedSemaphorePermit")
+            .field("count", &self.count)
+            .finish()
+    }
+}
+
+/// A future that waits until a number of permits is handed over.
+struct Acquire<'a> {
+    semaphore: &'a Semaphore,
+    needed: usize,
+    opt_key: Option<usize>,
+}
+
+impl Future for Acquire<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.semaphore.state.lock().unwrap();
+
+        match self.opt_key {
+            None => {
+                if state.try_take(self.needed) {
+                    return Poll::Ready(());
+                }
+
+                // Join the back of the queue.
+                let key = state.entries.insert(Entry {
+                    waker: cx.waker().clone(),
+                    needed: self.needed,
+                    granted: false,
+                });
+                state.queue.push_back(key);
+                drop(state);
+                self.opt_key = Some(key);
+                Poll::Pending
+            }
+            Some(key) => {
+                if state.entries[key].granted {
+                    state.entries.remove(key);
+                    drop(state);
+                    self.opt_key = None;
+                    Poll::Ready(())
+                } else {
+                    // Keep waiting, but make sure to wake the current task.
+                    let w = cx.waker();
+                    if !state.entries[key].waker.will_wake(w) {
+                        state.entries[key].waker = w.clone();
+                    }
+                    Poll::Pending
+                }
+            }
+        }
+    }
+}
+
+impl Drop for Acquire<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.opt_key {
+            let mut state = self.semaphore.state.lock().unwrap();
+            let entry = state.entries.remove(key);
+
+            if entry.granted {
+                // The permits were handed over to us, but we don't want them anymore.
+                state.release(entry.needed);
+            } else {
+                // Tasks queued up behind us may be able to proceed now.
+                state.queue.retain(|&k| k != key);
+                state.grant_waiting();
+            }
+        }
+    }
+}
+
+impl State {
+    /// Takes permits if nobody is waiting and enough of them are available.
+    fn try_take(&mut self, n: usize) -> bool {
+        if self.queue.is_empty() && self.permits >= n {
+            self.permits -= n;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns permits and hands them over to waiting tasks.
+    fn release(&mut self, n: usize) {
+        self.permits += n;
+        self.grant_waiting();
+    }
+
+    /// Hands permits over to tasks at the front of the queue for as long as possible.
+    fn grant_waiting(&mut self) {
+        while let Some(&key) = self.queue.front() {
+            let entry = &mut self.entries[key];
+            if entry.needed > self.permits {
+                break;
+            }
+
+            self.permits -= entry.needed;
+            entry.granted = true;
+            entry.waker.wake_by_ref();
+            self.queue.pop_front();
+        }
+    }
+}
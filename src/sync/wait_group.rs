@@ -0,0 +1,213 @@
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+use slab::Slab;
+
+use crate::future::Future;
+use crate::task::{Context, Poll, Waker};
+
+/// Waits for a group of tasks to finish.
+///
+/// Each clone of a wait group counts as one piece of outstanding work. Clones are moved into
+/// tasks and dropped when the work is done, and [`wait`] completes once all other clones are
+/// gone. This way, waiting for all outstanding work doesn't require collecting [`JoinHandle`]s.
+///
+/// [`wait`]: #method.wait
+/// [`JoinHandle`]: ../task/struct.JoinHandle.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// use async_std::sync::{Arc, WaitGroup};
+/// use async_std::task;
+///
+/// let wg = WaitGroup::new();
+/// let done = Arc::new(AtomicUsize::new(0));
+///
+/// for _ in 0..4 {
+///     let wg = wg.clone();
+///     let done = done.clone();
+///     task::spawn(async move {
+///         done.fetch_add(1, Ordering::SeqCst);
+///         drop(wg);
+///     });
+/// }
+///
+/// wg.wait().await;
+/// assert_eq!(done.load(Ordering::SeqCst), 4);
+/// #
+/// # })
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct WaitGroup {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    state: Mutex<State>,
+}
+
+struct State {
+    /// The number of wait groups that are still alive.
+    count: usize,
+
+    /// Wakers of tasks waiting for the count to drop to zero.
+    blocked: Slab<Option<Waker>>,
+}
+
+impl WaitGroup {
+    /// Creates a new wait group.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::WaitGroup;
+    ///
+    /// let wg = WaitGroup::new();
+    /// ```
+    pub fn new() -> WaitGroup {
+        WaitGroup {
+            inner: Arc::new(Inner {
+                state: Mutex::new(State {
+                    count: 1,
+                    blocked: Slab::new(),
+                }),
+            }),
+        }
+    }
+
+    /// Drops this wait group and waits until all its clones are dropped too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::WaitGroup;
+    /// use async_std::task;
+    ///
+    /// let wg = WaitGroup::new();
+    /// let wg2 = wg.clone();
+    ///
+    /// task::spawn(async move {
+    ///     // Do some work, then signal that it is done.
+    ///     drop(wg2);
+    /// });
+    ///
+    /// wg.wait().await;
+    /// #
+    /// # })
+    /// ```
+    pub async fn wait(self) {
+        let inner = self.inner.clone();
+        drop(self);
+
+        Wait {
+            inner,
+            opt_key: None,
+        }
+        .await
+    }
+
+    /// Returns the number of wait groups that are still alive, including this one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::WaitGroup;
+    ///
+    /// let wg = WaitGroup::new();
+    /// let wg2 = wg.clone();
+    /// assert_eq!(wg.count(), 2);
+    ///
+    /// drop(wg2);
+    /// assert_eq!(wg.count(), 1);
+    /// ```
+    pub fn count(&self) -> usize {
+        self.inner.state.lock().unwrap().count
+    }
+}
+
+impl Clone for WaitGroup {
+    fn clone(&self) -> WaitGroup {
+        self.inner.state.lock().unwrap().count += 1;
+        WaitGroup {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl Drop for WaitGroup {
+    fn drop(&mut self) {
+        let mut state = self.inner.state.lock().unwrap();
+        state.count -= 1;
+
+        if state.count == 0 {
+            for (_, opt_waker) in state.blocked.iter_mut() {
+                if let Some(w) = opt_waker.take() {
+                    w.wake();
+                }
+            }
+        }
+    }
+}
+
+impl Default for WaitGroup {
+    fn default() -> WaitGroup {
+        WaitGroup::new()
+    }
+}
+
+impl fmt::Debug for WaitGroup {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WaitGroup")
+            .field("count", &self.count())
+            .finish()
+    }
+}
+
+/// A future that completes once the count drops to zero.
+struct Wait {
+    inner: Arc<Inner>,
+    opt_key: Option<usize>,
+}
+
+impl Future for Wait {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let Wait { inner, opt_key } = &mut *self;
+        let mut state = inner.state.lock().unwrap();
+
+        if state.count == 0 {
+            if let Some(key) = opt_key.take() {
+                state.blocked.remove(key);
+            }
+            return Poll::Ready(());
+        }
+
+        let w = cx.waker();
+        match *opt_key {
+            None => *opt_key = Some(state.blocked.insert(Some(w.clone()))),
+            Some(key) => match &mut state.blocked[key] {
+                Some(old) if old.will_wake(w) => {}
+                opt_waker => *opt_waker = Some(w.clone()),
+            },
+        }
+        Poll::Pending
+    }
+}
+
+impl Drop for Wait {
+    fn drop(&mut self) {
+        if let Some(key) = self.opt_key {
+            self.inner.state.lock().unwrap().blocked.remove(key);
+        }
+    }
+}
@@ -0,0 +1,421 @@
+//! A channel that holds the latest value, for state shared across tasks.
+//!
+//! Unlike other channels, a watch channel always holds exactly one value. Sending replaces it,
+//! and receivers can read the current value at any time or wait until it changes. Receivers that
+//! don't keep up simply miss intermediate values, and always read the most recent one.
+//!
+//! This is useful for configuration that can be reloaded, or for readiness flags.
+//!
+//! # Examples
+//!
+//! ```
+//! # async_std::task::block_on(async {
+//! #
+//! use async_std::sync::watch;
+//! use async_std::task;
+//!
+//! let (s, mut r) = watch::channel("starting");
+//!
+//! task::spawn(async move {
+//!     s.send("ready");
+//! });
+//!
+//! while *r.borrow() != "ready" {
+//!     r.changed().await.unwrap();
+//! }
+//! #
+//! # })
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, RwLock, RwLockReadGuard};
+
+use slab::Slab;
+
+use crate::task::{Poll, Waker};
+
+/// Creates a watch channel holding the initial value.
+///
+/// # Examples
+///
+/// ```
+/// use async_std::sync::watch;
+///
+/// let (s, r) = watch::channel(1);
+/// assert_eq!(*r.borrow(), 1);
+///
+/// s.send(2);
+/// assert_eq!(*r.borrow(), 2);
+/// ```
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub fn channel<T>(init: T) -> (Sender<T>, Receiver<T>) {
+    let mut receivers = Slab::new();
+    let key = receivers.insert(None);
+
+    let shared = Arc::new(Shared {
+        value: RwLock::new(init),
+        state: Mutex::new(State {
+            version: 0,
+            sender_alive: true,
+            receivers,
+        }),
+    });
+
+    let s = Sender {
+        shared: shared.clone(),
+    };
+    let r = Receiver {
+        shared,
+        version: 0,
+        key,
+    };
+    (s, r)
+}
+
+/// The sending side of a watch channel.
+///
+/// This type is created by the [`channel`] function. See its documentation for more details.
+///
+/// [`channel`]: fn.channel.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> Sender<T> {
+    /// Replaces the value and notifies all receivers.
+    ///
+    /// The value is stored even if there are no receivers at the moment, so receivers created
+    /// later with [`subscribe`] see it.
+    ///
+    /// [`subscribe`]: #method.subscribe
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, r) = watch::channel(0);
+    /// s.send(1);
+    /// assert_eq!(*r.borrow(), 1);
+    /// ```
+    pub fn send(&self, value: T) {
+        *self.shared.value.write().unwrap() = value;
+
+        let mut state = self.shared.state.lock().unwrap();
+        state.version += 1;
+        state.wake_receivers();
+    }
+
+    /// Returns a reference to the current value.
+    ///
+    /// The value cannot be replaced while the reference is alive, so it should not be held for
+    /// long, and never across an `.await`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, _r) = watch::channel(7);
+    /// assert_eq!(*s.borrow(), 7);
+    /// ```
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref(self.shared.value.read().unwrap())
+    }
+
+    /// Creates a new receiver.
+    ///
+    /// The current value counts as already seen by the new receiver.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, r) = watch::channel(0);
+    /// drop(r);
+    ///
+    /// s.send(1);
+    /// let r = s.subscribe();
+    /// assert_eq!(*r.borrow(), 1);
+    /// assert!(!r.has_changed());
+    /// ```
+    pub fn subscribe(&self) -> Receiver<T> {
+        let mut state = self.shared.state.lock().unwrap();
+        let key = state.receivers.insert(None);
+        Receiver {
+            shared: self.shared.clone(),
+            version: state.version,
+            key,
+        }
+    }
+
+    /// Returns the number of receivers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, r) = watch::channel(());
+    /// assert_eq!(s.receiver_count(), 1);
+    ///
+    /// drop(r);
+    /// assert_eq!(s.receiver_count(), 0);
+    /// ```
+    pub fn receiver_count(&self) -> usize {
+        self.shared.state.lock().unwrap().receivers.len()
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.sender_alive = false;
+        state.wake_receivers();
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Sender<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sender")
+            .field("value", &&*self.borrow())
+            .finish()
+    }
+}
+
+/// The receiving side of a watch channel.
+///
+/// Cloning a receiver creates a new one that has seen the same values.
+///
+/// This type is created by the [`channel`] function or the [`Sender::subscribe`] method. See the
+/// [module documentation] for more details.
+///
+/// [`channel`]: fn.channel.html
+/// [`Sender::subscribe`]: struct.Sender.html#method.subscribe
+/// [module documentation]: index.html
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+
+    /// The version of the value last seen by this receiver.
+    version: u64,
+
+    /// The key of this receiver's waker slot.
+    key: usize,
+}
+
+impl<T> Receiver<T> {
+    /// Returns a reference to the current value.
+    ///
+    /// This does not mark the value as seen. The value cannot be replaced while the reference is
+    /// alive, so it should not be held for long, and never across an `.await`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (_s, r) = watch::channel("hello");
+    /// assert_eq!(*r.borrow(), "hello");
+    /// ```
+    pub fn borrow(&self) -> Ref<'_, T> {
+        Ref(self.shared.value.read().unwrap())
+    }
+
+    /// Returns `true` if the value has changed since this receiver last saw it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, r) = watch::channel(0);
+    /// assert!(!r.has_changed());
+    ///
+    /// s.send(1);
+    /// assert!(r.has_changed());
+    /// ```
+    pub fn has_changed(&self) -> bool {
+        self.shared.state.lock().unwrap().version != self.version
+    }
+
+    /// Waits until the value changes and marks the new value as seen.
+    ///
+    /// If the value has already changed since this receiver last saw it, this method returns
+    /// right away. Once the sender is dropped and the last value has been seen, an error is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::watch;
+    ///
+    /// let (s, mut r) = watch::channel(0);
+    /// s.send(1);
+    /// s.send(2);
+    ///
+    /// // Intermediate values are skipped.
+    /// r.changed().await.unwrap();
+    /// assert_eq!(*r.borrow(), 2);
+    ///
+    /// drop(s);
+    /// assert!(r.changed().await.is_err());
+    /// #
+    /// # })
+    /// ```
+    pub async fn changed(&mut self) -> Result<(), RecvError> {
+        crate::future::poll_fn(|cx| {
+            let mut state = self.shared.state.lock().unwrap();
+
+            if state.version != self.version {
+                self.version = state.version;
+                Poll::Ready(Ok(()))
+            } else if !state.sender_alive {
+                Poll::Ready(Err(RecvError))
+            } else {
+                let w = cx.waker();
+                match &mut state.receivers[self.key] {
+                    Some(old) if old.will_wake(w) => {}
+                    opt_waker => *opt_waker = Some(w.clone()),
+                }
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
+    /// Waits until the value changes and returns a copy of the new value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::sync::watch;
+    /// use async_std::task;
+    ///
+    /// let (s, mut r) = watch::channel(String::new());
+    ///
+    /// task::spawn(async move {
+    ///     s.send("reloaded".to_string());
+    /// });
+    ///
+    /// assert_eq!(r.recv().await.unwrap(), "reloaded");
+    /// #
+    /// # })
+    /// ```
+    pub async fn recv(&mut self) -> Result<T, RecvError>
+    where
+        T: Clone,
+    {
+        self.changed().await?;
+        Ok(self.borrow().clone())
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    fn clone(&self) -> Receiver<T> {
+        let mut state = self.shared.state.lock().unwrap();
+        let key = state.receivers.insert(None);
+        Receiver {
+            shared: self.shared.clone(),
+            version: self.version,
+            key,
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.state.lock().unwrap();
+        state.receivers.remove(self.key);
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver")
+            .field("value", &&*self.borrow())
+            .finish()
+    }
+}
+
+/// A reference to the value in a watch channel.
+///
+/// This type is returned by the [`Sender::borrow`] and [`Receiver::borrow`] methods.
+///
+/// [`Sender::borrow`]: struct.Sender.html#method.borrow
+/// [`Receiver::borrow`]: struct.Receiver.html#method.borrow
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+pub struct Ref<'a, T>(RwLockReadGuard<'a, T>);
+
+impl<T> Deref for Ref<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Ref<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&**self, f)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Ref<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+/// An error returned from the [`Receiver::changed`] method when the sender is dropped.
+///
+/// [`Receiver::changed`]: struct.Receiver.html#method.changed
+#[cfg(feature = "unstable")]
+#[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RecvError;
+
+impl Error for RecvError {}
+
+impl fmt::Display for RecvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        "the sender was dropped".fmt(f)
+    }
+}
+
+struct Shared<T> {
+    value: RwLock<T>,
+    state: Mutex<State>,
+}
+
+struct State {
+    /// Incremented every time the value is replaced.
+    version: u64,
+
+    sender_alive: bool,
+
+    /// Wakers of receivers waiting for a change, one slot per receiver.
+    receivers: Slab<Option<Waker>>,
+}
+
+impl State {
+    fn wake_receivers(&mut self) {
+        for (_, opt_waker) in self.receivers.iter_mut() {
+            if let Some(w) = opt_waker.take() {
+                w.wake();
+            }
+        }
+    }
+}
@@ -21,6 +21,11 @@ use kv_log_macro::trace;
 /// Calling this function is similar to [spawning] a thread and immediately [joining] it, except an
 /// asynchronous task will be spawned.
 ///
+/// With the `unstable` feature, tasks spawned with [`task::spawn_local`] on the current thread are
+/// run while this function is blocking.
+///
+/// [`task::spawn_local`]: fn.spawn_local.html
+///
 /// See also: [`task::spawn_blocking`].
 ///
 /// [`task::spawn_blocking`]: fn.spawn_blocking.html
@@ -141,12 +146,24 @@ where
         let waker = unsafe { ManuallyDrop::new(Waker::from_raw(RawWaker::new(ptr, vt))) };
         let cx = &mut Context::from_waker(&waker);
 
+        #[cfg(feature = "unstable")]
+        let _enter = super::spawn_local::Enter::new();
+
         loop {
             if let Poll::Ready(t) = f.as_mut().poll(cx) {
                 // Save the parker for the next invocation of `block`.
                 cache.set(Some(arc_parker));
                 return t;
             }
+
+            // Run local tasks in between polls of the future.
+            #[cfg(feature = "unstable")]
+            {
+                if super::spawn_local::run_ready(cx.waker()) {
+                    continue;
+                }
+            }
+
             arc_parker.park();
         }
     })
@@ -101,7 +101,13 @@ where
     R: Send + 'static,
 {
     let tag = Tag::new(None);
-    let future = async move { f() };
+    #[cfg(feature = "unstable")]
+    let guard = tag.stop_guard();
+    let future = async move {
+        #[cfg(feature = "unstable")]
+        let _guard = guard;
+        f()
+    };
     let (task, handle) = async_task::spawn(future, schedule, tag);
     task.schedule();
     JoinHandle::new(handle)
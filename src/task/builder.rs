@@ -16,17 +16,38 @@ impl Builder {
     }
 
     /// Configures the name of the task.
+    ///
+    /// The name is available through [`Task::name`] and is included in the log records of the
+    /// task. If the task panics, its name is written to stderr along with the panic message.
+    ///
+    /// [`Task::name`]: struct.Task.html#method.name
     pub fn name(mut self, name: String) -> Builder {
         self.name = Some(name);
         self
     }
 
     /// Spawns a task with the configured settings.
+    ///
+    /// # Errors
+    ///
+    /// With the `unstable` feature, an error is returned once [`shutdown`] has been called.
+    ///
+    /// [`shutdown`]: fn.shutdown.html
     pub fn spawn<F, T>(self, future: F) -> io::Result<JoinHandle<T>>
     where
         F: Future<Output = T> + Send + 'static,
         T: Send + 'static,
     {
+        #[cfg(feature = "unstable")]
+        {
+            if super::shutdown::is_shutting_down() {
+                return Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    "cannot spawn a task after shutdown",
+                ));
+            }
+        }
+
         Ok(pool::get().spawn(future, self))
     }
 }
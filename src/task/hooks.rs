@@ -0,0 +1,185 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+
+use super::task::{Runnable, Tag};
+use super::Task;
+
+/// Callbacks that are invoked as tasks are spawned, polled, and completed.
+///
+/// Hooks are installed with [`set_hooks`] and make it possible to export metrics such as the
+/// number of running tasks or how long polls take. They are invoked on the thread doing the work,
+/// so they should be quick.
+///
+/// Hooks only see tasks spawned with [`spawn`] or [`Builder::spawn`]. Tasks running in
+/// [`block_on`] or [`spawn_blocking`] are not reported.
+///
+/// [`set_hooks`]: fn.set_hooks.html
+/// [`spawn`]: fn.spawn.html
+/// [`Builder::spawn`]: struct.Builder.html#method.spawn
+/// [`block_on`]: fn.block_on.html
+/// [`spawn_blocking`]: fn.spawn_blocking.html
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// use async_std::task::{self, Hooks};
+///
+/// let running = Arc::new(AtomicUsize::new(0));
+/// let r1 = running.clone();
+/// let r2 = running.clone();
+///
+/// task::set_hooks(
+///     Hooks::new()
+///         .on_spawn(move |_| {
+///             r1.fetch_add(1, Ordering::SeqCst);
+///         })
+///         .on_complete(move |_| {
+///             r2.fetch_sub(1, Ordering::SeqCst);
+///         }),
+/// );
+/// ```
+#[derive(Default)]
+pub struct Hooks {
+    spawn: Option<TaskHook>,
+    poll_start: Option<TaskHook>,
+    poll_end: Option<PollEndHook>,
+    complete: Option<TaskHook>,
+}
+
+/// A hook that is passed a task.
+type TaskHook = Box<dyn Fn(&Task) + Send + Sync>;
+
+/// A hook that is passed a task and the time it was polled for.
+type PollEndHook = Box<dyn Fn(&Task, Duration) + Send + Sync>;
+
+impl Hooks {
+    /// Creates a set of hooks that do nothing.
+    pub fn new() -> Hooks {
+        Hooks::default()
+    }
+
+    /// Sets the hook that is invoked when a task is spawned.
+    pub fn on_spawn<F>(mut self, f: F) -> Hooks
+    where
+        F: Fn(&Task) + Send + Sync + 'static,
+    {
+        self.spawn = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the hook that is invoked before a task is polled.
+    pub fn on_poll_start<F>(mut self, f: F) -> Hooks
+    where
+        F: Fn(&Task) + Send + Sync + 'static,
+    {
+        self.poll_start = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the hook that is invoked after a task is polled, with the time the poll took.
+    pub fn on_poll_end<F>(mut self, f: F) -> Hooks
+    where
+        F: Fn(&Task, Duration) + Send + Sync + 'static,
+    {
+        self.poll_end = Some(Box::new(f));
+        self
+    }
+
+    /// Sets the hook that is invoked when the future of a task completes.
+    ///
+    /// This is not invoked for tasks that are cancelled before they complete.
+    pub fn on_complete<F>(mut self, f: F) -> Hooks
+    where
+        F: Fn(&Task) + Send + Sync + 'static,
+    {
+        self.complete = Some(Box::new(f));
+        self
+    }
+}
+
+impl fmt::Debug for Hooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad("Hooks { .. }")
+    }
+}
+
+/// Installs hooks for all tasks, replacing the previously installed ones.
+///
+/// See [`Hooks`] for more.
+///
+/// [`Hooks`]: struct.Hooks.html
+///
+/// # Examples
+///
+/// ```
+/// use async_std::task::{self, Hooks};
+///
+/// task::set_hooks(Hooks::new().on_poll_end(|task, elapsed| {
+///     println!("task {} was polled for {:?}", task.id(), elapsed);
+/// }));
+/// ```
+pub fn set_hooks(hooks: Hooks) {
+    *HOOKS.write().unwrap() = Some(Arc::new(hooks));
+    ENABLED.store(true, Ordering::SeqCst);
+}
+
+/// Set once hooks have been installed, so that tasks don't touch the lock until then.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+lazy_static! {
+    static ref HOOKS: RwLock<Option<Arc<Hooks>>> = RwLock::new(None);
+}
+
+/// Returns the installed hooks, if any.
+fn get() -> Option<Arc<Hooks>> {
+    if ENABLED.load(Ordering::Relaxed) {
+        HOOKS.read().unwrap().clone()
+    } else {
+        None
+    }
+}
+
+/// Invokes the spawn hook for a task that was just created.
+pub(crate) fn spawned(tag: &Tag) {
+    if let Some(hooks) = get() {
+        if let Some(f) = &hooks.spawn {
+            f(tag.task());
+        }
+    }
+}
+
+/// Invokes the completion hook for the current task.
+pub(crate) fn completed() {
+    if let Some(hooks) = get() {
+        if let Some(f) = &hooks.complete {
+            super::worker::get_task(|task| f(task));
+        }
+    }
+}
+
+/// Runs a task, invoking the poll hooks around it.
+pub(crate) fn run(task: Runnable) {
+    let hooks = match get() {
+        Some(hooks) if hooks.poll_start.is_some() || hooks.poll_end.is_some() => hooks,
+        _ => return task.run(),
+    };
+
+    // The task may complete and drop its tag while running, so keep a handle to it.
+    let handle = task.tag().task().clone();
+
+    if let Some(f) = &hooks.poll_start {
+        f(&handle);
+    }
+    let start = Instant::now();
+    task.run();
+    if let Some(f) = &hooks.poll_end {
+        f(&handle, start.elapsed());
+    }
+}
@@ -73,6 +73,17 @@
 //! # })
 //! ```
 //!
+//! ## Worker threads
+//!
+//! Spawned tasks run on a pool of worker threads. Each worker has its own queue of tasks, and new
+//! tasks spawned from outside the pool go into a shared queue. A worker that runs out of tasks
+//! takes a batch from the shared queue or steals one from another worker, so a busy worker
+//! doesn't hold up tasks while other threads are idle.
+//!
+//! By default there is one worker per CPU. The `ASYNC_STD_THREAD_COUNT` environment variable sets
+//! a different number. It is read once, when the first task is spawned. If it isn't a positive
+//! integer, a warning is logged and the number of CPUs is used instead.
+//!
 //! ## The `Task` type
 //!
 //! Tasks are represented via the [`Task`] type, which you can get in one of
@@ -144,7 +155,13 @@ mod worker;
 pub(crate) mod blocking;
 
 cfg_unstable! {
+    mod hooks;
+    mod shutdown;
+    mod spawn_local;
     mod yield_now;
+    pub use hooks::{set_hooks, Hooks};
+    pub use shutdown::shutdown;
+    pub use spawn_local::spawn_local;
     pub use yield_now::yield_now;
 }
 
@@ -154,6 +171,9 @@ cfg_unstable! {
 /// is useful to prevent long-running synchronous operations from blocking the main futures
 /// executor.
 ///
+/// The pool starts a new thread whenever all of its threads are busy, and threads that have been
+/// idle for a few seconds exit, so any number of blocking tasks can run at the same time.
+///
 /// See also: [`task::block_on`], [`task::spawn`].
 ///
 /// [`task::block_on`]: fn.block_on.html
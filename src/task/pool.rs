@@ -1,8 +1,10 @@
+use std::env;
+use std::io::{self, Write};
 use std::iter;
 use std::thread;
 
 use crossbeam_deque::{Injector, Stealer, Worker};
-use kv_log_macro::trace;
+use kv_log_macro::{error, trace, warn};
 use lazy_static::lazy_static;
 
 use super::sleepers::Sleepers;
@@ -55,6 +57,7 @@ impl Pool {
         F: Future<Output = T> + Send + 'static,
         T: Send + 'static,
     {
+        let name = builder.name.clone();
         let tag = task::Tag::new(builder.name);
 
         // Log this `spawn` operation.
@@ -64,21 +67,43 @@ impl Pool {
         trace!("spawn", {
             parent_id: parent_id,
             child_id: child_id,
+            name: name.as_deref().unwrap_or(""),
         });
 
+        #[cfg(feature = "unstable")]
+        super::hooks::spawned(&tag);
+
         // Wrap the future into one that drops task-local variables on exit.
         let future = unsafe { task_local::add_finalizer(future) };
 
+        // Wrap the future into one that signals cancellation waiters when it is dropped.
+        #[cfg(feature = "unstable")]
+        let guard = tag.stop_guard();
+
         // Wrap the future into one that logs completion on exit.
         let future = async move {
+            #[cfg(feature = "unstable")]
+            let _guard = guard;
+            let _notice = PanicNotice {
+                id: child_id,
+                name: name.as_deref(),
+            };
             let res = future.await;
             trace!("spawn completed", {
                 parent_id: parent_id,
                 child_id: child_id,
+                name: name.as_deref().unwrap_or(""),
             });
+
+            #[cfg(feature = "unstable")]
+            super::hooks::completed();
             res
         };
 
+        // Wrap the future into one that is tracked until it is dropped, for `task::shutdown`.
+        #[cfg(feature = "unstable")]
+        let future = super::shutdown::TaskGuard::new().track(future);
+
         let (task, handle) = async_task::spawn(future, worker::schedule, tag);
         task.schedule();
         JoinHandle::new(handle)
@@ -109,11 +134,54 @@ impl Pool {
     }
 }
 
+/// Reports the task if it panics, since the panic aborts the process and the panic message only
+/// names the worker thread.
+///
+/// The task is written to stderr right after the panic message, and logged as well.
+struct PanicNotice<'a> {
+    id: u64,
+    name: Option<&'a str>,
+}
+
+impl Drop for PanicNotice<'_> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            let mut stderr = io::stderr();
+            let _ = match self.name {
+                Some(name) => writeln!(stderr, "task {} ('{}') panicked, aborting", self.id, name),
+                None => writeln!(stderr, "task {} panicked, aborting", self.id),
+            };
+            error!("task panicked, aborting", {
+                task_id: self.id,
+                name: self.name.unwrap_or(""),
+            });
+        }
+    }
+}
+
+/// Returns the number of worker threads to start.
+///
+/// This is the `ASYNC_STD_THREAD_COUNT` environment variable if set to a positive integer, or
+/// the number of CPUs.
+fn num_threads() -> usize {
+    if let Ok(s) = env::var("ASYNC_STD_THREAD_COUNT") {
+        match s.parse::<usize>() {
+            Ok(n) if n > 0 => return n,
+            _ => {
+                warn!("ASYNC_STD_THREAD_COUNT is not a positive integer, using the number of CPUs", {
+                    value: s,
+                })
+            }
+        }
+    }
+    num_cpus::get().max(1)
+}
+
 #[inline]
 pub(crate) fn get() -> &'static Pool {
     lazy_static! {
         static ref POOL: Pool = {
-            let num_threads = num_cpus::get().max(1);
+            let num_threads = num_threads();
             let mut stealers = Vec::new();
 
             // Spawn worker threads.
@@ -0,0 +1,293 @@
+use std::future::Future;
+use std::mem::{self, ManuallyDrop};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{RawWaker, RawWakerVTable};
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use slab::Slab;
+
+use crate::future;
+use crate::task::{Context, Poll, Waker};
+
+/// Shuts down the executor, waiting up to `timeout` for spawned tasks to complete.
+///
+/// Once shutdown has started, no new tasks can be spawned: [`Builder::spawn`] returns an error
+/// and [`spawn`] panics. Tasks that are already running keep running until they complete or
+/// `timeout` passes, whichever comes first. Tasks that are still alive then are dropped the next
+/// time they would be polled.
+///
+/// Returns `true` if all tasks completed in time, or `false` if some had to be dropped.
+///
+/// Only tasks spawned with [`spawn`] and [`Builder::spawn`] are tracked. The future passed to
+/// [`block_on`] and tasks spawned with [`spawn_blocking`] are not affected, so this is usually
+/// called at the end of the future passed to `block_on` in `main`.
+///
+/// There are a few limits to be aware of:
+///
+/// * The executor is shared by the whole process, so shutdown applies to all tasks, no matter
+///   which call to `block_on` spawned them.
+/// * Shutdown can't be undone. Once this function has been called, no task can be spawned for
+///   the rest of the process.
+/// * Only tasks that were polled after shutdown started can be woken up to be dropped, and only
+///   those are waited for. Other tasks are dropped when something else wakes them.
+/// * Tasks that nothing can wake anymore never complete, so if there are any, this function
+///   always waits for the whole `timeout`.
+///
+/// [`spawn`]: fn.spawn.html
+/// [`Builder::spawn`]: struct.Builder.html#method.spawn
+/// [`block_on`]: fn.block_on.html
+/// [`spawn_blocking`]: fn.spawn_blocking.html
+///
+/// # Panics
+///
+/// This function panics if it is called from a spawned task, since it would wait for that task
+/// to complete.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::time::Duration;
+///
+/// use async_std::task;
+///
+/// task::spawn(async {
+///     task::sleep(Duration::from_millis(10)).await;
+/// });
+///
+/// // The task completes before the timeout.
+/// assert!(task::shutdown(Duration::from_secs(5)).await);
+///
+/// // No more tasks can be spawned.
+/// assert!(task::Builder::new().spawn(async {}).is_err());
+/// #
+/// # })
+/// ```
+pub async fn shutdown(timeout: Duration) -> bool {
+    if super::worker::is_worker() {
+        panic!("`task::shutdown` cannot be called from a spawned task");
+    }
+    SHUTTING_DOWN.store(true, Ordering::SeqCst);
+
+    if future::timeout(timeout, future::poll_fn(poll_drained))
+        .await
+        .is_ok()
+    {
+        return true;
+    }
+
+    // Wake up every task polled since shutdown started, so that the workers drop it instead of
+    // polling it.
+    CANCELLING.store(true, Ordering::SeqCst);
+    let wakers: Vec<Arc<Waker>> = STATE
+        .lock()
+        .unwrap()
+        .tasks
+        .iter()
+        .filter_map(|(_, w)| w.lock().unwrap().upgrade())
+        .collect();
+    for w in wakers {
+        w.wake_by_ref();
+    }
+
+    future::poll_fn(poll_cancelled).await;
+    false
+}
+
+/// Set once shutdown has started and new tasks are rejected.
+static SHUTTING_DOWN: AtomicBool = AtomicBool::new(false);
+
+/// Set once the timeout has passed and remaining tasks are dropped.
+static CANCELLING: AtomicBool = AtomicBool::new(false);
+
+/// The number of spawned tasks whose future hasn't been dropped yet.
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+
+/// The waker a tracked task was last polled with.
+///
+/// Only a weak reference is kept. A strong one would keep alive tasks that nothing else can wake,
+/// which are otherwise freed as soon as their last waker is dropped.
+type TaskWaker = Arc<Mutex<Weak<Waker>>>;
+
+/// The tasks polled since shutdown started, and the tasks waiting for tasks to be gone.
+///
+/// This is only used once shutdown has started, so that spawning and polling tasks never has to
+/// lock it before then.
+struct State {
+    tasks: Slab<TaskWaker>,
+    waiters: Vec<Waker>,
+}
+
+lazy_static! {
+    static ref STATE: Mutex<State> = Mutex::new(State {
+        tasks: Slab::new(),
+        waiters: Vec::new(),
+    });
+}
+
+impl State {
+    /// Registers a waker to be woken when a task is gone.
+    fn register(&mut self, waker: &Waker) {
+        if !self.waiters.iter().any(|w| w.will_wake(waker)) {
+            self.waiters.push(waker.clone());
+        }
+    }
+}
+
+/// Completes once there are no spawned tasks left.
+fn poll_drained(cx: &mut Context<'_>) -> Poll<()> {
+    let mut state = STATE.lock().unwrap();
+    if LIVE.load(Ordering::SeqCst) == 0 {
+        Poll::Ready(())
+    } else {
+        state.register(cx.waker());
+        Poll::Pending
+    }
+}
+
+/// Completes once there are no tasks left that were polled since shutdown started and can still
+/// be woken.
+fn poll_cancelled(cx: &mut Context<'_>) -> Poll<()> {
+    let mut state = STATE.lock().unwrap();
+    if state
+        .tasks
+        .iter()
+        .all(|(_, w)| Weak::strong_count(&w.lock().unwrap()) == 0)
+    {
+        Poll::Ready(())
+    } else {
+        state.register(cx.waker());
+        Poll::Pending
+    }
+}
+
+/// Returns `true` if new tasks must not be spawned.
+pub(crate) fn is_shutting_down() -> bool {
+    SHUTTING_DOWN.load(Ordering::SeqCst)
+}
+
+/// Returns `true` if scheduled tasks must be dropped instead of run.
+pub(crate) fn is_cancelling() -> bool {
+    CANCELLING.load(Ordering::Relaxed)
+}
+
+/// Tracks a spawned task until its future is dropped.
+pub(crate) struct TaskGuard {
+    /// The key and waker of the task in `STATE`, once it has been polled during shutdown.
+    slot: Option<(usize, TaskWaker)>,
+}
+
+impl TaskGuard {
+    pub fn new() -> TaskGuard {
+        LIVE.fetch_add(1, Ordering::SeqCst);
+        TaskGuard { slot: None }
+    }
+
+    /// Wraps the future of the task, so that shutdown can wake it.
+    pub fn track<F: Future>(self, future: F) -> Tracked<F> {
+        Tracked {
+            future,
+            guard: self,
+        }
+    }
+
+    /// Returns a waker that wakes `waker` and is recorded as the waker of the task.
+    fn wrap(&mut self, waker: &Waker) -> Waker {
+        let slot = match &self.slot {
+            Some((_, slot)) => slot.clone(),
+            None => {
+                let slot = TaskWaker::default();
+                let key = STATE.lock().unwrap().tasks.insert(slot.clone());
+                self.slot = Some((key, slot.clone()));
+                slot
+            }
+        };
+
+        let mut weak = slot.lock().unwrap();
+        let arc = match weak.upgrade() {
+            Some(arc) if arc.will_wake(waker) => arc,
+            _ => {
+                let arc = Arc::new(waker.clone());
+                *weak = Arc::downgrade(&arc);
+                arc
+            }
+        };
+        unsafe { Waker::from_raw(RawWaker::new(Arc::into_raw(arc) as *const (), vtable())) }
+    }
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        LIVE.fetch_sub(1, Ordering::SeqCst);
+        if is_shutting_down() {
+            let mut state = STATE.lock().unwrap();
+            if let Some((key, _)) = self.slot.take() {
+                state.tasks.remove(key);
+            }
+            for w in state.waiters.drain(..) {
+                w.wake();
+            }
+        }
+    }
+}
+
+/// A future that is tracked by a [`TaskGuard`].
+///
+/// [`TaskGuard`]: struct.TaskGuard.html
+pub(crate) struct Tracked<F> {
+    future: F,
+    guard: TaskGuard,
+}
+
+impl<F> Tracked<F> {
+    pin_utils::unsafe_pinned!(future: F);
+    pin_utils::unsafe_unpinned!(guard: TaskGuard);
+}
+
+impl<F: Future> Future for Tracked<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        if !is_shutting_down() {
+            return self.future().poll(cx);
+        }
+
+        let waker = self.as_mut().guard().wrap(cx.waker());
+        let res = self.future().poll(&mut Context::from_waker(&waker));
+
+        // Shutdown may have woken the tasks it knows of before this one was recorded, so make
+        // sure it is scheduled again to be dropped.
+        if res.is_pending() && is_cancelling() {
+            waker.wake_by_ref();
+        }
+        res
+    }
+}
+
+fn vtable() -> &'static RawWakerVTable {
+    unsafe fn clone_raw(ptr: *const ()) -> RawWaker {
+        let arc = ManuallyDrop::new(Arc::from_raw(ptr as *const Waker));
+        mem::forget(arc.clone());
+        RawWaker::new(ptr, vtable())
+    }
+
+    unsafe fn wake_raw(ptr: *const ()) {
+        let arc = Arc::from_raw(ptr as *const Waker);
+        arc.wake_by_ref();
+    }
+
+    unsafe fn wake_by_ref_raw(ptr: *const ()) {
+        let arc = ManuallyDrop::new(Arc::from_raw(ptr as *const Waker));
+        arc.wake_by_ref();
+    }
+
+    unsafe fn drop_raw(ptr: *const ()) {
+        drop(Arc::from_raw(ptr as *const Waker))
+    }
+
+    &RawWakerVTable::new(clone_raw, wake_raw, wake_by_ref_raw, drop_raw)
+}
@@ -11,9 +11,10 @@ use crate::io;
 ///
 /// [`std::thread::sleep`]: https://doc.rust-lang.org/std/thread/fn.sleep.html
 ///
-/// See also: [`stream::interval`].
+/// See also: [`stream::interval`], [`time::sleep_until`].
 ///
 /// [`stream::interval`]: ../stream/fn.interval.html
+/// [`time::sleep_until`]: ../time/fn.sleep_until.html
 ///
 /// # Examples
 ///
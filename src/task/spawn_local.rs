@@ -0,0 +1,165 @@
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+use kv_log_macro::trace;
+
+use super::task;
+use super::task_local;
+use super::worker;
+use super::JoinHandle;
+use crate::future::Future;
+use crate::task::Waker;
+
+/// Spawns a task that runs on the current thread.
+///
+/// Unlike [`spawn`], the future doesn't have to be [`Send`], so it can hold values like [`Rc`]
+/// and handles that must stay on one thread. The task is polled by [`block_on`] on the thread that
+/// spawned it, in between polls of the future `block_on` was given. It can use the I/O types and
+/// timers of this crate just like any other task.
+///
+/// Local tasks only make progress while a `block_on` call is running on their thread. A panic in a
+/// local task propagates out of that `block_on` call.
+///
+/// [`spawn`]: fn.spawn.html
+/// [`block_on`]: fn.block_on.html
+/// [`Send`]: https://doc.rust-lang.org/std/marker/trait.Send.html
+/// [`Rc`]: https://doc.rust-lang.org/std/rc/struct.Rc.html
+///
+/// # Panics
+///
+/// This function panics if it is not called within [`block_on`] on the current thread, for
+/// example from a task spawned with [`spawn`].
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use std::rc::Rc;
+///
+/// use async_std::task;
+///
+/// let shared = Rc::new(1);
+/// let s = shared.clone();
+///
+/// let handle = task::spawn_local(async move { *s + 2 });
+/// assert_eq!(handle.await, 3);
+/// #
+/// # })
+/// ```
+pub fn spawn_local<F, T>(future: F) -> JoinHandle<T>
+where
+    F: Future<Output = T> + 'static,
+    T: 'static,
+{
+    assert!(
+        DEPTH.with(|d| d.get()) > 0,
+        "`task::spawn_local()` called outside of `task::block_on()`"
+    );
+    let queue = QUEUE.with(|q| q.clone());
+
+    let tag = task::Tag::new(None);
+
+    // Log this `spawn_local` operation.
+    let child_id = tag.task_id().as_u64();
+    let parent_id = worker::get_task(|t| t.id().as_u64()).unwrap_or(0);
+
+    trace!("spawn_local", {
+        parent_id: parent_id,
+        child_id: child_id,
+    });
+
+    // Wrap the future into one that drops task-local variables on exit.
+    let future = unsafe { task_local::add_finalizer(future) };
+
+    // Wrap the future into one that signals cancellation waiters when it is dropped.
+    let guard = tag.stop_guard();
+
+    // Wrap the future into one that logs completion on exit.
+    let future = async move {
+        let _guard = guard;
+        let res = future.await;
+        trace!("spawn_local completed", {
+            parent_id: parent_id,
+            child_id: child_id,
+        });
+        res
+    };
+
+    let schedule = move |t| queue.push(t);
+    let (task, handle) = async_task::spawn_local(future, schedule, tag);
+    task.schedule();
+    JoinHandle::new(handle)
+}
+
+/// The local tasks of a thread that are ready to be run.
+///
+/// Local tasks can be woken up from any thread, so the queue is shared.
+struct Queue {
+    tasks: Mutex<VecDeque<task::Runnable>>,
+
+    /// The waker of the innermost `block_on` call on the thread, woken up when a task is pushed.
+    waker: Mutex<Option<Waker>>,
+}
+
+impl Queue {
+    fn push(&self, task: task::Runnable) {
+        self.tasks.lock().unwrap().push_back(task);
+        if let Some(w) = &*self.waker.lock().unwrap() {
+            w.wake_by_ref();
+        }
+    }
+}
+
+thread_local! {
+    static QUEUE: Arc<Queue> = Arc::new(Queue {
+        tasks: Mutex::new(VecDeque::new()),
+        waker: Mutex::new(None),
+    });
+
+    /// The number of `block_on` calls running on this thread.
+    static DEPTH: Cell<usize> = Cell::new(0);
+}
+
+/// Marks the current thread as running `block_on` for as long as the guard is alive.
+pub(crate) struct Enter(());
+
+impl Enter {
+    pub fn new() -> Enter {
+        DEPTH.with(|d| d.set(d.get() + 1));
+        Enter(())
+    }
+}
+
+impl Drop for Enter {
+    fn drop(&mut self) {
+        DEPTH.with(|d| d.set(d.get() - 1));
+    }
+}
+
+/// Runs the local tasks that are ready, and registers `waker` to be woken up when more are.
+///
+/// Returns `true` if any tasks were run. Tasks woken up while running are left for the next call,
+/// so that the future of `block_on` gets polled in between.
+pub(crate) fn run_ready(waker: &Waker) -> bool {
+    QUEUE.with(|queue| {
+        {
+            let mut w = queue.waker.lock().unwrap();
+            match &*w {
+                Some(w) if w.will_wake(waker) => {}
+                _ => *w = Some(waker.clone()),
+            }
+        }
+
+        let count = queue.tasks.lock().unwrap().len();
+        for _ in 0..count {
+            let task = match queue.tasks.lock().unwrap().pop_front() {
+                Some(task) => task,
+                None => break,
+            };
+            worker::set_tag(task.tag(), || task.run());
+        }
+        count > 0
+    })
+}
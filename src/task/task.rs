@@ -10,6 +10,13 @@ use std::sync::Arc;
 use super::task_local;
 use crate::task::{Context, Poll};
 
+cfg_unstable! {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Mutex;
+
+    use crate::task::Waker;
+}
+
 /// A handle to a task.
 #[derive(Clone)]
 pub struct Task(Arc<Metadata>);
@@ -49,13 +56,17 @@ impl fmt::Debug for Task {
 /// Dropping a [`JoinHandle`] will detach the task, meaning that there is no longer
 /// a handle to the task and no way to `join` on it.
 ///
+/// A task can be stopped early with [`cancel`].
+///
+/// [`cancel`]: #method.cancel
+///
 /// Created when a task is [spawned].
 ///
 /// [spawned]: fn.spawn.html
 #[derive(Debug)]
 pub struct JoinHandle<T>(async_task::JoinHandle<T, Tag>);
 
-unsafe impl<T> Send for JoinHandle<T> {}
+unsafe impl<T: Send> Send for JoinHandle<T> {}
 unsafe impl<T> Sync for JoinHandle<T> {}
 
 impl<T> JoinHandle<T> {
@@ -81,6 +92,71 @@ impl<T> JoinHandle<T> {
     pub fn task(&self) -> &Task {
         self.0.tag().task()
     }
+
+    /// Cancels the task and waits for it to stop.
+    ///
+    /// A cancelled task is not polled again. Its future is dropped the next time it would have
+    /// been polled, or once its current poll returns if it is running right now. The returned
+    /// future completes after the task's future has been dropped, so resources held by the task
+    /// are released by then.
+    ///
+    /// Returns the output of the task if it completed before it could be cancelled.
+    ///
+    /// Blocking tasks can only be cancelled before they start running.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use std::time::Duration;
+    ///
+    /// use async_std::task;
+    ///
+    /// let handle = task::spawn(async {
+    ///     task::sleep(Duration::from_secs(10)).await;
+    ///     1 + 2
+    /// });
+    ///
+    /// assert_eq!(handle.cancel().await, None);
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub async fn cancel(mut self) -> Option<T> {
+        self.0.cancel();
+        let output = (&mut self.0).await;
+
+        let stopped = self.0.tag().stopped.clone();
+        crate::future::poll_fn(|cx| stopped.poll(cx)).await;
+        output
+    }
+
+    /// Detaches the task so that it keeps running in the background.
+    ///
+    /// This is the same as dropping the handle, but makes the intent explicit. The output of the
+    /// task is dropped once it completes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::task;
+    ///
+    /// task::spawn(async {
+    ///     println!("running in the background");
+    /// })
+    /// .detach();
+    /// #
+    /// # })
+    /// ```
+    #[cfg(feature = "unstable")]
+    #[cfg_attr(feature = "docs", doc(cfg(unstable)))]
+    pub fn detach(self) {
+        drop(self);
+    }
 }
 
 impl<T> Future for JoinHandle<T> {
@@ -89,7 +165,7 @@ impl<T> Future for JoinHandle<T> {
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         match Pin::new(&mut self.0).poll(cx) {
             Poll::Pending => Poll::Pending,
-            Poll::Ready(None) => panic!("task has panicked"),
+            Poll::Ready(None) => panic!("task has panicked or was dropped by `task::shutdown`"),
             Poll::Ready(Some(val)) => Poll::Ready(val),
         }
     }
@@ -144,6 +220,50 @@ pub(crate) struct Metadata {
 pub(crate) struct Tag {
     task_id: TaskId,
     raw_metadata: AtomicUsize,
+
+    /// Signaled once the future of the task has been dropped.
+    #[cfg(feature = "unstable")]
+    stopped: Arc<Stopped>,
+}
+
+/// Tracks whether the future of a task has been dropped, either after completing or after the
+/// task was cancelled.
+#[cfg(feature = "unstable")]
+pub(crate) struct Stopped {
+    flag: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+#[cfg(feature = "unstable")]
+impl Stopped {
+    fn poll(&self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.flag.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+
+        let mut waker = self.waker.lock().unwrap();
+        if self.flag.load(Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+        *waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+/// Signals that the future of a task has been dropped when it is dropped itself.
+///
+/// A guard is moved into the future of every spawned task.
+#[cfg(feature = "unstable")]
+pub(crate) struct StopGuard(Arc<Stopped>);
+
+#[cfg(feature = "unstable")]
+impl Drop for StopGuard {
+    fn drop(&mut self) {
+        self.0.flag.store(true, Ordering::SeqCst);
+        if let Some(w) = self.0.waker.lock().unwrap().take() {
+            w.wake();
+        }
+    }
 }
 
 impl Tag {
@@ -163,9 +283,20 @@ impl Tag {
             raw_metadata: AtomicUsize::new(unsafe {
                 mem::transmute::<Option<Task>, usize>(opt_task)
             }),
+            #[cfg(feature = "unstable")]
+            stopped: Arc::new(Stopped {
+                flag: AtomicBool::new(false),
+                waker: Mutex::new(None),
+            }),
         }
     }
 
+    /// Returns a guard that signals the task has stopped once dropped.
+    #[cfg(feature = "unstable")]
+    pub fn stop_guard(&self) -> StopGuard {
+        StopGuard(self.stopped.clone())
+    }
+
     pub fn task(&self) -> &Task {
         unsafe {
             let raw = self.raw_metadata.load(Ordering::Acquire);
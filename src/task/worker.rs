@@ -42,17 +42,17 @@ pub(crate) fn set_tag<F, R>(tag: *const task::Tag, f: F) -> R
 where
     F: FnOnce() -> R,
 {
-    struct ResetTag<'a>(&'a Cell<*const task::Tag>);
+    struct ResetTag<'a>(&'a Cell<*const task::Tag>, *const task::Tag);
 
     impl Drop for ResetTag<'_> {
         fn drop(&mut self) {
-            self.0.set(ptr::null());
+            self.0.set(self.1);
         }
     }
 
+    // Restore the previous tag on exit because local tasks run inside `block_on`.
     TAG.with(|t| {
-        t.set(tag);
-        let _guard = ResetTag(t);
+        let _guard = ResetTag(t, t.replace(tag));
 
         f()
     })
@@ -103,8 +103,25 @@ pub(crate) fn main_loop(worker: Worker<task::Runnable>) {
 
     loop {
         match get_queue(|q| pool::get().find_task(q)) {
-            Some(task) => set_tag(task.tag(), || abort_on_panic(|| task.run())),
+            Some(task) => set_tag(task.tag(), || abort_on_panic(|| run(task))),
             None => pool::get().sleepers.wait(),
         }
     }
 }
+
+/// Runs a task, invoking the poll hooks if there are any.
+#[inline]
+fn run(task: task::Runnable) {
+    // Tasks still alive when the shutdown timeout passes are dropped instead.
+    #[cfg(feature = "unstable")]
+    {
+        if super::shutdown::is_cancelling() {
+            drop(task);
+        } else {
+            super::hooks::run(task);
+        }
+    }
+
+    #[cfg(not(feature = "unstable"))]
+    task.run();
+}
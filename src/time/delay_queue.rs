@@ -0,0 +1,399 @@
+use std::collections::BTreeMap;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+use futures_timer::Delay;
+use slab::Slab;
+
+use crate::future::Future;
+use crate::stream::Stream;
+use crate::task::{Context, Poll, Waker};
+
+/// A queue of items that are yielded once their deadlines have passed.
+///
+/// Items are inserted with a deadline and the queue is a stream that yields them as they expire,
+/// earliest deadline first. All items share a single timer, so a queue can track the idle
+/// timeouts of thousands of connections or the expiry of every entry in a cache without spawning a
+/// task per item.
+///
+/// Inserting an item returns a [`Key`] that can be used to [`reset`] its deadline or [`remove`] it
+/// before it expires.
+///
+/// The stream never ends. When the queue is empty, it waits until a new item is inserted.
+///
+/// [`Key`]: struct.Key.html
+/// [`reset`]: #method.reset
+/// [`remove`]: #method.remove
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::prelude::*;
+/// use async_std::time::{DelayQueue, Duration};
+///
+/// let mut queue = DelayQueue::new();
+/// queue.insert("later", Duration::from_millis(20));
+/// queue.insert("sooner", Duration::from_millis(10));
+///
+/// assert_eq!(queue.next().await.unwrap().into_inner(), "sooner");
+/// assert_eq!(queue.next().await.unwrap().into_inner(), "later");
+/// #
+/// # })
+/// ```
+#[derive(Debug)]
+pub struct DelayQueue<T> {
+    /// The items in the queue.
+    entries: Slab<Entry<T>>,
+
+    /// Keys of the items ordered by deadline, with ties broken by insertion order.
+    deadlines: BTreeMap<(Instant, u64), usize>,
+
+    /// The timer, which is set to fire at `delay_deadline`.
+    delay: Delay,
+    delay_deadline: Option<Instant>,
+
+    /// The task polling the queue, which is woken up when an item becomes the first to expire.
+    waker: Option<Waker>,
+
+    /// Incremented on every insertion so that keys of removed items don't refer to new ones.
+    next_seq: u64,
+}
+
+/// An item in the queue.
+#[derive(Debug)]
+struct Entry<T> {
+    value: T,
+    deadline: Instant,
+    seq: u64,
+}
+
+/// A key referring to an item in a [`DelayQueue`].
+///
+/// Keys are returned when items are inserted and stay unique for the lifetime of the queue, so a
+/// key of an item that has expired or been removed never refers to another item.
+///
+/// [`DelayQueue`]: struct.DelayQueue.html
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Key {
+    index: usize,
+    seq: u64,
+}
+
+/// An item yielded by a [`DelayQueue`] once it has expired.
+///
+/// [`DelayQueue`]: struct.DelayQueue.html
+#[derive(Debug)]
+pub struct Expired<T> {
+    value: T,
+    key: Key,
+    deadline: Instant,
+}
+
+impl<T> Expired<T> {
+    /// Returns a reference to the item.
+    pub fn get_ref(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns a mutable reference to the item.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Consumes this `Expired`, returning the item.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Returns the key the item was inserted with.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Returns the deadline of the item.
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+}
+
+impl<T> DelayQueue<T> {
+    /// Creates an empty queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::DelayQueue;
+    ///
+    /// let queue: DelayQueue<i32> = DelayQueue::new();
+    /// ```
+    pub fn new() -> DelayQueue<T> {
+        DelayQueue {
+            entries: Slab::new(),
+            deadlines: BTreeMap::new(),
+            delay: Delay::new(Duration::from_secs(0)),
+            delay_deadline: None,
+            waker: None,
+            next_seq: 0,
+        }
+    }
+
+    /// Inserts an item that expires after `timeout`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::{DelayQueue, Duration};
+    ///
+    /// let mut queue = DelayQueue::new();
+    /// let key = queue.insert("connection 1", Duration::from_secs(30));
+    /// ```
+    pub fn insert(&mut self, value: T, timeout: Duration) -> Key {
+        self.insert_at(value, Instant::now() + timeout)
+    }
+
+    /// Inserts an item that expires at `deadline`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::{DelayQueue, Duration, Instant};
+    ///
+    /// let mut queue = DelayQueue::new();
+    /// let key = queue.insert_at("retry", Instant::now() + Duration::from_secs(1));
+    /// ```
+    pub fn insert_at(&mut self, value: T, deadline: Instant) -> Key {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        let index = self.entries.insert(Entry {
+            value,
+            deadline,
+            seq,
+        });
+        self.deadlines.insert((deadline, seq), index);
+        self.wake_if_first(deadline, seq);
+
+        Key { index, seq }
+    }
+
+    /// Removes an item before it expires.
+    ///
+    /// Returns `None` if the item has already expired or been removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::{DelayQueue, Duration};
+    ///
+    /// let mut queue = DelayQueue::new();
+    /// let key = queue.insert("connection 1", Duration::from_secs(30));
+    ///
+    /// assert_eq!(queue.remove(&key), Some("connection 1"));
+    /// assert_eq!(queue.remove(&key), None);
+    /// ```
+    pub fn remove(&mut self, key: &Key) -> Option<T> {
+        if !self.contains(key) {
+            return None;
+        }
+
+        let entry = self.entries.remove(key.index);
+        self.deadlines.remove(&(entry.deadline, entry.seq));
+        Some(entry.value)
+    }
+
+    /// Changes the deadline of an item so that it expires after `timeout` from now.
+    ///
+    /// Returns `false` if the item has already expired or been removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::{DelayQueue, Duration};
+    ///
+    /// let mut queue = DelayQueue::new();
+    /// let key = queue.insert("connection 1", Duration::from_secs(30));
+    ///
+    /// // The connection was used, so it's no longer idle.
+    /// assert!(queue.reset(&key, Duration::from_secs(30)));
+    /// ```
+    pub fn reset(&mut self, key: &Key, timeout: Duration) -> bool {
+        self.reset_at(key, Instant::now() + timeout)
+    }
+
+    /// Changes the deadline of an item so that it expires at `deadline`.
+    ///
+    /// Returns `false` if the item has already expired or been removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::{DelayQueue, Duration, Instant};
+    ///
+    /// let mut queue = DelayQueue::new();
+    /// let key = queue.insert("retry", Duration::from_secs(1));
+    ///
+    /// let deadline = Instant::now() + Duration::from_secs(2);
+    /// assert!(queue.reset_at(&key, deadline));
+    /// assert_eq!(queue.deadline(&key), Some(deadline));
+    /// ```
+    pub fn reset_at(&mut self, key: &Key, deadline: Instant) -> bool {
+        if !self.contains(key) {
+            return false;
+        }
+
+        let entry = &mut self.entries[key.index];
+        self.deadlines.remove(&(entry.deadline, entry.seq));
+        self.deadlines.insert((deadline, entry.seq), key.index);
+        entry.deadline = deadline;
+        self.wake_if_first(deadline, key.seq);
+
+        true
+    }
+
+    /// Returns the deadline of an item, or `None` if it has already expired or been removed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::{DelayQueue, Duration, Instant};
+    ///
+    /// let mut queue = DelayQueue::new();
+    /// let deadline = Instant::now() + Duration::from_secs(1);
+    /// let key = queue.insert_at("retry", deadline);
+    ///
+    /// assert_eq!(queue.deadline(&key), Some(deadline));
+    /// ```
+    pub fn deadline(&self, key: &Key) -> Option<Instant> {
+        if self.contains(key) {
+            Some(self.entries[key.index].deadline)
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the item hasn't expired or been removed yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::{DelayQueue, Duration};
+    ///
+    /// let mut queue = DelayQueue::new();
+    /// let key = queue.insert("retry", Duration::from_secs(1));
+    ///
+    /// assert!(queue.contains(&key));
+    /// queue.remove(&key);
+    /// assert!(!queue.contains(&key));
+    /// ```
+    pub fn contains(&self, key: &Key) -> bool {
+        match self.entries.get(key.index) {
+            Some(entry) => entry.seq == key.seq,
+            None => false,
+        }
+    }
+
+    /// Returns the number of items in the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::{DelayQueue, Duration};
+    ///
+    /// let mut queue = DelayQueue::new();
+    /// queue.insert("retry", Duration::from_secs(1));
+    /// assert_eq!(queue.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the queue is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::DelayQueue;
+    ///
+    /// let queue: DelayQueue<i32> = DelayQueue::new();
+    /// assert!(queue.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all items from the queue.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::{DelayQueue, Duration};
+    ///
+    /// let mut queue = DelayQueue::new();
+    /// queue.insert("retry", Duration::from_secs(1));
+    ///
+    /// queue.clear();
+    /// assert!(queue.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.deadlines.clear();
+    }
+
+    /// Wakes up the task polling the queue if the given item is now the first one to expire, so
+    /// that the timer gets moved.
+    fn wake_if_first(&mut self, deadline: Instant, seq: u64) {
+        if self.deadlines.keys().next() == Some(&(deadline, seq)) {
+            if let Some(w) = self.waker.take() {
+                w.wake();
+            }
+        }
+    }
+}
+
+impl<T> Default for DelayQueue<T> {
+    fn default() -> DelayQueue<T> {
+        DelayQueue::new()
+    }
+}
+
+impl<T> Unpin for DelayQueue<T> {}
+
+impl<T> Stream for DelayQueue<T> {
+    type Item = Expired<T>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            let (deadline, seq, index) = match this.deadlines.iter().next() {
+                Some((&(deadline, seq), &index)) => (deadline, seq, index),
+                None => {
+                    this.waker = Some(cx.waker().clone());
+                    return Poll::Pending;
+                }
+            };
+
+            if deadline <= Instant::now() {
+                this.deadlines.remove(&(deadline, seq));
+                let entry = this.entries.remove(index);
+
+                return Poll::Ready(Some(Expired {
+                    value: entry.value,
+                    key: Key { index, seq },
+                    deadline,
+                }));
+            }
+
+            if this.delay_deadline != Some(deadline) {
+                this.delay.reset(deadline);
+                this.delay_deadline = Some(deadline);
+            }
+
+            // Inserting an item that expires earlier wakes this task up.
+            this.waker = Some(cx.waker().clone());
+            futures_core::ready!(Pin::new(&mut this.delay).poll(cx));
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::convert::TryFrom;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+use futures_timer::Delay;
+
+use crate::future::{self, Future};
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// Creates a stream that ticks every `period`, starting one `period` from now.
+///
+/// Each tick yields the instant it was scheduled for. Ticks may arrive slightly later than
+/// scheduled but never earlier. If ticks are missed because the stream wasn't polled in time,
+/// the stream catches up according to its [`MissedTickBehavior`].
+///
+/// [`MissedTickBehavior`]: enum.MissedTickBehavior.html
+///
+/// # Panics
+///
+/// This function panics if `period` is zero.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::time::{interval, Duration};
+///
+/// let mut interval = interval(Duration::from_millis(10));
+/// let first = interval.tick().await;
+/// let second = interval.tick().await;
+/// assert_eq!(second - first, Duration::from_millis(10));
+/// #
+/// # })
+/// ```
+pub fn interval(period: Duration) -> Interval {
+    interval_at(Instant::now() + period, period)
+}
+
+/// Creates a stream that ticks every `period`, starting at `start`.
+///
+/// See [`interval`] for more.
+///
+/// [`interval`]: fn.interval.html
+///
+/// # Panics
+///
+/// This function panics if `period` is zero.
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::time::{interval_at, Duration, Instant};
+///
+/// // Tick right away, and then every 10 milliseconds.
+/// let start = Instant::now();
+/// let mut interval = interval_at(start, Duration::from_millis(10));
+/// assert_eq!(interval.tick().await, start);
+/// #
+/// # })
+/// ```
+pub fn interval_at(start: Instant, period: Duration) -> Interval {
+    assert!(period > Duration::from_secs(0), "`period` must be non-zero");
+
+    let mut delay = Delay::new(Duration::from_secs(0));
+    delay.reset(start);
+
+    Interval {
+        delay,
+        deadline: start,
+        period,
+        missed_tick_behavior: MissedTickBehavior::default(),
+    }
+}
+
+/// What an [`Interval`] does when ticks were missed.
+///
+/// A tick is missed when the stream is polled so late that the next tick is already due too.
+///
+/// [`Interval`]: struct.Interval.html
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum MissedTickBehavior {
+    /// Yields the missed ticks right away, one after another, until the stream has caught up.
+    ///
+    /// This keeps the number of ticks over a long time equal to the elapsed time divided by the
+    /// period. This is the default.
+    #[default]
+    Burst,
+
+    /// Schedules the next tick one period after the late tick.
+    ///
+    /// The missed ticks are dropped, and all later ticks are shifted.
+    Delay,
+
+    /// Skips the missed ticks and schedules the next tick on the original schedule.
+    Skip,
+}
+
+/// A stream that ticks periodically.
+///
+/// This stream is created by the [`interval`] and [`interval_at`] functions. See their
+/// documentation for more.
+///
+/// [`interval`]: fn.interval.html
+/// [`interval_at`]: fn.interval_at.html
+#[derive(Debug)]
+pub struct Interval {
+    delay: Delay,
+
+    /// The instant the next tick is scheduled for.
+    deadline: Instant,
+
+    period: Duration,
+    missed_tick_behavior: MissedTickBehavior,
+}
+
+impl Interval {
+    /// Waits for the next tick and returns the instant it was scheduled for.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::time::{interval, Duration};
+    ///
+    /// let mut interval = interval(Duration::from_millis(10));
+    /// interval.tick().await;
+    /// #
+    /// # })
+    /// ```
+    pub async fn tick(&mut self) -> Instant {
+        future::poll_fn(|cx| self.poll_tick(cx)).await
+    }
+
+    /// Polls for the next tick.
+    ///
+    /// This is the same as polling the stream, except the result is not wrapped in `Some`.
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+        futures_core::ready!(Pin::new(&mut self.delay).poll(cx));
+
+        let tick = self.deadline;
+        let now = Instant::now();
+        let next = tick + self.period;
+
+        self.deadline = if next > now {
+            next
+        } else {
+            match self.missed_tick_behavior {
+                MissedTickBehavior::Burst => next,
+                MissedTickBehavior::Delay => now + self.period,
+                MissedTickBehavior::Skip => {
+                    let period = self.period.as_nanos();
+                    let missed = (now - tick).as_nanos() / period;
+
+                    // If the next tick on the original schedule can't be represented, fall back
+                    // to delaying.
+                    (missed + 1)
+                        .checked_mul(period)
+                        .and_then(|n| {
+                            let secs = u64::try_from(n / 1_000_000_000).ok()?;
+                            tick.checked_add(Duration::new(secs, (n % 1_000_000_000) as u32))
+                        })
+                        .unwrap_or(now + self.period)
+                }
+            }
+        };
+        self.delay.reset(self.deadline);
+
+        Poll::Ready(tick)
+    }
+
+    /// Restarts the interval so that the next tick happens one period from now.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # async_std::task::block_on(async {
+    /// #
+    /// use async_std::time::{interval, Duration, Instant};
+    ///
+    /// let mut interval = interval(Duration::from_millis(10));
+    /// interval.reset();
+    /// assert!(interval.tick().await > Instant::now() - Duration::from_millis(10));
+    /// #
+    /// # })
+    /// ```
+    pub fn reset(&mut self) {
+        self.deadline = Instant::now() + self.period;
+        self.delay.reset(self.deadline);
+    }
+
+    /// Returns the period of the interval.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::{interval, Duration};
+    ///
+    /// let interval = interval(Duration::from_secs(1));
+    /// assert_eq!(interval.period(), Duration::from_secs(1));
+    /// ```
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// Returns what the interval does when ticks were missed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::{interval, Duration, MissedTickBehavior};
+    ///
+    /// let interval = interval(Duration::from_secs(1));
+    /// assert_eq!(interval.missed_tick_behavior(), MissedTickBehavior::Burst);
+    /// ```
+    pub fn missed_tick_behavior(&self) -> MissedTickBehavior {
+        self.missed_tick_behavior
+    }
+
+    /// Sets what the interval does when ticks were missed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use async_std::time::{interval, Duration, MissedTickBehavior};
+    ///
+    /// let mut interval = interval(Duration::from_secs(1));
+    /// interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+    /// ```
+    pub fn set_missed_tick_behavior(&mut self, behavior: MissedTickBehavior) {
+        self.missed_tick_behavior = behavior;
+    }
+}
+
+impl Stream for Interval {
+    type Item = Instant;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Instant>> {
+        self.get_mut().poll_tick(cx).map(Some)
+    }
+}
@@ -0,0 +1,52 @@
+//! Temporal quantification.
+//!
+//! This module is an async extension of [`std::time`]. It re-exports [`Duration`] and
+//! [`Instant`], and adds ways of waiting for them:
+//!
+//! * [`sleep`] and [`sleep_until`] wait for a duration or until a deadline.
+//! * [`interval`] creates a stream that ticks periodically.
+//! * [`Future::timeout`] and [`Stream::timeout`] put a time limit on futures and streams.
+//! * [`DelayQueue`] yields items once their deadlines have passed.
+//!
+//! All timers are driven by the same global timer thread.
+//!
+//! [`std::time`]: https://doc.rust-lang.org/std/time/index.html
+//! [`Duration`]: struct.Duration.html
+//! [`Instant`]: struct.Instant.html
+//! [`sleep`]: fn.sleep.html
+//! [`sleep_until`]: fn.sleep_until.html
+//! [`interval`]: fn.interval.html
+//! [`Future::timeout`]: ../future/trait.Future.html#method.timeout
+//! [`Stream::timeout`]: ../stream/trait.Stream.html#method.timeout
+//! [`DelayQueue`]: struct.DelayQueue.html
+//!
+//! # Examples
+//!
+//! ```
+//! # async_std::task::block_on(async {
+//! #
+//! use async_std::prelude::*;
+//! use async_std::time::{interval, Duration};
+//!
+//! let mut ticks = interval(Duration::from_millis(10)).take(3);
+//! while let Some(instant) = ticks.next().await {
+//!     println!("tick at {:?}", instant);
+//! }
+//! #
+//! # })
+//! ```
+
+pub use std::time::{Duration, Instant};
+
+pub use crate::future::TimeoutError;
+pub use crate::task::sleep;
+
+pub use delay_queue::{DelayQueue, Expired, Key};
+pub use interval::{interval, interval_at, Interval, MissedTickBehavior};
+pub use sleep_until::sleep_until;
+pub use timeout::Timeout;
+
+mod delay_queue;
+mod interval;
+mod sleep_until;
+mod timeout;
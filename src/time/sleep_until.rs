@@ -0,0 +1,31 @@
+use std::time::{Duration, Instant};
+
+use futures_timer::Delay;
+
+/// Sleeps until a deadline.
+///
+/// This function might sleep until slightly after the deadline but never before. If the deadline
+/// has already passed, it returns right away.
+///
+/// See also: [`sleep`].
+///
+/// [`sleep`]: fn.sleep.html
+///
+/// # Examples
+///
+/// ```
+/// # async_std::task::block_on(async {
+/// #
+/// use async_std::time::{sleep_until, Duration, Instant};
+///
+/// let deadline = Instant::now() + Duration::from_millis(10);
+/// sleep_until(deadline).await;
+/// assert!(Instant::now() >= deadline);
+/// #
+/// # })
+/// ```
+pub async fn sleep_until(deadline: Instant) {
+    let mut delay = Delay::new(Duration::from_secs(0));
+    delay.reset(deadline);
+    delay.await
+}
@@ -0,0 +1,80 @@
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+use futures_timer::Delay;
+
+use crate::future::{Future, TimeoutError};
+use crate::stream::Stream;
+use crate::task::{Context, Poll};
+
+/// A future or stream with a time limit.
+///
+/// This type is created by [`Future::timeout`] and [`Stream::timeout`]. See their documentation
+/// for more.
+///
+/// [`Future::timeout`]: ../future/trait.Future.html#method.timeout
+/// [`Stream::timeout`]: ../stream/trait.Stream.html#method.timeout
+#[derive(Debug)]
+#[must_use = "futures and streams do nothing unless polled"]
+pub struct Timeout<T> {
+    inner: T,
+    dur: Duration,
+    delay: Delay,
+}
+
+impl<T> Timeout<T> {
+    pin_utils::unsafe_pinned!(inner: T);
+    pin_utils::unsafe_pinned!(delay: Delay);
+
+    pub(crate) fn new(inner: T, dur: Duration) -> Timeout<T> {
+        Timeout {
+            inner,
+            dur,
+            delay: Delay::new(dur),
+        }
+    }
+
+    /// Returns a reference to the inner future or stream.
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Consumes this `Timeout`, returning the inner future or stream.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: Future> Future for Timeout<T> {
+    type Output = Result<T::Output, TimeoutError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match self.as_mut().inner().poll(cx) {
+            Poll::Ready(v) => Poll::Ready(Ok(v)),
+            Poll::Pending => match self.delay().poll(cx) {
+                Poll::Ready(_) => Poll::Ready(Err(TimeoutError::new())),
+                Poll::Pending => Poll::Pending,
+            },
+        }
+    }
+}
+
+impl<T: Stream> Stream for Timeout<T> {
+    type Item = Result<T::Item, TimeoutError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let res = match self.as_mut().inner().poll_next(cx) {
+            Poll::Ready(Some(v)) => Some(Ok(v)),
+            Poll::Ready(None) => None,
+            Poll::Pending => match self.as_mut().delay().poll(cx) {
+                Poll::Ready(_) => Some(Err(TimeoutError::new())),
+                Poll::Pending => return Poll::Pending,
+            },
+        };
+
+        // Give the next item the full duration.
+        let deadline = Instant::now() + self.dur;
+        self.as_mut().delay().reset(deadline);
+        Poll::Ready(res)
+    }
+}
@@ -0,0 +1,20 @@
+#![cfg(feature = "attributes")]
+
+use std::time::Duration;
+
+use async_std::task;
+
+#[async_std::test]
+async fn test() {
+    task::sleep(Duration::from_millis(1)).await;
+    assert_eq!(task::spawn(async { 1 + 1 }).await, 2);
+}
+
+#[async_std::test]
+async fn test_with_result() -> std::io::Result<()> {
+    let dir = tempdir::TempDir::new("attributes")?;
+    let path = dir.path().join("file");
+    async_std::fs::write(&path, b"hello").await?;
+    assert_eq!(async_std::fs::read(&path).await?, b"hello");
+    Ok(())
+}
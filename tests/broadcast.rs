@@ -0,0 +1,95 @@
+#![cfg(feature = "unstable")]
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::sync::broadcast::{self, LagPolicy, RecvError, TryRecvError};
+use async_std::task::{self, Poll};
+
+#[test]
+fn every_receiver_sees_every_message() {
+    task::block_on(async {
+        let (s, r) = broadcast::channel(4);
+        let mut tasks = Vec::new();
+
+        for _ in 0..3 {
+            let r = r.clone();
+            tasks.push(task::spawn(async move {
+                let mut v = Vec::new();
+                while let Ok(n) = r.recv().await {
+                    v.push(n);
+                }
+                v
+            }));
+        }
+        drop(r);
+
+        for i in 0..4 {
+            assert_eq!(s.send(i), Ok(3));
+        }
+        drop(s);
+
+        for t in tasks {
+            assert_eq!(t.await, vec![0, 1, 2, 3]);
+        }
+    })
+}
+
+#[test]
+fn waiting_receiver_is_woken() {
+    task::block_on(async {
+        let (s, r) = broadcast::channel(1);
+
+        let mut recv = Box::pin(r.recv());
+        future::poll_fn(|cx| {
+            assert!(recv.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+
+        s.send("hello").unwrap();
+        assert_eq!(recv.await, Ok("hello"));
+    })
+}
+
+#[test]
+fn lag_policies() {
+    task::block_on(async {
+        let (s, r1) = broadcast::channel(2);
+        let mut r2 = r1.clone();
+        r2.set_lag_policy(LagPolicy::Skip);
+
+        for i in 0..5 {
+            s.send(i).unwrap();
+        }
+        assert_eq!(r1.len(), 5);
+
+        assert_eq!(r1.try_recv(), Err(TryRecvError::Lagged(3)));
+        assert_eq!(r1.try_recv(), Ok(3));
+        assert_eq!(r2.recv().await, Ok(3));
+
+        assert_eq!(r1.recv().await, Ok(4));
+        assert_eq!(r2.recv().await, Ok(4));
+        assert!(r1.is_empty());
+
+        drop(s);
+        assert_eq!(r1.recv().await, Err(RecvError::Closed));
+        assert_eq!(r2.try_recv(), Err(TryRecvError::Closed));
+    })
+}
+
+#[test]
+fn subscribe_and_no_receivers() {
+    task::block_on(async {
+        let (s, r) = broadcast::channel(2);
+        s.send(1).unwrap();
+        drop(r);
+
+        assert_eq!(s.send(2).unwrap_err().into_inner(), 2);
+
+        // A new receiver only sees messages sent after it subscribed.
+        let r = s.subscribe();
+        assert_eq!(r.try_recv(), Err(TryRecvError::Empty));
+        s.send(3).unwrap();
+        assert_eq!(r.recv().await, Ok(3));
+    })
+}
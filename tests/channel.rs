@@ -0,0 +1,158 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::sync::{channel, unbounded, RecvError, TryRecvError, TrySendError};
+use async_std::task::{self, Poll};
+
+#[test]
+fn smoke() {
+    task::block_on(async {
+        let (s, r) = channel(1);
+
+        s.send(7).await.unwrap();
+        assert_eq!(r.try_recv(), Ok(7));
+
+        s.send(8).await.unwrap();
+        assert_eq!(r.recv().await, Ok(8));
+
+        drop(s);
+        assert_eq!(r.recv().await, Err(RecvError));
+        assert_eq!(r.try_recv(), Err(TryRecvError::Disconnected));
+    })
+}
+
+#[test]
+#[should_panic(expected = "capacity must be positive")]
+fn zero_capacity() {
+    let _ = channel::<()>(0);
+}
+
+#[test]
+fn backpressure() {
+    task::block_on(async {
+        let (s, r) = channel(2);
+        s.send(1).await.unwrap();
+        s.send(2).await.unwrap();
+        assert!(s.is_full());
+        assert_eq!(s.try_send(3), Err(TrySendError::Full(3)));
+
+        // A full channel makes the sender wait until a message is received.
+        let mut send = Box::pin(s.send(3));
+        future::poll_fn(|cx| {
+            assert!(send.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+
+        assert_eq!(r.recv().await, Ok(1));
+        send.await.unwrap();
+        assert_eq!(r.len(), 2);
+    })
+}
+
+#[test]
+fn cancelled_send() {
+    task::block_on(async {
+        let (s, r) = channel(1);
+        s.send(1).await.unwrap();
+
+        let mut first = Box::pin(s.send(2));
+        future::poll_fn(|cx| {
+            assert!(first.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+
+        let s2 = s.clone();
+        let second = task::spawn(async move { s2.send(3).await });
+        task::sleep(Duration::from_millis(50)).await;
+
+        // Receiving wakes up the first sender, which gives up and passes the free slot on.
+        assert_eq!(r.recv().await, Ok(1));
+        drop(first);
+
+        future::timeout(Duration::from_secs(5), second)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(r.recv().await, Ok(3));
+    })
+}
+
+#[test]
+fn disconnected_receivers() {
+    task::block_on(async {
+        let (s, r) = channel(1);
+        s.send(1).await.unwrap();
+
+        // A sender waiting for room learns that the last receiver is gone.
+        let s2 = s.clone();
+        let blocked = task::spawn(async move { s2.send(2).await });
+        task::sleep(Duration::from_millis(50)).await;
+        drop(r);
+
+        assert_eq!(blocked.await.unwrap_err().into_inner(), 2);
+        assert_eq!(s.try_send(3), Err(TrySendError::Disconnected(3)));
+    })
+}
+
+#[test]
+fn stream() {
+    task::block_on(async {
+        let (s, r) = unbounded();
+        assert_eq!(s.capacity(), None);
+
+        task::spawn(async move {
+            for i in 0..100 {
+                s.send(i).await.unwrap();
+            }
+        });
+
+        let v: Vec<i32> = r.collect().await;
+        assert_eq!(v, (0..100).collect::<Vec<_>>());
+    })
+}
+
+#[test]
+fn mpmc() {
+    const COUNT: usize = 25_000;
+    const TASKS: usize = 4;
+
+    task::block_on(async {
+        let (s, r) = channel::<usize>(3);
+        let received = Arc::new(AtomicUsize::new(0));
+        let mut tasks = Vec::new();
+
+        for _ in 0..TASKS {
+            let r = r.clone();
+            let received = received.clone();
+            tasks.push(task::spawn(async move {
+                while let Ok(n) = r.recv().await {
+                    received.fetch_add(n, Ordering::SeqCst);
+                }
+            }));
+        }
+        drop(r);
+
+        for _ in 0..TASKS {
+            let s = s.clone();
+            task::spawn(async move {
+                for i in 0..COUNT {
+                    s.send(i).await.unwrap();
+                }
+            });
+        }
+        drop(s);
+
+        for t in tasks {
+            t.await;
+        }
+        let expected = TASKS * COUNT * (COUNT - 1) / 2;
+        assert_eq!(received.load(Ordering::SeqCst), expected);
+    })
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::sync::{Condvar, Mutex};
+use async_std::task;
+
+#[test]
+fn wait_and_notify_one() {
+    task::block_on(async {
+        let pair = Arc::new((Mutex::new(false), Condvar::new()));
+        let pair2 = pair.clone();
+
+        task::spawn(async move {
+            let (lock, cvar) = &*pair2;
+            task::sleep(Duration::from_millis(10)).await;
+            *lock.lock().await = true;
+            cvar.notify_one();
+        });
+
+        let (lock, cvar) = &*pair;
+        let mut ready = lock.lock().await;
+        while !*ready {
+            ready = cvar.wait(ready).await;
+        }
+    })
+}
+
+#[test]
+fn notify_all() {
+    task::block_on(async {
+        let pair = Arc::new((Mutex::new(0), Condvar::new()));
+        let mut tasks = Vec::new();
+
+        for _ in 0..10 {
+            let pair = pair.clone();
+            tasks.push(task::spawn(async move {
+                let (lock, cvar) = &*pair;
+                let mut n = cvar.wait_until(lock.lock().await, |n| *n > 0).await;
+                *n += 1;
+            }));
+        }
+
+        let (lock, cvar) = &*pair;
+        task::sleep(Duration::from_millis(10)).await;
+        *lock.lock().await = 1;
+        cvar.notify_all();
+
+        for t in tasks {
+            t.await;
+        }
+        assert_eq!(*lock.lock().await, 11);
+    })
+}
+
+#[test]
+fn wait_timeout() {
+    task::block_on(async {
+        let pair = Arc::new((Mutex::new(false), Condvar::new()));
+        let (lock, cvar) = &*pair;
+
+        let (guard, res) = cvar
+            .wait_timeout(lock.lock().await, Duration::from_millis(10))
+            .await;
+        assert!(res.timed_out());
+
+        // The lock is released while waiting.
+        let pair2 = pair.clone();
+        task::spawn(async move {
+            let (lock, cvar) = &*pair2;
+            *lock.lock().await = true;
+            cvar.notify_one();
+        });
+        let (guard, res) = cvar.wait_timeout(guard, Duration::from_secs(10)).await;
+        assert!(!res.timed_out());
+        assert!(*guard);
+    })
+}
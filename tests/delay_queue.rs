@@ -0,0 +1,81 @@
+#![cfg(feature = "unstable")]
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::sync::{Arc, Mutex};
+use async_std::task;
+use async_std::time::{DelayQueue, Duration, Instant};
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn expires_in_order() {
+    task::block_on(async {
+        let start = Instant::now();
+        let mut queue = DelayQueue::new();
+        queue.insert(3, ms(30));
+        queue.insert(1, ms(10));
+        queue.insert(2, ms(20));
+
+        for i in 1..=3 {
+            let expired = queue.next().await.unwrap();
+            assert_eq!(expired.into_inner(), i);
+            assert!(Instant::now() >= start + ms(10 * i));
+        }
+        assert!(queue.is_empty());
+    })
+}
+
+#[test]
+fn reset_and_remove() {
+    task::block_on(async {
+        let mut queue = DelayQueue::new();
+        let a = queue.insert("a", ms(10));
+        let b = queue.insert("b", ms(20));
+        let c = queue.insert("c", ms(30));
+
+        assert!(queue.reset(&a, ms(40)));
+        assert_eq!(queue.remove(&b), Some("b"));
+
+        let expired = queue.next().await.unwrap();
+        assert_eq!(expired.key(), c);
+        assert_eq!(*expired.get_ref(), "c");
+        assert_eq!(queue.next().await.unwrap().into_inner(), "a");
+
+        // Keys of expired items don't refer to new items.
+        let d = queue.insert("d", ms(10));
+        assert!(!queue.contains(&a));
+        assert!(!queue.reset(&a, ms(10)));
+        assert_eq!(queue.remove(&c), None);
+        assert!(queue.contains(&d));
+    })
+}
+
+#[test]
+fn insert_while_waiting() {
+    task::block_on(async {
+        let queue = Arc::new(Mutex::new(DelayQueue::new()));
+        queue.lock().await.insert("late", Duration::from_secs(10));
+
+        let q = queue.clone();
+        task::spawn(async move {
+            task::sleep(ms(10)).await;
+            q.lock().await.insert("early", ms(10));
+        });
+
+        // The early item moves the timer forward.
+        let next = future::timeout(Duration::from_secs(5), async {
+            loop {
+                let mut queue = queue.lock().await;
+                let res = future::timeout(ms(5), queue.next()).await;
+                if let Ok(expired) = res {
+                    return expired.unwrap().into_inner();
+                }
+            }
+        })
+        .await;
+        assert_eq!(next, Ok("early"));
+    })
+}
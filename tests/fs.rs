@@ -0,0 +1,1100 @@
+use async_std::fs::{self, DirBuilder, File, OpenOptions};
+use async_std::io;
+use async_std::prelude::*;
+use async_std::task;
+
+use tempdir::TempDir;
+
+#[test]
+fn create_dir() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("dir");
+
+    task::block_on(async {
+        fs::create_dir(&path).await?;
+        assert!(path.is_dir());
+
+        // Creating the same directory twice is an error.
+        let err = fs::create_dir(&path).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+        Ok(())
+    })
+}
+
+#[test]
+fn create_dir_all() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a").join("b").join("c");
+
+    task::block_on(async {
+        fs::create_dir_all(&path).await?;
+        assert!(path.is_dir());
+
+        // Recursive creation succeeds if the directory already exists.
+        fs::create_dir_all(&path).await?;
+        Ok(())
+    })
+}
+
+#[test]
+fn dir_builder() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a").join("b");
+
+    task::block_on(async {
+        assert!(DirBuilder::new().create(&path).await.is_err());
+
+        DirBuilder::new().recursive(true).create(&path).await?;
+        assert!(path.is_dir());
+        Ok(())
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn dir_builder_mode() -> io::Result<()> {
+    use async_std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("dir");
+
+    task::block_on(async {
+        DirBuilder::new().mode(0o700).create(&path).await?;
+        let mode = std::fs::metadata(&path)?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+        Ok(())
+    })
+}
+
+#[test]
+fn metadata() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+    std::fs::write(&path, b"hello")?;
+
+    task::block_on(async {
+        let meta = fs::metadata(&path).await?;
+        assert!(meta.is_file());
+        assert!(meta.file_type().is_file());
+        assert_eq!(meta.len(), 5);
+        assert!(!meta.permissions().readonly());
+        assert!(meta.modified().is_ok());
+
+        let meta = fs::metadata(tmp.path()).await?;
+        assert!(meta.is_dir());
+
+        let err = fs::metadata(tmp.path().join("missing")).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        Ok(())
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink_metadata() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+    let link = tmp.path().join("b.txt");
+    std::fs::write(&path, b"hello")?;
+    std::os::unix::fs::symlink(&path, &link)?;
+
+    task::block_on(async {
+        let meta = fs::symlink_metadata(&link).await?;
+        assert!(meta.file_type().is_symlink());
+
+        let meta = fs::metadata(&link).await?;
+        assert!(meta.is_file());
+        Ok(())
+    })
+}
+
+#[test]
+fn canonicalize() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    std::fs::create_dir(tmp.path().join("dir"))?;
+    std::fs::write(tmp.path().join("a.txt"), b"")?;
+
+    task::block_on(async {
+        let expected = std::fs::canonicalize(tmp.path().join("a.txt"))?;
+        let path = fs::canonicalize(tmp.path().join("dir").join("..").join("a.txt")).await?;
+        assert_eq!(path, expected.into());
+
+        let err = fs::canonicalize(tmp.path().join("missing")).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        Ok(())
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn read_link() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+    let link = tmp.path().join("b.txt");
+    std::fs::write(&path, b"")?;
+    std::os::unix::fs::symlink(&path, &link)?;
+
+    task::block_on(async {
+        assert_eq!(fs::read_link(&link).await?, path.clone().into());
+        assert!(fs::read_link(&path).await.is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn read_dir() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    std::fs::write(tmp.path().join("a.txt"), b"hello")?;
+    std::fs::create_dir(tmp.path().join("b"))?;
+
+    task::block_on(async {
+        let mut entries = Vec::new();
+        let mut dir = fs::read_dir(tmp.path()).await?;
+        while let Some(entry) = dir.next().await {
+            let entry = entry?;
+            let is_dir = entry.file_type().await?.is_dir();
+            let len = entry.metadata().await?.len();
+            assert_eq!(entry.path(), tmp.path().join(entry.file_name()).into());
+            entries.push((entry.file_name().into_string().unwrap(), is_dir, len));
+        }
+        entries.sort();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], ("a.txt".to_string(), false, 5));
+        assert_eq!((&*entries[1].0, entries[1].1), ("b", true));
+
+        // Reading a missing directory is an error.
+        assert!(fs::read_dir(tmp.path().join("missing")).await.is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn copy() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let from = tmp.path().join("a.txt");
+    let to = tmp.path().join("b.txt");
+    std::fs::write(&from, b"hello")?;
+    std::fs::write(&to, b"longer old contents")?;
+
+    task::block_on(async {
+        assert_eq!(fs::copy(&from, &to).await?, 5);
+        assert_eq!(fs::read(&to).await?, b"hello");
+        assert!(from.exists());
+        Ok(())
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn copy_permissions() -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = TempDir::new("async-std")?;
+    let from = tmp.path().join("a.txt");
+    let to = tmp.path().join("b.txt");
+    std::fs::write(&from, vec![7; 1 << 16])?;
+    std::fs::set_permissions(&from, std::fs::Permissions::from_mode(0o640))?;
+    std::fs::write(&to, b"old contents that are overwritten")?;
+
+    task::block_on(async {
+        assert_eq!(fs::copy(&from, &to).await?, 1 << 16);
+        assert_eq!(fs::read(&to).await?, vec![7; 1 << 16]);
+        assert_eq!(std::fs::metadata(&to)?.permissions().mode() & 0o777, 0o640);
+
+        // Copying a directory is an error.
+        assert!(fs::copy(tmp.path(), &to).await.is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn rename() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let from = tmp.path().join("a.txt");
+    let to = tmp.path().join("b.txt");
+    std::fs::write(&from, b"hello")?;
+
+    task::block_on(async {
+        fs::rename(&from, &to).await?;
+        assert!(!from.exists());
+        assert_eq!(fs::read_to_string(&to).await?, "hello");
+        Ok(())
+    })
+}
+
+#[test]
+fn remove_file() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+    std::fs::write(&path, b"")?;
+
+    task::block_on(async {
+        fs::remove_file(&path).await?;
+        assert!(!path.exists());
+
+        let err = fs::remove_file(&path).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        Ok(())
+    })
+}
+
+#[test]
+fn remove_dir() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("dir");
+    std::fs::create_dir(&path)?;
+    std::fs::write(path.join("a.txt"), b"")?;
+
+    task::block_on(async {
+        // Only empty directories can be removed.
+        assert!(fs::remove_dir(&path).await.is_err());
+
+        fs::remove_file(path.join("a.txt")).await?;
+        fs::remove_dir(&path).await?;
+        assert!(!path.exists());
+        Ok(())
+    })
+}
+
+#[test]
+fn remove_dir_all() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("dir");
+    std::fs::create_dir_all(path.join("a").join("b"))?;
+    std::fs::write(path.join("a").join("b").join("c.txt"), b"")?;
+
+    task::block_on(async {
+        fs::remove_dir_all(&path).await?;
+        assert!(!path.exists());
+        Ok(())
+    })
+}
+
+#[test]
+fn hard_link() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+    let link = tmp.path().join("b.txt");
+    std::fs::write(&path, b"hello")?;
+
+    task::block_on(async {
+        fs::hard_link(&path, &link).await?;
+        fs::remove_file(&path).await?;
+        assert_eq!(fs::read(&link).await?, b"hello");
+        Ok(())
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn symlink() -> io::Result<()> {
+    use async_std::os::unix::fs::symlink;
+
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+    let link = tmp.path().join("b.txt");
+    std::fs::write(&path, b"hello")?;
+
+    task::block_on(async {
+        symlink(&path, &link).await?;
+        assert!(fs::symlink_metadata(&link).await?.file_type().is_symlink());
+        assert_eq!(fs::read(&link).await?, b"hello");
+        Ok(())
+    })
+}
+
+#[test]
+fn set_permissions() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+    std::fs::write(&path, b"")?;
+
+    task::block_on(async {
+        let mut perm = fs::metadata(&path).await?.permissions();
+        perm.set_readonly(true);
+        fs::set_permissions(&path, perm).await?;
+        assert!(fs::metadata(&path).await?.permissions().readonly());
+        Ok(())
+    })
+}
+
+#[cfg(unix)]
+#[test]
+fn set_permissions_mode() -> io::Result<()> {
+    use async_std::fs::Permissions;
+    use async_std::os::unix::fs::PermissionsExt;
+
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+    std::fs::write(&path, b"")?;
+
+    task::block_on(async {
+        fs::set_permissions(&path, Permissions::from_mode(0o640)).await?;
+        let perm = fs::metadata(&path).await?.permissions();
+        assert_eq!(perm.mode() & 0o777, 0o640);
+        Ok(())
+    })
+}
+
+#[test]
+fn open_options() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+
+    task::block_on(async {
+        // Opening a missing file without `create` is an error.
+        let err = OpenOptions::new()
+            .write(true)
+            .open(&path)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        fs::write(&path, b"hello world").await?;
+
+        let mut file = OpenOptions::new().write(true).open(&path).await?;
+        file.write_all(b"HELLO").await?;
+        file.flush().await?;
+        drop(file);
+        assert_eq!(fs::read_to_string(&path).await?, "HELLO world");
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .open(&path)
+            .await?;
+        file.write_all(b"bye").await?;
+        file.flush().await?;
+        drop(file);
+        assert_eq!(fs::read_to_string(&path).await?, "bye");
+
+        let mut file = OpenOptions::new().read(true).open(&path).await?;
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).await?;
+        assert_eq!(contents, "bye");
+        Ok(())
+    })
+}
+
+#[test]
+fn file_sync() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+
+    task::block_on(async {
+        let mut file = File::create(&path).await?;
+        file.write_all(b"hello").await?;
+        file.sync_data().await?;
+        assert_eq!(std::fs::read(&path)?, b"hello");
+
+        file.write_all(b" world").await?;
+        file.sync_all().await?;
+        assert_eq!(std::fs::read(&path)?, b"hello world");
+        Ok(())
+    })
+}
+
+#[test]
+fn file_set_len() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+
+    task::block_on(async {
+        let mut file = File::create(&path).await?;
+        file.write_all(b"hello world").await?;
+
+        // Pending writes are flushed before truncating.
+        file.set_len(5).await?;
+        assert_eq!(file.metadata().await?.len(), 5);
+        assert_eq!(std::fs::read(&path)?, b"hello");
+
+        // Extending the file fills it with zeros.
+        file.set_len(8).await?;
+        assert_eq!(std::fs::read(&path)?, b"hello\0\0\0");
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn file_append_and_create_new() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.log");
+
+    task::block_on(async {
+        let mut file = File::create_new(&path).await?;
+        file.write_all(b"first\n").await?;
+        drop(file);
+
+        let err = File::create_new(&path).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+
+        // Records from two appenders are written whole and in order.
+        let mut a = io::BufWriter::with_capacity(64, File::append(&path).await?);
+        let mut b = io::BufWriter::with_capacity(64, File::append(&path).await?);
+        for i in 0..10 {
+            a.write_all(format!("a{}\n", i).as_bytes()).await?;
+            a.flush().await?;
+            b.write_all(format!("b{}\n", i).as_bytes()).await?;
+            b.flush().await?;
+        }
+
+        let contents = fs::read_to_string(&path).await?;
+        let mut lines = contents.lines();
+        assert_eq!(lines.next(), Some("first"));
+        for i in 0..10 {
+            assert_eq!(lines.next(), Some(&*format!("a{}", i)));
+            assert_eq!(lines.next(), Some(&*format!("b{}", i)));
+        }
+
+        // A write that doesn't fit into the space left in the cache isn't split.
+        let path = tmp.path().join("b.log");
+        let mut file = File::append(&path).await?;
+        file.write_all(&[b'a'; 100]).await?;
+        file.flush().await?;
+        file.write_all(&[b'b'; 90]).await?;
+        assert_eq!(file.write(&[b'c'; 20]).await?, 20);
+        file.flush().await?;
+        assert_eq!(std::fs::metadata(&path)?.len(), 210);
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn file_try_clone() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+
+    task::block_on(async {
+        let mut file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&path)
+            .await?;
+        file.write_all(b"hello ").await?;
+
+        // Pending writes are flushed before cloning, and both handles share the cursor.
+        let mut clone = file.try_clone().await?;
+        assert_eq!(std::fs::read(&path)?, b"hello ");
+        clone.write_all(b"world").await?;
+        clone.flush().await?;
+        assert_eq!(std::fs::read(&path)?, b"hello world");
+
+        // The clone can be used from another task.
+        file.seek(io::SeekFrom::Start(0)).await?;
+        let contents = task::spawn(async move {
+            let mut contents = String::new();
+            clone.read_to_string(&mut contents).await?;
+            io::Result::Ok(contents)
+        })
+        .await?;
+        assert_eq!(contents, "hello world");
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn file_read_at_write_at() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+
+    task::block_on(async {
+        let mut file = fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&path)
+            .await?;
+        file.write_all(b"hello world").await?;
+
+        // Pending writes are flushed and the cursor stays where it was.
+        assert_eq!(file.write_at(b"W", 6).await?, 1);
+        let mut buf = [0; 5];
+        assert_eq!(file.read_at(&mut buf, 6).await?, 5);
+        assert_eq!(&buf, b"World");
+        assert_eq!(file.seek(io::SeekFrom::Current(0)).await?, 11);
+
+        // Reads past the end return zero bytes.
+        assert_eq!(file.read_at(&mut buf, 100).await?, 0);
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn file_lock() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+
+    task::block_on(async {
+        let a = File::create(&path).await?;
+        let b = File::open(&path).await?;
+
+        a.lock_exclusive().await?;
+        assert!(!b.try_lock_shared().await?);
+        assert!(!b.try_lock_exclusive().await?);
+
+        a.unlock().await?;
+        assert!(b.try_lock_shared().await?);
+        assert!(!a.try_lock_exclusive().await?);
+
+        a.lock_shared().await?;
+        b.unlock().await?;
+        a.unlock().await?;
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn file_allocate() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.bin");
+
+    task::block_on(async {
+        let mut file = File::create(&path).await?;
+        file.write_all(b"hello").await?;
+
+        file.allocate(1 << 20).await?;
+        assert_eq!(file.metadata().await?.len(), 1 << 20);
+        assert_eq!(&std::fs::read(&path)?[..6], b"hello\0");
+
+        // Allocating less than the current length doesn't truncate.
+        file.allocate(16).await?;
+        assert_eq!(file.metadata().await?.len(), 1 << 20);
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn file_bytes_stream() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.bin");
+    let contents: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+    std::fs::write(&path, &contents)?;
+
+    task::block_on(async {
+        let mut chunks = File::open(&path).await?.bytes_stream(4096);
+        let mut data = Vec::new();
+        let mut progress = Vec::new();
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk?;
+            progress.push(chunk.progress());
+            data.extend_from_slice(chunk.bytes());
+        }
+
+        // Only the last chunk is shorter.
+        assert_eq!(progress, [4096, 8192, 10_000]);
+        assert_eq!(chunks.progress(), 10_000);
+        assert_eq!(data, contents);
+        assert!(chunks.next().await.is_none());
+        Ok(())
+    })
+}
+
+#[cfg(all(feature = "unstable", unix))]
+#[test]
+fn unix_ownership_and_mode() -> io::Result<()> {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    use async_std::os::unix::fs::{chown, fchown, set_permissions_mode};
+
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.txt");
+
+    task::block_on(async {
+        let file = File::create(&path).await?;
+        let metadata = std::fs::metadata(&path)?;
+
+        // Changing ownership to the current owner is always allowed.
+        chown(&path, Some(metadata.uid()), Some(metadata.gid())).await?;
+        fchown(&file, None, Some(metadata.gid())).await?;
+        chown(&path, None, None).await?;
+        assert_eq!(std::fs::metadata(&path)?.uid(), metadata.uid());
+
+        let missing = tmp.path().join("missing");
+        let err = chown(&missing, None, None).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+        set_permissions_mode(&path, 0o600).await?;
+        assert_eq!(
+            std::fs::metadata(&path)?.permissions().mode() & 0o777,
+            0o600
+        );
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn write_atomic() -> io::Result<()> {
+    use std::time::Duration;
+
+    use async_std::fs::AtomicFile;
+    use async_std::future;
+
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("state.json");
+
+    task::block_on(async {
+        fs::write_atomic(&path, b"{}").await?;
+        assert_eq!(fs::read(&path).await?, b"{}");
+
+        let mut file = AtomicFile::create(&path).await?;
+        file.write_all(b"{\"a\": ").await?;
+        file.write_all(b"1}").await?;
+
+        // The target is untouched until the file is committed.
+        assert_eq!(fs::read(&path).await?, b"{}");
+        file.commit().await?;
+        assert_eq!(fs::read(&path).await?, b"{\"a\": 1}");
+
+        // Dropping an uncommitted file removes the temporary file.
+        let mut file = AtomicFile::create(&path).await?;
+        file.write_all(b"discarded").await?;
+        drop(file);
+        assert_eq!(fs::read(&path).await?, b"{\"a\": 1}");
+        future::timeout(Duration::from_secs(10), async {
+            while std::fs::read_dir(tmp.path())?.count() > 1 {
+                task::sleep(Duration::from_millis(10)).await;
+            }
+            io::Result::Ok(())
+        })
+        .await
+        .expect("the temporary file wasn't removed")?;
+        Ok(())
+    })
+}
+
+#[cfg(all(feature = "unstable", unix))]
+#[test]
+fn write_atomic_permissions() -> io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("secret");
+    std::fs::write(&path, b"old")?;
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600))?;
+
+    task::block_on(async {
+        fs::write_atomic(&path, b"new").await?;
+        assert_eq!(fs::read(&path).await?, b"new");
+        assert_eq!(
+            std::fs::metadata(&path)?.permissions().mode() & 0o777,
+            0o600
+        );
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn cached_fs() -> io::Result<()> {
+    use std::time::Duration;
+
+    use async_std::fs::CachedFs;
+
+    let tmp = TempDir::new("async-std")?;
+    let a = tmp.path().join("a.txt");
+    let b = tmp.path().join("b.txt");
+    std::fs::write(&a, b"a")?;
+    std::fs::write(&b, b"b")?;
+
+    task::block_on(async {
+        // The TTL is long enough that entries never expire while the test runs.
+        let cache = CachedFs::with_capacity(Duration::from_secs(3600), 1);
+        assert_eq!(cache.metadata(&a).await?.len(), 1);
+
+        // Changes are not seen until the entry expires or is invalidated.
+        std::fs::write(&a, b"aa")?;
+        assert_eq!(cache.metadata(&a).await?.len(), 1);
+        cache.invalidate(&a);
+        assert_eq!(cache.metadata(&a).await?.len(), 2);
+
+        // Sleeping takes at least as long as asked, so these entries always expire.
+        let short = CachedFs::with_capacity(Duration::from_millis(10), 1);
+        assert_eq!(short.metadata(&a).await?.len(), 2);
+        std::fs::write(&a, b"aaa")?;
+        task::sleep(Duration::from_millis(50)).await;
+        assert_eq!(short.metadata(&a).await?.len(), 3);
+
+        // Inserting into a full cache evicts the oldest entry.
+        std::fs::write(&a, b"aaaa")?;
+        assert_eq!(cache.metadata(&b).await?.len(), 1);
+        assert_eq!(cache.metadata(&a).await?.len(), 4);
+
+        // Errors are not cached.
+        std::fs::remove_file(&b)?;
+        cache.clear();
+        assert!(cache.metadata(&b).await.is_err());
+        std::fs::write(&b, b"b")?;
+        assert_eq!(cache.metadata(&b).await?.len(), 1);
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn cancel_safe() -> io::Result<()> {
+    use std::sync::mpsc;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use async_std::future;
+    use async_std::task::{Context, Poll};
+
+    let tmp = TempDir::new("async-std")?;
+    let from = tmp.path().join("a.bin");
+    let out = tmp.path().join("out");
+    let to = out.join("b.bin");
+    std::fs::write(&from, b"hello")?;
+    std::fs::create_dir(&out)?;
+
+    task::block_on(async {
+        let (sender, receiver) = mpsc::channel();
+        let sender = Mutex::new(sender);
+        let mut handle = fs::cancel_safe();
+        handle.on_cancel(move |cancelled| {
+            let _ = sender.lock().unwrap().send(cancelled.into_result());
+        });
+
+        assert_eq!(handle.copy(&from, &to).await?, 5);
+        assert_eq!(fs::read(&to).await?, b"hello");
+        std::fs::remove_file(&to)?;
+
+        // An operation that is dropped while running is stopped and reported.
+        let (started, wait_started) = mpsc::channel();
+        let mut op = Box::pin(handle.run("wait", &to, move |cancellation| {
+            started.send(()).unwrap();
+            while !cancellation.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            cancellation.check()
+        }));
+        future::poll_fn(|cx: &mut Context<'_>| {
+            assert!(op.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        wait_started.recv().unwrap();
+        drop(op);
+        let res = receiver
+            .recv_timeout(Duration::from_secs(10))
+            .expect("the dropped operation wasn't reported");
+        assert_eq!(res.unwrap_err().kind(), io::ErrorKind::Interrupted);
+
+        // A copy that is dropped midway leaves no temporary file behind.
+        std::fs::File::create(&from)?.set_len(1 << 20)?;
+        let res = future::timeout(Duration::from_millis(1), handle.copy(&from, &to)).await;
+        future::timeout(Duration::from_secs(10), async {
+            while std::fs::read_dir(&out)?.any(|e| e.unwrap().path() != to) {
+                task::sleep(Duration::from_millis(10)).await;
+            }
+            io::Result::Ok(())
+        })
+        .await
+        .expect("the temporary file wasn't removed")?;
+        if res.is_err() && to.exists() {
+            // The copy had already finished when it was dropped.
+            assert!(receiver.recv_timeout(Duration::from_secs(10)).unwrap().is_ok());
+        }
+        if to.exists() {
+            assert_eq!(std::fs::metadata(&to)?.len(), 1 << 20);
+        }
+
+        let dir = tmp.path().join("dir");
+        std::fs::create_dir_all(dir.join("a/b"))?;
+        std::fs::write(dir.join("a/b/c.txt"), b"c")?;
+        handle.remove_dir_all(&dir).await?;
+        assert!(!dir.exists());
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn tempfile() -> io::Result<()> {
+    task::block_on(async {
+        let mut file = fs::tempfile().await?;
+        file.write_all(b"hello").await?;
+        file.seek(io::SeekFrom::Start(0)).await?;
+
+        let mut contents = String::new();
+        file.read_to_string(&mut contents).await?;
+        assert_eq!(contents, "hello");
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn tempdir() -> io::Result<()> {
+    task::block_on(async {
+        let dir = fs::tempdir().await?;
+        let path = dir.path().to_path_buf();
+        assert!(path.is_dir().await);
+
+        fs::write(path.join("a.txt"), b"hello").await?;
+        dir.close().await?;
+        assert!(!path.exists().await);
+
+        let dir = fs::tempdir().await?;
+        let path = dir.path().to_path_buf();
+        drop(dir);
+
+        // Dropping removes the directory in the background.
+        while path.exists().await {
+            task::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        Ok(())
+    })
+}
+
+#[cfg(all(feature = "unstable", target_os = "linux"))]
+#[test]
+fn watch() -> io::Result<()> {
+    use async_std::fs::EventKind;
+
+    task::block_on(async {
+        let dir = fs::tempdir().await?;
+        let mut events = fs::watch(dir.path()).await?;
+
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b.txt");
+
+        fs::write(&a, b"hello").await?;
+        let event = events.next().await.unwrap()?;
+        assert_eq!(event.kind(), EventKind::Create);
+        assert_eq!(event.path(), &*a);
+        let event = events.next().await.unwrap()?;
+        assert_eq!(event.kind(), EventKind::Modify);
+
+        fs::rename(&a, &b).await?;
+        let event = events.next().await.unwrap()?;
+        assert_eq!((event.kind(), event.path()), (EventKind::RenameFrom, &*a));
+        let event = events.next().await.unwrap()?;
+        assert_eq!((event.kind(), event.path()), (EventKind::RenameTo, &*b));
+
+        fs::remove_file(&b).await?;
+        let event = events.next().await.unwrap()?;
+        assert_eq!((event.kind(), event.path()), (EventKind::Remove, &*b));
+
+        // The stream ends once the watched directory is removed.
+        dir.close().await?;
+        let event = events.next().await.unwrap()?;
+        assert_eq!(event.kind(), EventKind::Remove);
+        assert!(events.next().await.is_none());
+        Ok(())
+    })
+}
+
+#[cfg(all(feature = "unstable", unix))]
+#[test]
+fn walk_dir() -> io::Result<()> {
+    use async_std::path::PathBuf;
+
+    task::block_on(async {
+        let dir = fs::tempdir().await?;
+        let root = dir.path();
+        fs::create_dir_all(root.join("a/b/c")).await?;
+        fs::create_dir_all(root.join("skip/x")).await?;
+        fs::write(root.join("a/f.txt"), b"").await?;
+        fs::write(root.join("a/b/c/g.txt"), b"").await?;
+        std::os::unix::fs::symlink(root.join("a"), root.join("link"))?;
+
+        async fn collect(mut walk: fs::WalkDir) -> io::Result<Vec<(PathBuf, usize)>> {
+            let mut entries = Vec::new();
+            while let Some(entry) = walk.next().await {
+                let entry = entry?;
+                if entry.file_name() == "skip" {
+                    walk.skip_current_dir();
+                }
+                entries.push((entry.path().to_path_buf(), entry.depth()));
+            }
+            entries.sort();
+            Ok(entries)
+        }
+
+        let entries = collect(fs::walk_dir(root)).await?;
+        let expected = vec![
+            (root.to_path_buf(), 0),
+            (root.join("a"), 1),
+            (root.join("a/b"), 2),
+            (root.join("a/b/c"), 3),
+            (root.join("a/b/c/g.txt"), 4),
+            (root.join("a/f.txt"), 2),
+            (root.join("link"), 1),
+            (root.join("skip"), 1),
+        ];
+        assert_eq!(entries, expected);
+
+        let entries = collect(fs::walk_dir(root).max_depth(1)).await?;
+        assert_eq!(entries.len(), 4);
+        assert!(entries.iter().all(|(_, depth)| *depth <= 1));
+
+        // Following links walks into the linked directory too.
+        let entries = collect(fs::walk_dir(root).follow_links(true)).await?;
+        assert_eq!(entries.len(), expected.len() + 4);
+        assert!(entries.contains(&(root.join("link/b/c/g.txt"), 4)));
+
+        // A link to an ancestor is reported as an error.
+        std::os::unix::fs::symlink(root, root.join("a/b/loop"))?;
+        let res = collect(fs::walk_dir(root).follow_links(true)).await;
+        assert!(res.is_err());
+        Ok(())
+    })
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn file_direct_io() -> io::Result<()> {
+    use async_std::fs::OpenOptions;
+    use async_std::io::SeekFrom;
+    use async_std::os::unix::fs::OpenOptionsExt;
+
+    // tmpfs doesn't support direct I/O, so use the target directory, which is on a disk.
+    let tmp = TempDir::new_in(env!("CARGO_TARGET_TMPDIR"), "async-std")?;
+    let path = tmp.path().join("direct");
+
+    task::block_on(async {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .custom_flags(libc::O_DIRECT)
+            .open(&path)
+            .await?;
+
+        let data: Vec<u8> = (0..8192).map(|i| i as u8).collect();
+        file.write_all(&data[..4096]).await?;
+        file.write_all(&data[4096..]).await?;
+        file.flush().await?;
+
+        file.seek(SeekFrom::Start(0)).await?;
+        let mut buf = vec![0; 8192];
+        file.read_exact(&mut buf).await?;
+        assert_eq!(buf, data);
+
+        // Reads smaller than the alignment are served from the cache.
+        file.seek(SeekFrom::Start(0)).await?;
+        let mut buf = Vec::new();
+        let mut chunk = [0; 100];
+        loop {
+            let n = file.read(&mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..n]);
+        }
+        assert_eq!(buf, data);
+        Ok(())
+    })
+}
+
+#[cfg(all(target_os = "linux", feature = "unstable"))]
+#[test]
+fn file_direct_read_at_write_at() -> io::Result<()> {
+    use async_std::fs::OpenOptions;
+    use async_std::os::unix::fs::OpenOptionsExt;
+
+    // tmpfs doesn't support direct I/O, so use the target directory, which is on a disk.
+    let tmp = TempDir::new_in(env!("CARGO_TARGET_TMPDIR"), "async-std")?;
+    let path = tmp.path().join("direct");
+
+    task::block_on(async {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .custom_flags(libc::O_DIRECT)
+            .open(&path)
+            .await?;
+
+        let data: Vec<u8> = (0..4096).map(|i| i as u8).collect();
+        assert_eq!(file.write_at(&data, 4096).await?, 4096);
+
+        let mut buf = vec![0; 8192];
+        assert_eq!(file.read_at(&mut buf, 0).await?, 8192);
+        assert!(buf[..4096].iter().all(|&b| b == 0));
+        assert_eq!(&buf[4096..], &data[..]);
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn read_dir_options() -> io::Result<()> {
+    use async_std::fs::ReadDirOptions;
+
+    let tmp = TempDir::new("async-std")?;
+    std::fs::write(tmp.path().join("a.rs"), b"a")?;
+    std::fs::write(tmp.path().join("b.rs"), b"bb")?;
+    std::fs::write(tmp.path().join("c.txt"), b"ccc")?;
+    std::fs::create_dir(tmp.path().join("d.rs"))?;
+
+    task::block_on(async {
+        let mut dir = ReadDirOptions::new()
+            .extension("rs")
+            .dirs(false)
+            .metadata(true)
+            .read_dir(tmp.path())
+            .await?;
+
+        let mut entries = Vec::new();
+        while let Some(entry) = dir.next().await {
+            let entry = entry?;
+            let len = entry.metadata().await?.len();
+            assert!(entry.file_type().await?.is_file());
+            entries.push((entry.file_name(), len));
+        }
+        entries.sort();
+        assert_eq!(entries, vec![("a.rs".into(), 1), ("b.rs".into(), 2)]);
+
+        let mut dir = ReadDirOptions::new()
+            .files(false)
+            .read_dir(tmp.path())
+            .await?;
+        let entry = dir.next().await.unwrap()?;
+        assert_eq!(entry.file_name(), "d.rs");
+        assert!(dir.next().await.is_none());
+        Ok(())
+    })
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn mmap() -> io::Result<()> {
+    use async_std::fs::{Mmap, MmapMut, OpenOptions};
+
+    let tmp = TempDir::new("async-std")?;
+    let path = tmp.path().join("a.bin");
+
+    task::block_on(async {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .open(&path)
+            .await?;
+
+        // Pending writes are flushed before mapping.
+        file.write_all(b"hello world").await?;
+        let mut map = unsafe { MmapMut::map_mut(&file).await? };
+        assert_eq!(&map[..], b"hello world");
+
+        map[..5].copy_from_slice(b"HELLO");
+        map.flush().await?;
+        assert_eq!(std::fs::read(&path)?, b"HELLO world");
+
+        let map = unsafe { Mmap::map(&File::open(&path).await?).await? };
+        let mut contents = String::new();
+        io::Cursor::new(map).read_to_string(&mut contents).await?;
+        assert_eq!(contents, "HELLO world");
+        Ok(())
+    })
+}
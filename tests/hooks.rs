@@ -0,0 +1,52 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_std::task::{self, Hooks};
+
+// Hooks are global, so this is the only test in the binary.
+#[test]
+fn hooks() {
+    let spawned = Arc::new(Mutex::new(Vec::new()));
+    let polls = Arc::new(AtomicUsize::new(0));
+    let poll_time = Arc::new(Mutex::new(Duration::from_secs(0)));
+    let completed = Arc::new(Mutex::new(Vec::new()));
+
+    task::set_hooks({
+        let spawned = spawned.clone();
+        let polls = polls.clone();
+        let poll_time = poll_time.clone();
+        let completed = completed.clone();
+
+        Hooks::new()
+            .on_spawn(move |t| spawned.lock().unwrap().push(t.name().map(String::from)))
+            .on_poll_start(move |_| {
+                polls.fetch_add(1, Ordering::SeqCst);
+            })
+            .on_poll_end(move |_, elapsed| *poll_time.lock().unwrap() += elapsed)
+            .on_complete(move |t| completed.lock().unwrap().push(t.id()))
+    });
+
+    task::block_on(async {
+        let handle = task::Builder::new()
+            .name("worker".to_string())
+            .spawn(async {
+                std::thread::sleep(Duration::from_millis(10));
+                task::sleep(Duration::from_millis(10)).await;
+            })
+            .unwrap();
+        let id = handle.task().id();
+        handle.await;
+
+        // The completion hook runs before the task's output is handed to the handle.
+        assert_eq!(*completed.lock().unwrap(), vec![id]);
+        assert_eq!(*spawned.lock().unwrap(), vec![Some("worker".to_string())]);
+
+        // The task was polled once before sleeping and once after. The first poll blocked the
+        // thread, and its end was reported before the task could be woken up again.
+        assert!(polls.load(Ordering::SeqCst) >= 2);
+        assert!(*poll_time.lock().unwrap() >= Duration::from_millis(10));
+    });
+}
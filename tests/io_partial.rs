@@ -0,0 +1,153 @@
+use std::pin::Pin;
+
+use async_std::io::{self, Read, Write};
+use async_std::prelude::*;
+use async_std::task::{self, Context, Poll};
+
+/// A reader that returns at most `chunk` bytes per read, and is pending every other poll.
+struct Trickle {
+    data: Vec<u8>,
+    pos: usize,
+    chunk: usize,
+    ready: bool,
+}
+
+impl Trickle {
+    fn new(data: &[u8], chunk: usize) -> Trickle {
+        Trickle {
+            data: data.to_vec(),
+            pos: 0,
+            chunk,
+            ready: false,
+        }
+    }
+}
+
+impl Read for Trickle {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        self.ready = !self.ready;
+        if !self.ready {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let n = buf.len().min(self.chunk).min(self.data.len() - self.pos);
+        buf[..n].copy_from_slice(&self.data[self.pos..self.pos + n]);
+        self.pos += n;
+        Poll::Ready(Ok(n))
+    }
+}
+
+/// A writer that accepts at most `chunk` bytes per write, and is pending every other poll.
+struct Sip {
+    data: Vec<u8>,
+    chunk: usize,
+    ready: bool,
+}
+
+impl Write for Sip {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        self.ready = !self.ready;
+        if !self.ready {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let n = buf.len().min(self.chunk);
+        self.data.extend_from_slice(&buf[..n]);
+        Poll::Ready(Ok(n))
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+const DATA: &[u8] = b"The quick brown fox jumps over the lazy dog.";
+
+#[test]
+fn read_exact_partial() -> io::Result<()> {
+    task::block_on(async {
+        let mut r = Trickle::new(DATA, 3);
+        let mut buf = [0; 10];
+        r.read_exact(&mut buf).await?;
+        assert_eq!(&buf, &DATA[..10]);
+
+        // Running out of data before the buffer is full is an error.
+        let mut buf = vec![0; DATA.len()];
+        let err = r.read_exact(&mut buf).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+        Ok(())
+    })
+}
+
+#[test]
+fn read_to_end_partial() -> io::Result<()> {
+    task::block_on(async {
+        let mut r = Trickle::new(DATA, 4);
+        let mut buf = b"> ".to_vec();
+        assert_eq!(r.read_to_end(&mut buf).await?, DATA.len());
+        assert_eq!(&buf[..2], b"> ");
+        assert_eq!(&buf[2..], DATA);
+        Ok(())
+    })
+}
+
+#[test]
+fn read_to_string_partial() -> io::Result<()> {
+    task::block_on(async {
+        // Multi-byte characters are split across reads.
+        let text = "héllo wörld ✓";
+        let mut r = Trickle::new(text.as_bytes(), 1);
+        let mut s = String::new();
+        assert_eq!(r.read_to_string(&mut s).await?, text.len());
+        assert_eq!(s, text);
+
+        // Invalid UTF-8 is an error.
+        let mut r = Trickle::new(&[b'a', 0xff, b'b'], 1);
+        let err = r.read_to_string(&mut String::new()).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        Ok(())
+    })
+}
+
+#[test]
+fn write_all_partial() -> io::Result<()> {
+    task::block_on(async {
+        let mut w = Sip {
+            data: Vec::new(),
+            chunk: 5,
+            ready: false,
+        };
+        w.write_all(DATA).await?;
+        assert_eq!(w.data, DATA);
+        Ok(())
+    })
+}
+
+#[test]
+fn copy_partial() -> io::Result<()> {
+    task::block_on(async {
+        let mut r = Trickle::new(DATA, 7);
+        let mut w = Sip {
+            data: Vec::new(),
+            chunk: 3,
+            ready: false,
+        };
+        assert_eq!(io::copy(&mut r, &mut w).await?, DATA.len() as u64);
+        assert_eq!(w.data, DATA);
+        Ok(())
+    })
+}
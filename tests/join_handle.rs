@@ -0,0 +1,90 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::sync::channel;
+use async_std::task;
+
+struct SetOnDrop(Arc<AtomicBool>);
+
+impl Drop for SetOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn cancel() {
+    task::block_on(async {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let guard = SetOnDrop(dropped.clone());
+
+        let handle = task::spawn(async move {
+            let _guard = guard;
+            task::sleep(Duration::from_secs(10)).await;
+        });
+        task::sleep(Duration::from_millis(10)).await;
+
+        // The future has been dropped once `cancel` completes.
+        assert_eq!(handle.cancel().await, None);
+        assert!(dropped.load(Ordering::SeqCst));
+    })
+}
+
+#[test]
+fn cancel_completed() {
+    task::block_on(async {
+        let handle = task::spawn(async { 1 + 2 });
+        task::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(handle.cancel().await, Some(3));
+    })
+}
+
+#[test]
+fn not_polled_after_cancel() {
+    task::block_on(async {
+        let polls = Arc::new(AtomicUsize::new(0));
+
+        let p = polls.clone();
+        let handle = task::spawn(async move {
+            loop {
+                p.fetch_add(1, Ordering::SeqCst);
+                task::sleep(Duration::from_millis(1)).await;
+            }
+        });
+        task::sleep(Duration::from_millis(20)).await;
+
+        handle.cancel().await;
+        let count = polls.load(Ordering::SeqCst);
+        task::sleep(Duration::from_millis(20)).await;
+        assert_eq!(polls.load(Ordering::SeqCst), count);
+    })
+}
+
+#[test]
+fn cancel_blocking() {
+    task::block_on(async {
+        let handle = task::spawn_blocking(|| 7);
+        task::sleep(Duration::from_millis(50)).await;
+
+        assert_eq!(handle.cancel().await, Some(7));
+    })
+}
+
+#[test]
+fn detach() {
+    task::block_on(async {
+        let (s, r) = channel(1);
+
+        task::spawn(async move {
+            task::sleep(Duration::from_millis(10)).await;
+            s.send(1).await.unwrap();
+        })
+        .detach();
+
+        assert_eq!(r.recv().await.unwrap(), 1);
+    })
+}
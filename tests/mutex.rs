@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
+use async_std::future;
 use async_std::prelude::*;
 use async_std::sync::Mutex;
-use async_std::task;
+use async_std::task::{self, Poll};
 use futures::channel::mpsc;
 
 #[test]
@@ -62,3 +63,42 @@ fn contention() {
         assert_eq!(num_tasks, *lock);
     });
 }
+
+#[test]
+fn fairness() {
+    task::block_on(async {
+        let m = Mutex::new(0);
+        let guard = m.lock().await;
+
+        let mut first = Box::pin(m.lock());
+        let mut second = Box::pin(m.lock());
+        future::poll_fn(|cx| {
+            assert!(first.as_mut().poll(cx).is_pending());
+            assert!(second.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+
+        // The lock is handed over to the first waiting task, so nobody can jump the queue.
+        drop(guard);
+        assert!(m.try_lock().is_none());
+        future::poll_fn(|cx| {
+            assert!(second.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        *first.await += 1;
+
+        // The lock is now handed over to the second task. Dropping it without polling passes
+        // the lock on to the next one in line.
+        let mut third = Box::pin(m.lock());
+        future::poll_fn(|cx| {
+            assert!(third.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        drop(second);
+        assert_eq!(*third.await, 1);
+        assert!(m.try_lock().is_some());
+    });
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::sync::{Arc, Notify};
+use async_std::task::{self, Poll};
+
+#[test]
+fn notify_one_is_fifo() {
+    task::block_on(async {
+        let notify = Notify::new();
+        let mut first = Box::pin(notify.notified());
+        let mut second = Box::pin(notify.notified());
+
+        notify.notify_one();
+        future::poll_fn(|cx| {
+            assert!(second.as_mut().poll(cx).is_pending());
+            assert!(first.as_mut().poll(cx).is_ready());
+            Poll::Ready(())
+        })
+        .await;
+
+        notify.notify_one();
+        second.await;
+    })
+}
+
+#[test]
+fn permit_is_stored_once() {
+    task::block_on(async {
+        let notify = Notify::new();
+        notify.notify_one();
+        notify.notify_one();
+
+        notify.notified().await;
+
+        let mut notified = Box::pin(notify.notified());
+        future::poll_fn(|cx| {
+            assert!(notified.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+    })
+}
+
+#[test]
+fn notify_waiters_stores_no_permit() {
+    task::block_on(async {
+        let notify = Arc::new(Notify::new());
+        let mut tasks = Vec::new();
+
+        for _ in 0..5 {
+            let notify = notify.clone();
+            tasks.push(task::spawn(async move { notify.notified().await }));
+        }
+        task::sleep(Duration::from_millis(50)).await;
+
+        notify.notify_waiters();
+        for t in tasks {
+            t.await;
+        }
+
+        let mut late = Box::pin(notify.notified());
+        future::poll_fn(|cx| {
+            assert!(late.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+    })
+}
+
+#[test]
+fn dropped_waiter_passes_notification_on() {
+    task::block_on(async {
+        let notify = Notify::new();
+        let first = notify.notified();
+        let second = notify.notified();
+
+        notify.notify_one();
+        drop(first);
+        second.await;
+    })
+}
@@ -0,0 +1,89 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::sync::{Lazy, OnceCell};
+use async_std::task::{self, Poll};
+
+#[test]
+fn concurrent_initializers_are_deduplicated() {
+    task::block_on(async {
+        let cell = Arc::new(OnceCell::new());
+        let runs = Arc::new(AtomicUsize::new(0));
+        let mut tasks = Vec::new();
+
+        for i in 0..10 {
+            let cell = cell.clone();
+            let runs = runs.clone();
+            tasks.push(task::spawn(async move {
+                let value = cell
+                    .get_or_init(|| async move {
+                        runs.fetch_add(1, Ordering::SeqCst);
+                        task::sleep(Duration::from_millis(20)).await;
+                        i
+                    })
+                    .await;
+                *value
+            }));
+        }
+
+        let first = tasks.remove(0).await;
+        for t in tasks {
+            assert_eq!(t.await, first);
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    })
+}
+
+#[test]
+fn cancelled_initializer() {
+    task::block_on(async {
+        let cell = OnceCell::new();
+
+        // Start an initializer that never finishes, then give up on it.
+        let mut init = Box::pin(cell.get_or_init(future::pending::<i32>));
+        future::poll_fn(|cx| {
+            assert!(init.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        drop(init);
+
+        assert_eq!(cell.get(), None);
+        assert_eq!(*cell.get_or_init(|| async { 2 }).await, 2);
+    })
+}
+
+#[test]
+fn failed_initializer() {
+    task::block_on(async {
+        let cell = OnceCell::new();
+
+        let res = cell.get_or_try_init(|| async { Err(()) }).await;
+        assert_eq!(res, Err(()));
+
+        let res = cell.get_or_try_init(|| async { Ok::<_, ()>(3) }).await;
+        assert_eq!(res, Ok(&3));
+        assert_eq!(cell.set(4).await, Err(4));
+    })
+}
+
+#[test]
+fn lazy() {
+    task::block_on(async {
+        let runs = AtomicUsize::new(0);
+        let lazy = Lazy::new(|| async {
+            runs.fetch_add(1, Ordering::SeqCst);
+            "value"
+        });
+
+        assert_eq!(lazy.try_get(), None);
+        assert_eq!(*lazy.get().await, "value");
+        assert_eq!(*lazy.get().await, "value");
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    })
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "unstable")]
+
+use std::time::Duration;
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::sync::oneshot::{self, RecvError, TryRecvError};
+use async_std::task::{self, Poll};
+
+#[test]
+fn smoke() {
+    task::block_on(async {
+        let (s, r) = oneshot::channel();
+
+        task::spawn(async move {
+            task::sleep(Duration::from_millis(10)).await;
+            s.send(7).unwrap();
+        });
+
+        assert_eq!(r.await, Ok(7));
+    })
+}
+
+#[test]
+fn sender_dropped() {
+    task::block_on(async {
+        let (s, mut r) = oneshot::channel::<i32>();
+
+        // A waiting receiver learns that the sender is gone.
+        let mut recv = Box::pin(async move {
+            let res = (&mut r).await;
+            (res, r.try_recv())
+        });
+        future::poll_fn(|cx| {
+            assert!(recv.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+
+        drop(s);
+        let (res, again) = recv.await;
+        assert_eq!(res, Err(RecvError));
+        assert_eq!(again, Err(TryRecvError::Disconnected));
+    })
+}
+
+#[test]
+fn receiver_dropped() {
+    task::block_on(async {
+        let (s, r) = oneshot::channel();
+
+        let mut closed = Box::pin(s.closed());
+        future::poll_fn(|cx| {
+            assert!(closed.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+
+        drop(r);
+        closed.await;
+        assert!(s.is_closed());
+        assert_eq!(s.send("late"), Err("late"));
+    })
+}
+
+#[test]
+fn value_survives_sender_drop() {
+    task::block_on(async {
+        let (s, mut r) = oneshot::channel();
+        assert_eq!(r.try_recv(), Err(TryRecvError::Empty));
+
+        // Sending consumes the sender, but the value is still delivered.
+        s.send(vec![1, 2, 3]).unwrap();
+        r.close();
+        assert_eq!(r.await, Ok(vec![1, 2, 3]));
+    })
+}
@@ -0,0 +1,80 @@
+use std::collections::HashSet;
+
+use async_std::io;
+use async_std::path::{Path, PathBuf};
+use async_std::prelude::*;
+use async_std::task;
+
+use tempdir::TempDir;
+
+#[test]
+fn exists() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let dir = PathBuf::from(tmp.path().to_path_buf());
+    let file = dir.join("a.txt");
+    std::fs::write(&file, b"")?;
+
+    task::block_on(async {
+        assert!(dir.exists().await);
+        assert!(dir.is_dir().await);
+        assert!(!dir.is_file().await);
+
+        assert!(file.exists().await);
+        assert!(file.is_file().await);
+        assert!(!file.is_dir().await);
+
+        assert!(!dir.join("missing").exists().await);
+        Ok(())
+    })
+}
+
+#[test]
+fn metadata_and_canonicalize() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let dir = PathBuf::from(tmp.path().to_path_buf());
+    std::fs::write(dir.join("a.txt"), b"hello")?;
+
+    task::block_on(async {
+        let path = dir.join(".").join("a.txt");
+        assert_eq!(path.metadata().await?.len(), 5);
+        assert_eq!(
+            path.canonicalize().await?,
+            std::fs::canonicalize(dir.join("a.txt"))?.into()
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn read_dir() -> io::Result<()> {
+    let tmp = TempDir::new("async-std")?;
+    let dir = PathBuf::from(tmp.path().to_path_buf());
+    std::fs::write(dir.join("a.txt"), b"")?;
+    std::fs::write(dir.join("b.txt"), b"")?;
+
+    task::block_on(async {
+        let mut names = HashSet::new();
+        let mut entries = dir.read_dir().await?;
+        while let Some(entry) = entries.next().await {
+            names.insert(entry?.file_name().into_string().unwrap());
+        }
+        assert_eq!(names.len(), 2);
+        assert!(names.contains("a.txt"));
+        assert!(names.contains("b.txt"));
+        Ok(())
+    })
+}
+
+#[test]
+fn std_traits() {
+    let a = PathBuf::from("a");
+    let b = a.clone();
+    assert_eq!(a, b);
+    assert!(Path::new("a") < Path::new("b"));
+    assert_eq!(PathBuf::default(), PathBuf::new());
+
+    let mut set = HashSet::new();
+    set.insert(a);
+    assert!(set.contains(&b));
+    assert!(set.contains(Path::new("a")));
+}
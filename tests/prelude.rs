@@ -0,0 +1,22 @@
+#![cfg(feature = "unstable")]
+
+use async_std::prelude::*;
+
+#[test]
+fn method_syntax() {
+    async_std::task::block_on(async {
+        let mut s = async_std::stream::once(1);
+        assert_eq!(s.next().await, Some(1));
+
+        let mut reader = async_std::io::Cursor::new("hello");
+        let mut buf = String::new();
+        reader.read_to_string(&mut buf).await.unwrap();
+        assert_eq!(buf, "hello");
+
+        let dur = std::time::Duration::from_secs(1);
+        assert_eq!(async_std::future::ready(2).timeout(dur).await, Ok(2));
+
+        let mut s = async_std::stream::once(3).timeout(dur);
+        assert_eq!(s.next().await, Some(Ok(3)));
+    })
+}
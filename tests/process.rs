@@ -0,0 +1,149 @@
+#![cfg(all(feature = "unstable", unix))]
+
+use std::os::unix::process::ExitStatusExt;
+use std::time::Duration;
+
+use async_std::io;
+use async_std::prelude::*;
+use async_std::process::{Command, Stdio};
+use async_std::task;
+
+#[test]
+fn output() {
+    task::block_on(async {
+        let output = Command::new("echo").arg("hello").output().await.unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hello\n");
+        assert!(output.stderr.is_empty());
+    })
+}
+
+#[test]
+fn piped_stdin() {
+    task::block_on(async {
+        let mut child = Command::new("cat")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let mut stdin = child.stdin.take().unwrap();
+        stdin.write_all(b"hello world").await.unwrap();
+        drop(stdin);
+
+        let output = child.output().await.unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hello world");
+    })
+}
+
+#[test]
+fn exit_code() {
+    task::block_on(async {
+        let status = Command::new("sh")
+            .args(["-c", "exit 3"])
+            .status()
+            .await
+            .unwrap();
+        assert_eq!(status.code(), Some(3));
+    })
+}
+
+#[test]
+fn try_status() {
+    task::block_on(async {
+        let mut child = Command::new("cat")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap();
+        assert!(child.try_status().unwrap().is_none());
+
+        drop(child.stdin.take());
+        assert!(child.status().await.unwrap().success());
+        assert!(child.try_status().unwrap().unwrap().success());
+    })
+}
+
+#[test]
+fn many_concurrent() {
+    task::block_on(async {
+        let mut tasks = Vec::new();
+        for i in 0..20 {
+            tasks.push(task::spawn(async move {
+                let status = Command::new("sh")
+                    .arg("-c")
+                    .arg(format!("sleep 0.0{}; exit {}", i % 5, i))
+                    .status()
+                    .await
+                    .unwrap();
+                assert_eq!(status.code(), Some(i));
+            }));
+        }
+        for t in tasks {
+            t.await;
+        }
+    })
+}
+
+#[test]
+fn kill() {
+    task::block_on(async {
+        let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+        child.kill().unwrap();
+
+        let status = child.status().await.unwrap();
+        assert_eq!(status.signal(), Some(9));
+    })
+}
+
+#[test]
+fn kill_on_drop() {
+    task::block_on(async {
+        let mut child = Command::new("sleep")
+            .arg("10")
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut stdout = child.stdout.take().unwrap();
+        child.kill_on_drop(true);
+        drop(child);
+
+        // The pipe is closed once the process is gone.
+        let mut buf = Vec::new();
+        let res = io::timeout(Duration::from_secs(5), stdout.read_to_end(&mut buf)).await;
+        assert_eq!(res.unwrap(), 0);
+    })
+}
+
+#[test]
+fn terminate_then_kill() {
+    task::block_on(async {
+        // The child exits on `SIGTERM`.
+        let mut child = Command::new("sleep").arg("10").spawn().unwrap();
+        let status = child
+            .terminate_then_kill(Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(status.signal(), Some(15));
+
+        // The child ignores `SIGTERM` and is killed after the grace period.
+        let mut child = Command::new("sh")
+            .args(["-c", "trap '' TERM; sleep 10"])
+            .spawn()
+            .unwrap();
+        task::sleep(Duration::from_millis(100)).await;
+        let status = child
+            .terminate_then_kill(Duration::from_millis(100))
+            .await
+            .unwrap();
+        assert_eq!(status.signal(), Some(9));
+
+        // An exited child is not signaled.
+        let status = child
+            .terminate_then_kill(Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert_eq!(status.signal(), Some(9));
+    })
+}
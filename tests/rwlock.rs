@@ -5,6 +5,7 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
+use async_std::future;
 use async_std::prelude::*;
 use async_std::sync::RwLock;
 use async_std::task;
@@ -182,3 +183,87 @@ fn writer_and_readers() {
         assert_eq!(*lock, 10);
     });
 }
+
+#[test]
+fn writer_preference() {
+    task::block_on(async {
+        let lock = Arc::new(RwLock::new(0));
+        let read = lock.read().await;
+
+        // Once a write is waiting, new reads have to wait for it.
+        let mut write = Box::pin(lock.write());
+        future::poll_fn(|cx| {
+            assert!(write.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        assert!(lock.try_read().is_none());
+
+        let mut second_read = Box::pin(lock.read());
+        future::poll_fn(|cx| {
+            assert!(second_read.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+
+        drop(read);
+        *write.await += 1;
+        assert_eq!(*second_read.await, 1);
+
+        // Giving up on a write lets held back reads through.
+        let read = lock.read().await;
+        let mut write = Box::pin(lock.write());
+        future::poll_fn(|cx| {
+            assert!(write.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        let lock2 = lock.clone();
+        let second_read = task::spawn(async move { *lock2.read().await });
+        drop(write);
+        assert_eq!(second_read.await, 1);
+        drop(read);
+    });
+}
+
+#[cfg(feature = "unstable")]
+#[test]
+fn upgradable_read() {
+    use std::time::Duration;
+
+    use async_std::sync::RwLockUpgradableReadGuard;
+
+    task::block_on(async {
+        let lock = Arc::new(RwLock::new(0));
+
+        let upgradable = lock.upgradable_read().await;
+        let read = lock.read().await;
+        assert!(lock.try_upgradable_read().is_none());
+        assert!(lock.try_write().is_none());
+
+        // Upgrading waits for the other reads, and no other write can sneak in.
+        let lock2 = lock.clone();
+        let other_write = task::spawn(async move {
+            let mut n = lock2.write().await;
+            *n *= 10;
+        });
+        let upgrade = async {
+            let mut n = RwLockUpgradableReadGuard::upgrade(upgradable).await;
+            *n += 1;
+        };
+        let release = async {
+            task::sleep(Duration::from_millis(50)).await;
+            drop(read);
+        };
+        futures::join!(upgrade, release);
+
+        other_write.await;
+        assert_eq!(*lock.read().await, 10);
+
+        // Downgrading lets other upgradable reads in.
+        let upgradable = lock.upgradable_read().await;
+        let read = RwLockUpgradableReadGuard::downgrade(upgradable);
+        assert!(lock.try_upgradable_read().is_some());
+        assert_eq!(*read, 10);
+    });
+}
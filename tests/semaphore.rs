@@ -0,0 +1,109 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::sync::Semaphore;
+use async_std::task::{self, Poll};
+
+#[test]
+fn smoke() {
+    task::block_on(async {
+        let semaphore = Semaphore::new(2);
+
+        let a = semaphore.acquire().await;
+        let b = semaphore.acquire().await;
+        assert!(semaphore.try_acquire().is_none());
+        assert_eq!(semaphore.available_permits(), 0);
+
+        drop(a);
+        let c = semaphore.try_acquire().unwrap();
+        drop((b, c));
+        assert_eq!(semaphore.available_permits(), 2);
+
+        // Forgotten permits are gone for good.
+        semaphore.acquire().await.forget();
+        assert_eq!(semaphore.available_permits(), 1);
+    })
+}
+
+#[test]
+fn acquire_many_fairness() {
+    task::block_on(async {
+        let semaphore = Semaphore::new(3);
+        let permit = semaphore.acquire_many(2).await;
+
+        // A large request waits at the front of the queue and holds back smaller ones.
+        let mut many = Box::pin(semaphore.acquire_many(3));
+        let mut one = Box::pin(semaphore.acquire());
+        future::poll_fn(|cx| {
+            assert!(many.as_mut().poll(cx).is_pending());
+            assert!(one.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        assert!(semaphore.try_acquire().is_none());
+
+        drop(permit);
+        let many = many.await;
+        assert_eq!(many.count(), 3);
+        future::poll_fn(|cx| {
+            assert!(one.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+
+        drop(many);
+        assert_eq!(one.await.count(), 1);
+    })
+}
+
+#[test]
+fn cancelled_acquire() {
+    task::block_on(async {
+        let semaphore = Semaphore::new(1);
+        let permit = semaphore.acquire().await;
+
+        // Giving up on a large request lets the tasks behind it proceed.
+        let mut many = Box::pin(semaphore.acquire_many(2));
+        let mut one = Box::pin(semaphore.acquire());
+        future::poll_fn(|cx| {
+            assert!(many.as_mut().poll(cx).is_pending());
+            assert!(one.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        drop(permit);
+        drop(many);
+        drop(one.await);
+        assert_eq!(semaphore.available_permits(), 1);
+    })
+}
+
+#[test]
+fn owned_permits_limit_concurrency() {
+    task::block_on(async {
+        let semaphore = Arc::new(Semaphore::new(3));
+        let active = Arc::new(AtomicUsize::new(0));
+        let mut tasks = Vec::new();
+
+        for _ in 0..20 {
+            let permit = semaphore.clone().acquire_owned().await;
+            let active = active.clone();
+            tasks.push(task::spawn(async move {
+                assert!(active.fetch_add(1, Ordering::SeqCst) < 3);
+                task::sleep(Duration::from_millis(5)).await;
+                active.fetch_sub(1, Ordering::SeqCst);
+                drop(permit);
+            }));
+        }
+
+        for t in tasks {
+            t.await;
+        }
+        assert_eq!(semaphore.available_permits(), 3);
+    })
+}
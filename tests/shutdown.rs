@@ -0,0 +1,51 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::future;
+use async_std::task;
+
+struct SetOnDrop(Arc<AtomicBool>);
+
+impl Drop for SetOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+// Shutdown is global, so this is the only test in the binary.
+#[test]
+fn shutdown_drops_remaining_tasks() {
+    task::block_on(async {
+        let finished = Arc::new(AtomicBool::new(false));
+        let dropped = Arc::new(AtomicBool::new(false));
+
+        let f = finished.clone();
+        let quick = task::spawn(async move {
+            task::sleep(Duration::from_millis(10)).await;
+            f.store(true, Ordering::SeqCst);
+        });
+
+        // This task is polled after shutdown starts, so shutdown can wake it to drop it.
+        let d = SetOnDrop(dropped.clone());
+        task::spawn(async move {
+            let _d = d;
+            loop {
+                task::sleep(Duration::from_millis(5)).await;
+            }
+        });
+
+        // Nothing can wake this task, so shutdown doesn't wait for it.
+        task::spawn(future::pending::<()>());
+
+        assert!(!task::shutdown(Duration::from_millis(100)).await);
+        assert!(finished.load(Ordering::SeqCst));
+        assert!(dropped.load(Ordering::SeqCst));
+        quick.await;
+
+        let err = task::Builder::new().spawn(async {}).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Other);
+    })
+}
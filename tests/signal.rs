@@ -0,0 +1,71 @@
+#![cfg(all(feature = "unstable", unix))]
+
+use std::time::Duration;
+
+use async_std::future;
+use async_std::io;
+use async_std::os::unix::signal::{signal, SignalKind};
+use async_std::prelude::*;
+use async_std::process::Command;
+use async_std::signal::ctrl_c;
+use async_std::task;
+
+async fn raise(name: &str) {
+    let pid = std::process::id().to_string();
+    let status = Command::new("kill")
+        .args(["-s", name, &pid])
+        .status()
+        .await
+        .unwrap();
+    assert!(status.success());
+}
+
+#[test]
+fn stream() {
+    task::block_on(async {
+        let mut a = signal(SignalKind::user_defined1()).unwrap();
+        let mut b = signal(SignalKind::user_defined1()).unwrap();
+
+        for _ in 0..3 {
+            raise("USR1").await;
+            let dur = Duration::from_secs(5);
+            future::timeout(dur, a.next()).await.unwrap();
+            future::timeout(dur, b.next()).await.unwrap();
+        }
+    })
+}
+
+#[test]
+fn earlier_signals_are_not_yielded() {
+    task::block_on(async {
+        let mut first = signal(SignalKind::user_defined2()).unwrap();
+        raise("USR2").await;
+        first.next().await;
+
+        let mut s = signal(SignalKind::user_defined2()).unwrap();
+        let res = future::timeout(Duration::from_millis(100), s.next()).await;
+        assert!(res.is_err());
+    })
+}
+
+#[test]
+fn ctrl_c_signal() {
+    task::block_on(async {
+        let handle = task::spawn(ctrl_c());
+        task::sleep(Duration::from_millis(100)).await;
+
+        raise("INT").await;
+        future::timeout(Duration::from_secs(5), handle)
+            .await
+            .unwrap()
+            .unwrap();
+    })
+}
+
+#[test]
+fn forbidden() {
+    for &signum in &[9, 19] {
+        let err = signal(SignalKind::from_raw(signum)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,31 @@
+#![cfg(feature = "unstable")]
+
+use std::thread;
+use std::time::{Duration, Instant};
+
+use async_std::task;
+
+#[test]
+fn smoke() {
+    task::block_on(async {
+        let res = task::spawn_blocking(|| 1 + 2).await;
+        assert_eq!(res, 3);
+    })
+}
+
+#[test]
+fn runs_concurrently() {
+    task::block_on(async {
+        let start = Instant::now();
+
+        // The pool grows so that the tasks don't wait for each other.
+        let handles: Vec<_> = (0..32)
+            .map(|_| task::spawn_blocking(|| thread::sleep(Duration::from_millis(200))))
+            .collect();
+        for handle in handles {
+            handle.await;
+        }
+
+        assert!(start.elapsed() < Duration::from_secs(3));
+    })
+}
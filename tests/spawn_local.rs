@@ -0,0 +1,97 @@
+#![cfg(feature = "unstable")]
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use async_std::net::{TcpListener, TcpStream};
+use async_std::prelude::*;
+use async_std::task;
+
+#[test]
+fn smoke() {
+    task::block_on(async {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let l = log.clone();
+        let a = task::spawn_local(async move {
+            task::sleep(Duration::from_millis(20)).await;
+            l.borrow_mut().push("a");
+        });
+        let l = log.clone();
+        let b = task::spawn_local(async move {
+            l.borrow_mut().push("b");
+        });
+
+        a.await;
+        b.await;
+        assert_eq!(*log.borrow(), vec!["b", "a"]);
+    })
+}
+
+#[test]
+fn current_task() {
+    task::block_on(async {
+        let id = task::current().id();
+        let child = task::spawn_local(async { task::current().id() }).await;
+
+        // The task of `block_on` is restored after running a local task.
+        assert_ne!(child, id);
+        assert_eq!(task::current().id(), id);
+    })
+}
+
+#[test]
+fn io() -> std::io::Result<()> {
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        let server = task::spawn_local(async move {
+            let (mut stream, _) = listener.accept().await?;
+            let mut buf = [0; 5];
+            stream.read_exact(&mut buf).await?;
+            stream.write_all(&buf).await?;
+            std::io::Result::Ok(())
+        });
+
+        let mut stream = TcpStream::connect(&addr).await?;
+        stream.write_all(b"hello").await?;
+        let mut buf = [0; 5];
+        stream.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"hello");
+
+        server.await
+    })
+}
+
+#[test]
+fn cancel() {
+    task::block_on(async {
+        let dropped = Rc::new(RefCell::new(false));
+
+        struct SetOnDrop(Rc<RefCell<bool>>);
+
+        impl Drop for SetOnDrop {
+            fn drop(&mut self) {
+                *self.0.borrow_mut() = true;
+            }
+        }
+
+        let d = SetOnDrop(dropped.clone());
+        let handle = task::spawn_local(async move {
+            let _d = d;
+            task::sleep(Duration::from_secs(60)).await;
+        });
+        task::sleep(Duration::from_millis(10)).await;
+
+        assert_eq!(handle.cancel().await, None);
+        assert!(*dropped.borrow());
+    })
+}
+
+#[test]
+#[should_panic(expected = "outside of `task::block_on()`")]
+fn outside_block_on() {
+    drop(task::spawn_local(async {}));
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "unstable")]
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use async_std::prelude::*;
+use async_std::stream;
+use async_std::task;
+
+#[test]
+fn throttle_spaces_items() {
+    task::block_on(async {
+        let start = Instant::now();
+        let v: Vec<i32> = stream::repeat(7)
+            .take(4)
+            .throttle(Duration::from_millis(20))
+            .collect()
+            .await;
+
+        assert_eq!(v, vec![7, 7, 7, 7]);
+        assert!(start.elapsed() >= Duration::from_millis(60));
+    })
+}
+
+#[test]
+fn throttle_yields_first_item_immediately() {
+    task::block_on(async {
+        let start = Instant::now();
+        let mut s = stream::once(1).throttle(Duration::from_secs(10));
+
+        assert_eq!(s.next().await, Some(1));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    })
+}
+
+#[test]
+fn throttle_does_not_drop_items() {
+    task::block_on(async {
+        let s: VecDeque<i32> = (1..=5).collect();
+        let v: Vec<i32> = s
+            .throttle(Duration::from_millis(1))
+            .filter(|n| n % 2 == 1)
+            .map(|n| n * 10)
+            .collect()
+            .await;
+
+        assert_eq!(v, vec![10, 30, 50]);
+    })
+}
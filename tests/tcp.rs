@@ -49,3 +49,42 @@ fn incoming_read() -> io::Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn buffered_lines() -> io::Result<()> {
+    task::block_on(async {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+
+        // An echo server that answers each line with its length.
+        task::spawn(async move {
+            let stream = listener.accept().await?.0;
+            let mut lines = io::BufReader::new(&stream).lines();
+            let mut writer = io::BufWriter::new(&stream);
+
+            while let Some(line) = lines.next().await {
+                writer
+                    .write_all(format!("{}\n", line?.len()).as_bytes())
+                    .await?;
+                writer.flush().await?;
+            }
+            io::Result::Ok(())
+        });
+
+        let stream = TcpStream::connect(&addr).await?;
+        let mut reader = io::BufReader::new(&stream);
+        let mut writer = io::BufWriter::new(&stream);
+        let mut line = String::new();
+
+        for msg in &["hello", "async world", ""] {
+            writer.write_all(format!("{}\n", msg).as_bytes()).await?;
+            writer.flush().await?;
+
+            line.clear();
+            reader.read_line(&mut line).await?;
+            assert_eq!(line, format!("{}\n", msg.len()));
+        }
+
+        Ok(())
+    })
+}
@@ -0,0 +1,23 @@
+use std::env;
+use std::thread;
+
+use async_std::task;
+
+#[test]
+fn single_worker_thread() {
+    // The variable is read when the first task is spawned, and this is the only test in the
+    // binary, so no task has been spawned yet.
+    env::set_var("ASYNC_STD_THREAD_COUNT", "1");
+
+    task::block_on(async {
+        let mut handles = Vec::new();
+        for _ in 0..100 {
+            handles.push(task::spawn(async { thread::current().id() }));
+        }
+
+        let first = handles.remove(0).await;
+        for h in handles {
+            assert_eq!(h.await, first);
+        }
+    })
+}
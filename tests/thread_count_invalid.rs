@@ -0,0 +1,14 @@
+use std::env;
+
+use async_std::task;
+
+#[test]
+fn invalid_thread_count() {
+    // The variable is read when the first task is spawned, and this is the only test in the
+    // binary, so no task has been spawned yet.
+    env::set_var("ASYNC_STD_THREAD_COUNT", "zero");
+
+    task::block_on(async {
+        assert_eq!(task::spawn(async { 1 + 2 }).await, 3);
+    })
+}
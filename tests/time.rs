@@ -0,0 +1,96 @@
+#![cfg(feature = "unstable")]
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::stream;
+use async_std::task;
+use async_std::time::{interval, interval_at, sleep_until, Duration, Instant, MissedTickBehavior};
+
+fn ms(n: u64) -> Duration {
+    Duration::from_millis(n)
+}
+
+#[test]
+fn sleep_until_deadline() {
+    task::block_on(async {
+        let deadline = Instant::now() + ms(20);
+        sleep_until(deadline).await;
+        assert!(Instant::now() >= deadline);
+
+        // A deadline in the past completes right away.
+        sleep_until(Instant::now() - ms(20)).await;
+    })
+}
+
+#[test]
+fn interval_ticks() {
+    task::block_on(async {
+        let start = Instant::now();
+        let ticks: Vec<Instant> = interval_at(start, ms(10)).take(3).collect().await;
+        assert_eq!(ticks, vec![start, start + ms(10), start + ms(20)]);
+        assert!(Instant::now() >= start + ms(20));
+    })
+}
+
+#[test]
+fn missed_ticks() {
+    task::block_on(async {
+        let start = Instant::now();
+
+        // The ticks at 0, 50 and 100 are missed, and the next one is due at 150.
+        let mut burst = interval_at(start, ms(50));
+        let mut delay = interval_at(start, ms(50));
+        delay.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut skip = interval_at(start, ms(50));
+        skip.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        task::sleep(ms(120)).await;
+
+        assert_eq!(burst.tick().await, start);
+        assert_eq!(burst.tick().await, start + ms(50));
+        assert_eq!(burst.tick().await, start + ms(100));
+
+        assert_eq!(skip.tick().await, start);
+        assert_eq!(skip.tick().await, start + ms(150));
+
+        assert_eq!(delay.tick().await, start);
+        let next = delay.tick().await;
+        assert!(next >= start + ms(170));
+    })
+}
+
+#[test]
+fn interval_reset() {
+    task::block_on(async {
+        let mut interval = interval(ms(10));
+        task::sleep(ms(30)).await;
+        interval.reset();
+
+        let before = Instant::now();
+        let tick = interval.tick().await;
+        assert!(tick >= before);
+        assert!(Instant::now() >= before + ms(10));
+    })
+}
+
+#[test]
+fn future_timeout() {
+    task::block_on(async {
+        assert!(future::pending::<()>().timeout(ms(10)).await.is_err());
+        assert_eq!(future::ready(1).timeout(ms(10)).await, Ok(1));
+    })
+}
+
+#[test]
+fn stream_timeout() {
+    task::block_on(async {
+        let s = stream::once(1).chain(stream::from_fn(|| async {
+            task::sleep(ms(30)).await;
+            Some(2)
+        }));
+        let mut s = Box::pin(s.timeout(ms(20)));
+
+        assert_eq!(s.next().await, Some(Ok(1)));
+        assert!(s.next().await.unwrap().is_err());
+        assert_eq!(s.next().await, Some(Ok(2)));
+    })
+}
@@ -27,3 +27,38 @@ fn send_recv() -> io::Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn send_to_recv_from() -> io::Result<()> {
+    task::block_on(async {
+        let socket1 = UdpSocket::bind("127.0.0.1:0").await?;
+        let socket2 = UdpSocket::bind("127.0.0.1:0").await?;
+
+        socket1
+            .send_to(THE_MERCHANT_OF_VENICE, socket2.local_addr()?)
+            .await?;
+
+        let mut buf = [0u8; 1024];
+        let (n, peer) = socket2.recv_from(&mut buf).await?;
+        assert_eq!(&buf[..n], THE_MERCHANT_OF_VENICE);
+        assert_eq!(peer, socket1.local_addr()?);
+        assert!(socket2.take_error()?.is_none());
+
+        Ok(())
+    })
+}
+
+#[test]
+fn multicast_options() -> io::Result<()> {
+    task::block_on(async {
+        let socket = UdpSocket::bind("127.0.0.1:0").await?;
+
+        socket.set_multicast_ttl_v4(4)?;
+        assert_eq!(socket.multicast_ttl_v4()?, 4);
+
+        socket.set_multicast_loop_v4(false)?;
+        assert!(!socket.multicast_loop_v4()?);
+
+        Ok(())
+    })
+}
@@ -94,3 +94,38 @@ async fn ping_pong_client(socket: &std::path::PathBuf, iterations: u32) -> std::
     }
     Ok(())
 }
+
+#[test]
+fn stream_pair() -> io::Result<()> {
+    task::block_on(async {
+        let (mut a, mut b) = UnixStream::pair()?;
+        a.write_all(JULIUS_CAESAR).await?;
+        a.shutdown(std::net::Shutdown::Write)?;
+
+        let mut buf = Vec::new();
+        b.read_to_end(&mut buf).await?;
+        assert_eq!(buf, JULIUS_CAESAR);
+
+        Ok(())
+    })
+}
+
+#[test]
+fn datagram_send_to_recv_from() -> io::Result<()> {
+    let tmp_dir = TempDir::new("datagram_send_to_recv_from")?;
+    let path1 = tmp_dir.as_ref().join("sock1");
+    let path2 = tmp_dir.as_ref().join("sock2");
+
+    task::block_on(async {
+        let socket1 = UnixDatagram::bind(&path1).await?;
+        let socket2 = UnixDatagram::bind(&path2).await?;
+        socket1.send_to(JULIUS_CAESAR, &path2).await?;
+
+        let mut buf = vec![0; 1024];
+        let (n, addr) = socket2.recv_from(&mut buf).await?;
+        assert_eq!(&buf[..n], JULIUS_CAESAR);
+        assert_eq!(addr.as_pathname(), Some(path1.as_ref()));
+
+        Ok(())
+    })
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "unstable")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::sync::WaitGroup;
+use async_std::task::{self, Poll};
+
+#[test]
+fn waits_for_all_clones() {
+    task::block_on(async {
+        let wg = WaitGroup::new();
+        let done = Arc::new(AtomicUsize::new(0));
+
+        for i in 0..10 {
+            let wg = wg.clone();
+            let done = done.clone();
+            task::spawn(async move {
+                task::sleep(Duration::from_millis(i * 5)).await;
+                done.fetch_add(1, Ordering::SeqCst);
+                drop(wg);
+            });
+        }
+
+        wg.wait().await;
+        assert_eq!(done.load(Ordering::SeqCst), 10);
+    })
+}
+
+#[test]
+fn pending_until_last_drop() {
+    task::block_on(async {
+        let wg = WaitGroup::new();
+        let wg2 = wg.clone();
+        let wg3 = wg.clone();
+        assert_eq!(wg2.count(), 3);
+
+        let mut wait = Box::pin(wg.wait());
+        future::poll_fn(|cx| {
+            assert!(wait.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+
+        drop(wg2);
+        future::poll_fn(|cx| {
+            assert!(wait.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+
+        drop(wg3);
+        wait.await;
+    })
+}
+
+#[test]
+fn alone() {
+    task::block_on(async {
+        WaitGroup::new().wait().await;
+    })
+}
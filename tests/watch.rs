@@ -0,0 +1,63 @@
+#![cfg(feature = "unstable")]
+
+use async_std::future;
+use async_std::prelude::*;
+use async_std::sync::watch::{self, RecvError};
+use async_std::task::{self, Poll};
+
+#[test]
+fn smoke() {
+    task::block_on(async {
+        let (s, mut r) = watch::channel(0);
+
+        let mut changed = Box::pin(async move {
+            r.changed().await.unwrap();
+            let value = *r.borrow();
+            value
+        });
+        future::poll_fn(|cx| {
+            assert!(changed.as_mut().poll(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+
+        s.send(1);
+        assert_eq!(changed.await, 1);
+    })
+}
+
+#[test]
+fn latest_value_wins() {
+    task::block_on(async {
+        let (s, mut r1) = watch::channel(0);
+        let mut r2 = r1.clone();
+
+        for i in 1..=10 {
+            s.send(i);
+        }
+
+        assert_eq!(r1.recv().await, Ok(10));
+        assert!(!r1.has_changed());
+        assert!(r2.has_changed());
+        assert_eq!(r2.recv().await, Ok(10));
+        assert_eq!(*s.borrow(), 10);
+    })
+}
+
+#[test]
+fn sender_dropped() {
+    task::block_on(async {
+        let (s, mut r) = watch::channel("a");
+        let mut r2 = s.subscribe();
+        assert_eq!(s.receiver_count(), 2);
+
+        s.send("b");
+        drop(s);
+
+        // The last value is still delivered before the error.
+        assert_eq!(r.recv().await, Ok("b"));
+        assert_eq!(r.changed().await, Err(RecvError));
+        assert_eq!(r2.recv().await, Ok("b"));
+        assert_eq!(*r2.borrow(), "b");
+    })
+}